@@ -10,15 +10,18 @@ pub struct VoteDetail {
     pub amount: u128,
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Serialize)]
-#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+/// An independent lock of lpt with its own unlock schedule.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, Default)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize, PartialEq))]
 #[serde(crate = "near_sdk::serde")]
-pub struct Account {
-    pub sponsor_id: AccountId,
+pub struct LockPosition {
+    /// Which lptoken locked in
+    pub token_id: String,
     /// The amount of LPT locked
     #[serde(with = "u128_dec_format")]
     pub lpt_amount: Balance,
-    /// The amount of veLPT the account holds
+    /// The amount of veLPT the position got when locking, backed by love token.
+    /// Use get_ve_lpt_amount to get the current one under decay.
     #[serde(with = "u128_dec_format")]
     pub ve_lpt_amount: Balance,
     /// When the locking token can be unlocked without slash in nanoseconds.
@@ -26,6 +29,215 @@ pub struct Account {
     pub unlock_timestamp: u64,
     /// The duration of current locking in seconds.
     pub duration_sec: u32,
+    /// The veLPT decreasing per second until unlock, 0 if the lock doesn't decay.
+    #[serde(with = "u128_dec_format")]
+    pub ve_slope: Balance,
+    /// The slashed_lpt_per_share of the lock pool when last settled
+    #[serde(with = "u128_dec_format")]
+    pub slashed_lpt_per_share_paid: Balance,
+    /// While set, the position is treated as always having max_locking_duration_sec remaining,
+    /// it never decays and can not be withdrawn.
+    pub auto_relock: bool,
+}
+
+impl LockPosition {
+    /// The current veLPT, ve_lpt_amount minus what has decayed since locking.
+    pub fn get_ve_lpt_amount(&self, timestamp: Timestamp) -> Balance {
+        if self.ve_slope == 0 {
+            return self.ve_lpt_amount;
+        }
+        let remaining_sec = std::cmp::min(nano_to_sec(self.unlock_timestamp).saturating_sub(nano_to_sec(timestamp)), self.duration_sec);
+        self.ve_lpt_amount - self.ve_slope * (self.duration_sec - remaining_sec) as u128
+    }
+
+    pub fn get_ve_state(&self, timestamp: Timestamp) -> VeState {
+        VeState {
+            ve_lpt_amount: self.get_ve_lpt_amount(timestamp),
+            slope: self.ve_slope,
+            end_sec: nano_to_sec(self.unlock_timestamp),
+        }
+    }
+
+    /// The remaining locking time in seconds.
+    pub fn get_remaining_sec(&self, timestamp: Timestamp, config: &Config) -> u32 {
+        if self.auto_relock {
+            config.max_locking_duration_sec
+        } else {
+            nano_to_sec(self.unlock_timestamp).saturating_sub(nano_to_sec(timestamp))
+        }
+    }
+
+    /// A decaying lock is cut to unlock at the start of a DECAY_PERIOD_SEC period,
+    /// so the total veLPT only changes its slope once a period, but never earlier than it unlocks now.
+    fn round_decay_duration_sec(&self, duration_sec: u32, timestamp: Timestamp, config: &Config) -> u32 {
+        if self.auto_relock || config.decay_mode == DecayMode::Disabled {
+            return duration_sec;
+        }
+        let now_sec = nano_to_sec(timestamp);
+        let unlock_sec = std::cmp::max(
+            (now_sec + duration_sec) / DECAY_PERIOD_SEC * DECAY_PERIOD_SEC,
+            nano_to_sec(self.unlock_timestamp),
+        );
+        if unlock_sec > now_sec {
+            unlock_sec - now_sec
+        } else {
+            duration_sec
+        }
+    }
+
+    /// Lock more lpt or relock for a longer duration, return (increased love, decreased love).
+    /// A relock starts from the current veLPT rather than the one at the last locking,
+    /// so the love of what has decayed is burnt.
+    pub fn lock_lpt(&mut self, amount: Balance, duration_sec: u32, config: &Config, lock_pool: &LockPool) -> (Balance, Balance) {
+        let prev = self.ve_lpt_amount;
+        let duration_sec = if self.auto_relock { config.max_locking_duration_sec } else { duration_sec };
+
+        let timestamp = env::block_timestamp();
+        let duration_sec = self.round_decay_duration_sec(duration_sec, timestamp, config);
+        let new_unlock_timestamp = timestamp + to_nano(duration_sec);
+
+        if self.unlock_timestamp > 0 && self.unlock_timestamp > timestamp {
+            // exist lpt locked need relock
+            require!(nano_to_sec(self.unlock_timestamp) <= nano_to_sec(new_unlock_timestamp), E304_CAUSE_PRE_UNLOCK);
+            let relocked_ve = compute_ve_lpt_amount(config, lock_pool, self.lpt_amount, duration_sec);
+            self.ve_lpt_amount = std::cmp::max(self.get_ve_lpt_amount(timestamp), relocked_ve);
+            let extra_x = compute_ve_lpt_amount(config, lock_pool, amount, duration_sec);
+            self.ve_lpt_amount += extra_x;
+        } else {
+            self.ve_lpt_amount = compute_ve_lpt_amount(config, lock_pool, self.lpt_amount + amount, duration_sec);
+        }
+        self.unlock_timestamp = new_unlock_timestamp;
+        self.lpt_amount += amount;
+        self.duration_sec = duration_sec;
+        self.reset_ve_slope(config, lock_pool);
+
+        (self.ve_lpt_amount.saturating_sub(prev), prev.saturating_sub(self.ve_lpt_amount))
+    }
+
+    /// Enable or disable auto relock, return (increased love, decreased love).
+    /// Enabling relocks the position with the max duration,
+    /// disabling starts a normal countdown of the max duration from now on.
+    pub fn set_auto_relock(&mut self, auto_relock: bool, config: &Config, lock_pool: &LockPool) -> (Balance, Balance) {
+        if auto_relock {
+            self.auto_relock = true;
+            self.lock_lpt(0, config.max_locking_duration_sec, config, lock_pool)
+        } else {
+            self.auto_relock = false;
+            let timestamp = env::block_timestamp();
+            // the countdown starts over regardless of the relocked unlock time
+            self.unlock_timestamp = timestamp;
+            let duration_sec = self.round_decay_duration_sec(config.max_locking_duration_sec, timestamp, config);
+            self.unlock_timestamp = timestamp + to_nano(duration_sec);
+            self.duration_sec = duration_sec;
+            self.reset_ve_slope(config, lock_pool);
+            (0, 0)
+        }
+    }
+
+    /// The whole lock restarts decaying from now on.
+    fn reset_ve_slope(&mut self, config: &Config, lock_pool: &LockPool) {
+        let ve_lpt_floor = if self.auto_relock {
+            self.ve_lpt_amount
+        } else {
+            match config.decay_mode {
+                DecayMode::Disabled => self.ve_lpt_amount,
+                DecayMode::ToBase => std::cmp::min(compute_ve_lpt_amount(config, lock_pool, self.lpt_amount, 0), self.ve_lpt_amount),
+                DecayMode::ToZero => 0,
+            }
+        };
+        self.ve_slope = (self.ve_lpt_amount - ve_lpt_floor) / self.duration_sec as u128;
+    }
+
+    pub fn withdraw_lpt(&mut self, amount: u128) -> Balance {
+        require!(!self.auto_relock, E110_AUTO_RELOCKED);
+        let timestamp = env::block_timestamp();
+        require!(timestamp >= self.unlock_timestamp, E305_STILL_IN_LOCK);
+        self.remove_lpt(amount)
+    }
+
+    /// Withdraw lpt before unlock_timestamp, 
+    /// return (decreased ve lpt, slashed lpt in the amount)
+    pub fn withdraw_lpt_early(&mut self, amount: u128, config: &Config) -> (Balance, Balance) {
+        require!(!self.auto_relock, E110_AUTO_RELOCKED);
+        let timestamp = env::block_timestamp();
+        let remaining_sec = if self.unlock_timestamp > timestamp {
+            std::cmp::min(nano_to_sec(self.unlock_timestamp - timestamp), config.max_locking_duration_sec)
+        } else {
+            0
+        };
+        let slashed = u128_ratio(
+            amount,
+            u128::from(config.max_early_unlock_penalty_rate) * u128::from(remaining_sec),
+            u128::from(MAX_EARLY_UNLOCK_PENALTY_RATE) * u128::from(config.max_locking_duration_sec),
+        );
+        (self.remove_lpt(amount), slashed)
+    }
+
+    /// Split off a slice backed by ve_lpt_amount of love token, with lpt and slope in proportion.
+    pub fn split(&mut self, ve_lpt_amount: Balance) -> LockPosition {
+        let mut slice = self.clone();
+        slice.lpt_amount = u128_ratio(self.lpt_amount, ve_lpt_amount, self.ve_lpt_amount);
+        slice.ve_lpt_amount = ve_lpt_amount;
+        slice.ve_slope = u128_ratio(self.ve_slope, ve_lpt_amount, self.ve_lpt_amount);
+        self.lpt_amount -= slice.lpt_amount;
+        self.ve_lpt_amount -= slice.ve_lpt_amount;
+        self.ve_slope -= slice.ve_slope;
+        slice
+    }
+
+    /// Move slashed lpt shared to lockers since last settlement into the position,
+    /// with the veLPT and slope in proportion, return (settled lpt, increased love).
+    pub fn settle_slashed_lpt(&mut self, slashed_lpt_per_share: Balance) -> (Balance, Balance) {
+        let settled = u128_ratio(
+            self.lpt_amount,
+            slashed_lpt_per_share - self.slashed_lpt_per_share_paid,
+            SLASHED_LPT_PER_SHARE_PRECISION,
+        );
+        self.slashed_lpt_per_share_paid = slashed_lpt_per_share;
+        if settled == 0 {
+            return (0, 0);
+        }
+        let increased_love = u128_ratio(self.ve_lpt_amount, settled, self.lpt_amount);
+        self.ve_slope += u128_ratio(self.ve_slope, settled, self.lpt_amount);
+        self.ve_lpt_amount += increased_love;
+        self.lpt_amount += settled;
+        (settled, increased_love)
+    }
+
+    fn remove_lpt(&mut self, amount: u128) -> Balance {
+        let prev = self.ve_lpt_amount;
+
+        require!(amount <= self.lpt_amount && amount != 0, E101_INSUFFICIENT_BALANCE);
+
+        if amount < self.lpt_amount {
+            let new_ve = u128_ratio(self.ve_lpt_amount, self.lpt_amount - amount, self.lpt_amount);
+            self.ve_lpt_amount = new_ve;
+            self.ve_slope = u128_ratio(self.ve_slope, self.lpt_amount - amount, self.lpt_amount);
+        } else {
+            self.ve_lpt_amount = 0;
+            self.ve_slope = 0;
+            self.unlock_timestamp = 0;
+            self.duration_sec = 0;
+        }
+        self.lpt_amount -= amount;
+
+        prev - self.ve_lpt_amount
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct Account {
+    pub sponsor_id: AccountId,
+    /// Whether sponsor_id paid the storage, which is refunded on unregister
+    pub storage_paid: bool,
+    /// NEAR deposited beyond the registration, paying the storage of the proposals
+    /// created by transfer call or grown by update, the rest is refunded on withdraw or unregister
+    #[serde(with = "u128_dec_format")]
+    pub storage_available: Balance,
+    /// Lock positions keyed by position id
+    pub locks: HashMap<u32, LockPosition>,
     /// Record voting info
     #[serde(skip_serializing)]
     pub proposals: HashMap<u32, VoteDetail>,
@@ -34,16 +246,43 @@ pub struct Account {
     pub proposals_history: UnorderedMap<u32, VoteDetail>,
     #[serde(with = "u128_map_format")]
     pub rewards: HashMap<AccountId, Balance>,
+    /// The account this one delegates its veLPT to
+    pub delegatee: Option<AccountId>,
+    /// The veLPT delegated to delegatee, counted when delegating and following the lock changes since then
+    #[serde(with = "u128_dec_format")]
+    pub delegated_ve_lpt: Balance,
+    /// The veLPT delegated to this account by others
+    #[serde(with = "u128_dec_format")]
+    pub received_ve_lpt: Balance,
+    /// The received veLPT withheld from each proposal, as the delegators have voted on it directly
+    #[serde(skip_serializing)]
+    pub delegation_overrides: HashMap<u32, Balance>,
+    /// veLPT after each change of the locks, a ring of the latest MAX_ACCOUNT_VE_CHECKPOINTS ordered by time
+    #[serde(skip_serializing)]
+    pub ve_checkpoints: Vector<AccountVeCheckpoint>,
+    /// The number of oldest checkpoints overwritten
+    #[serde(skip_serializing)]
+    pub ve_checkpoints_dropped: u64,
+    /// veLPT of a legacy account before the upgrade, seeded into ve_checkpoints when it is saved
+    #[borsh_skip]
+    #[serde(skip_serializing)]
+    pub legacy_ve_lpt: Option<Balance>,
 }
 
+// loaded into the current one right away, boxing it would need a manual BorshDeserialize
+#[allow(clippy::large_enum_variant)]
 #[derive(BorshSerialize, BorshDeserialize)]
 pub enum VAccount {
+    V0200(AccountV0200),
     Current(Account),
 }
 
-impl From<VAccount> for Account {
-    fn from(v: VAccount) -> Self {
-        match v {
+impl VAccount {
+    /// Upgrades from other versions to the currently used version,
+    /// legacy locks belong to the lptoken of lptoken_id.
+    pub fn into_current(self, account_id: &AccountId, lptoken_id: &str) -> Account {
+        match self {
+            VAccount::V0200(c) => c.into_current(account_id, lptoken_id),
             VAccount::Current(c) => c,
         }
     }
@@ -59,13 +298,19 @@ impl Account {
     pub fn new(account_id: &AccountId, sponsor_id: &AccountId) -> Self {
         Account {
             sponsor_id: sponsor_id.clone(),
-            lpt_amount: 0,
-            ve_lpt_amount: 0,
-            unlock_timestamp: 0,
-            duration_sec: 0,
+            storage_paid: false,
+            storage_available: 0,
+            locks: HashMap::new(),
             proposals: HashMap::new(),
             proposals_history: UnorderedMap::new(StorageKeys::AccountProposalHistory { account_id: account_id.clone() }),
-            rewards: HashMap::new()
+            rewards: HashMap::new(),
+            delegatee: None,
+            delegated_ve_lpt: 0,
+            received_ve_lpt: 0,
+            delegation_overrides: HashMap::new(),
+            ve_checkpoints: Vector::new(StorageKeys::AccountVeCheckpoints { account_id: account_id.clone() }),
+            ve_checkpoints_dropped: 0,
+            legacy_ve_lpt: None,
         }
     }
 
@@ -94,47 +339,61 @@ impl Account {
         }
     }
 
-    pub fn lock_lpt(&mut self, amount: Balance, duration_sec: u32, config: &Config, lptoken_decimals: u8) -> Balance {
-        let prev = self.ve_lpt_amount;
+    /// The current veLPT summed over all positions.
+    pub fn get_ve_lpt_amount(&self, timestamp: Timestamp) -> Balance {
+        self.locks.values().map(|lock| lock.get_ve_lpt_amount(timestamp)).sum()
+    }
 
-        let timestamp = env::block_timestamp();
-        let new_unlock_timestamp = timestamp + to_nano(duration_sec);
+    pub fn get_lock(&self, lock_id: u32) -> Option<&LockPosition> {
+        self.locks.get(&lock_id)
+    }
 
-        if self.unlock_timestamp > 0 && self.unlock_timestamp > timestamp {
-            // exist lpt locked need relock
-            require!(nano_to_sec(self.unlock_timestamp) <= nano_to_sec(new_unlock_timestamp), E304_CAUSE_PRE_UNLOCK);
-            let relocked_ve = compute_ve_lpt_amount(config, self.lpt_amount, duration_sec, lptoken_decimals);
-            self.ve_lpt_amount = std::cmp::max(self.ve_lpt_amount, relocked_ve);
-            let extra_x = compute_ve_lpt_amount(config, amount, duration_sec, lptoken_decimals);
-            self.ve_lpt_amount += extra_x;
+    pub fn unwrap_lock(&self, lock_id: u32) -> &LockPosition {
+        self.get_lock(lock_id).expect(E105_ACC_NOT_LOCKED)
+    }
+
+    /// The veLPT this account votes with on proposal_id, its own plus the received one not overridden.
+    pub fn get_voting_ve_lpt_amount(&self, proposal_id: u32, timestamp: Timestamp) -> Balance {
+        let overridden = self.delegation_overrides.get(&proposal_id).unwrap_or(&0);
+        self.get_ve_lpt_amount(timestamp) + self.received_ve_lpt.saturating_sub(*overridden)
+    }
+
+    pub fn update_received_ve_lpt(&mut self, amount: Balance, is_increased: bool) {
+        if is_increased {
+            self.received_ve_lpt += amount;
         } else {
-            self.ve_lpt_amount = compute_ve_lpt_amount(config, self.lpt_amount + amount, duration_sec, lptoken_decimals);
+            self.received_ve_lpt = self.received_ve_lpt.saturating_sub(amount);
         }
-        self.unlock_timestamp = new_unlock_timestamp;
-        self.lpt_amount += amount;
-        self.duration_sec = duration_sec;
-
-        self.ve_lpt_amount - prev
     }
 
-    pub fn withdraw_lpt(&mut self, amount: u128) -> Balance {
-        let prev = self.ve_lpt_amount;
+    pub fn update_delegation_override(&mut self, proposal_id: u32, amount: Balance, is_increased: bool) {
+        let prev = self.delegation_overrides.remove(&proposal_id).unwrap_or(0);
+        let overridden = if is_increased { prev + amount } else { prev.saturating_sub(amount) };
+        if overridden > 0 {
+            self.delegation_overrides.insert(proposal_id, overridden);
+        }
+    }
 
-        let timestamp = env::block_timestamp();
-        require!(timestamp >= self.unlock_timestamp, E305_STILL_IN_LOCK);
-        require!(amount <= self.lpt_amount && amount != 0, E101_INSUFFICIENT_BALANCE);
+    /// The smallest position id not in use.
+    pub fn next_lock_id(&self) -> u32 {
+        (0..).find(|lock_id| !self.locks.contains_key(lock_id)).unwrap()
+    }
 
-        if amount < self.lpt_amount {
-            let new_ve = u128_ratio(self.ve_lpt_amount, self.lpt_amount - amount, self.lpt_amount);
-            self.ve_lpt_amount = new_ve;
+    /// Take the position out to operate on, a new one would be created if not exist.
+    pub fn take_lock(&mut self, lock_id: u32) -> LockPosition {
+        if let Some(lock) = self.locks.remove(&lock_id) {
+            lock
         } else {
-            self.ve_lpt_amount = 0;
-            self.unlock_timestamp = 0;
-            self.duration_sec = 0;
+            require!(self.locks.len() < MAX_LOCK_POSITIONS, E107_TOO_MANY_LOCK_POSITIONS);
+            LockPosition::default()
         }
-        self.lpt_amount -= amount;
+    }
 
-        prev - self.ve_lpt_amount
+    /// Put the position back, the empty one would be dropped.
+    pub fn put_lock(&mut self, lock_id: u32, lock: LockPosition) {
+        if lock.lpt_amount > 0 {
+            self.locks.insert(lock_id, lock);
+        }
     }
 }
 
@@ -142,9 +401,13 @@ impl Contract {
     pub fn update_impacted_proposals(&mut self, account: &mut Account, diff_ve_lpt_amount: Balance, is_increased: bool){
         let mut rewards = HashMap::new();
         let mut history = HashMap::new();
+        // votes left by decay would be cleared once all lpt withdrawn
+        let is_exhausted = !is_increased && account.locks.is_empty();
+        let direct_voted: Vec<u32> = account.proposals.keys().cloned().collect();
         account.proposals.retain(|proposal_id, vote_detail| {
             let mut proposal = self.internal_unwrap_proposal(*proposal_id);
-            if proposal.status == Some(ProposalStatus::Expired) {
+            if proposal.is_finished() {
+                self.internal_settle_proposal(&mut proposal);
                 if let Some(reward_details) = proposal.claim_reward(vote_detail) {
                     reward_details.into_iter().for_each(|(reward_token, reward_amount)| {
                         rewards.insert(reward_token.clone(), reward_amount + rewards.get(&reward_token).unwrap_or(&0_u128));
@@ -153,20 +416,30 @@ impl Contract {
                 self.internal_set_proposal(*proposal_id, proposal.into());
                 history.insert(*proposal_id, vote_detail.clone());
                 false
+            } else if proposal.voting_mode == VotingMode::Snapshot {
+                // votes are fixed at the snapshot
+                true
             } else {
                 let mut is_retain = true;
-                if diff_ve_lpt_amount > 0 {
-                    proposal.update_votes(&vote_detail.action, diff_ve_lpt_amount, is_increased);
+                if diff_ve_lpt_amount > 0 || is_exhausted {
                     if is_increased {
+                        proposal.update_votes(&vote_detail.action, vote_detail.amount, vote_detail.amount + diff_ve_lpt_amount);
                         vote_detail.amount += diff_ve_lpt_amount;
-                    } else if vote_detail.amount == diff_ve_lpt_amount {
-                        proposal.votes[vote_detail.action.get_index()].participants -= 1;
-                        proposal.participants -= 1;
-                        is_retain = false
                     } else {
-                        vote_detail.amount -= diff_ve_lpt_amount;
+                        let decreased = if is_exhausted {
+                            vote_detail.amount
+                        } else {
+                            std::cmp::min(vote_detail.amount, diff_ve_lpt_amount)
+                        };
+                        proposal.update_votes(&vote_detail.action, vote_detail.amount, vote_detail.amount - decreased);
+                        if vote_detail.amount == decreased {
+                            proposal.update_participants(&vote_detail.action, false);
+                            is_retain = false
+                        } else {
+                            vote_detail.amount -= decreased;
+                        }
                     }
-                    proposal.ve_amount_at_last_action = self.data().cur_total_ve_lpt;
+                    proposal.ve_amount_at_last_action = self.internal_cur_total_ve_lpt();
                     self.internal_set_proposal(*proposal_id, proposal.into());
                 }
                 is_retain
@@ -174,6 +447,87 @@ impl Contract {
         });
         account.add_rewards(&rewards);
         account.add_history(&history);
+
+        // the delegated veLPT follows the lock changes
+        if let Some(delegatee_id) = account.delegatee.clone() {
+            let prev = account.delegated_ve_lpt;
+            account.delegated_ve_lpt = if is_increased {
+                prev + diff_ve_lpt_amount
+            } else if is_exhausted {
+                0
+            } else {
+                prev.saturating_sub(diff_ve_lpt_amount)
+            };
+            if account.delegated_ve_lpt >= prev {
+                self.internal_update_delegation(&delegatee_id, &direct_voted, account.delegated_ve_lpt - prev, true);
+            } else {
+                self.internal_update_delegation(&delegatee_id, &direct_voted, prev - account.delegated_ve_lpt, false);
+            }
+        }
+    }
+
+    /// The veLPT delegated to delegatee_id changed, let the delegatee's votes follow,
+    /// except on the proposals in direct_voted the delegator has voted on directly.
+    pub fn internal_update_delegation(&mut self, delegatee_id: &AccountId, direct_voted: &[u32], amount: Balance, is_increased: bool) {
+        if amount == 0 {
+            return;
+        }
+        if let Some(mut delegatee) = self.internal_get_account(delegatee_id) {
+            delegatee.update_received_ve_lpt(amount, is_increased);
+            delegatee.checkpoint_ve_lpt();
+            self.internal_prune_delegation_overrides(&mut delegatee);
+            for proposal_id in direct_voted {
+                delegatee.update_delegation_override(*proposal_id, amount, is_increased);
+            }
+            let delegated_votes: Vec<u32> = delegatee.proposals.keys().filter(|proposal_id| !direct_voted.contains(proposal_id)).cloned().collect();
+            for proposal_id in delegated_votes {
+                self.internal_update_delegated_vote(&mut delegatee, proposal_id, amount, is_increased);
+            }
+            self.internal_set_account(delegatee_id, delegatee);
+        }
+    }
+
+    /// The delegator voted on or cancelled proposal_id directly,
+    /// the delegated veLPT is withheld from or given back to the delegatee's vote.
+    pub fn internal_override_delegation(&mut self, delegatee_id: &AccountId, proposal_id: u32, amount: Balance, is_overridden: bool) {
+        if amount == 0 {
+            return;
+        }
+        if let Some(mut delegatee) = self.internal_get_account(delegatee_id) {
+            delegatee.update_delegation_override(proposal_id, amount, is_overridden);
+            if delegatee.proposals.contains_key(&proposal_id) {
+                self.internal_update_delegated_vote(&mut delegatee, proposal_id, amount, !is_overridden);
+            }
+            self.internal_set_account(delegatee_id, delegatee);
+        }
+    }
+
+    /// Drop the overrides on proposals no longer votable.
+    pub fn internal_prune_delegation_overrides(&self, account: &mut Account) {
+        account.delegation_overrides.retain(|proposal_id, _| {
+            self.internal_get_proposal(*proposal_id)
+                .map(|mut proposal| {
+                    proposal.update_status();
+                    !proposal.is_finished()
+                })
+                .unwrap_or(false)
+        });
+    }
+
+    fn internal_update_delegated_vote(&mut self, delegatee: &mut Account, proposal_id: u32, amount: Balance, is_increased: bool) {
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        if proposal.status == Some(ProposalStatus::InProgress) && proposal.voting_mode == VotingMode::Live {
+            let vote_detail = delegatee.proposals.get_mut(&proposal_id).unwrap();
+            let prev_amount = vote_detail.amount;
+            if is_increased {
+                vote_detail.amount += amount;
+            } else {
+                vote_detail.amount -= std::cmp::min(amount, vote_detail.amount);
+            }
+            proposal.update_votes(&vote_detail.action, prev_amount, vote_detail.amount);
+            proposal.ve_amount_at_last_action = self.internal_cur_total_ve_lpt();
+            self.internal_set_proposal(proposal_id, proposal);
+        }
     }
 
     pub fn internal_account_vote(
@@ -183,7 +537,12 @@ impl Contract {
         action: &Action,
     ) -> Balance {
         let mut account = self.internal_unwrap_account(voter);
-        let ve_lpt_amount = account.ve_lpt_amount;
+        let proposal = self.internal_unwrap_proposal(proposal_id);
+        let ve_lpt_amount = match proposal.voting_mode {
+            VotingMode::Live => account.get_voting_ve_lpt_amount(proposal_id, env::block_timestamp()),
+            // the delegation at the start goes with the snapshot and can not be overridden
+            VotingMode::Snapshot => account.get_snapshot_voting_ve_lpt_amount(nano_to_sec(proposal.start_at)).expect(E117_VE_LPT_HISTORY_DROPPED),
+        };
         require!(ve_lpt_amount > 0, E303_INSUFFICIENT_VE_LPT);
         require!(!account.proposals.contains_key(&proposal_id), E200_ALREADY_VOTED);
         account.proposals.insert(proposal_id, VoteDetail{
@@ -191,7 +550,16 @@ impl Contract {
             amount: ve_lpt_amount,
         });
         self.internal_claim_all(&mut account);
-        self.internal_set_account(voter, account.into());
+        self.internal_prune_delegation_overrides(&mut account);
+        // voting directly overrides the delegatee
+        match account.delegatee.clone() {
+            Some(delegatee_id) if proposal.voting_mode == VotingMode::Live => {
+                let delegated_ve_lpt = account.delegated_ve_lpt;
+                self.internal_set_account(voter, account);
+                self.internal_override_delegation(&delegatee_id, proposal_id, delegated_ve_lpt, true);
+            }
+            _ => self.internal_set_account(voter, account),
+        }
         ve_lpt_amount
     }
 
@@ -204,14 +572,62 @@ impl Contract {
         require!(account.proposals.contains_key(&proposal_id), E206_NO_VOTED);
         let action = account.proposals.remove(&proposal_id).unwrap();
         self.internal_claim_all(&mut account);
-        self.internal_set_account(voter, account.into());
+        let voting_mode = self.internal_unwrap_proposal(proposal_id).voting_mode;
+        match account.delegatee.clone() {
+            Some(delegatee_id) if voting_mode == VotingMode::Live => {
+                let delegated_ve_lpt = account.delegated_ve_lpt;
+                self.internal_set_account(voter, account);
+                self.internal_override_delegation(&delegatee_id, proposal_id, delegated_ve_lpt, false);
+            }
+            _ => self.internal_set_account(voter, account),
+        }
         action
     }
 }
 
 impl Contract {
+    /// Settle the slashed lpt shared to the locks of the account, minting the love token for the veLPT
+    /// in proportion, the votes follow the increased veLPT. The account should be saved after.
+    pub fn internal_settle_slashed_lpt(&mut self, account_id: &AccountId, account: &mut Account) {
+        let timestamp = env::block_timestamp();
+        let mut increased_ve_lpt = 0;
+        let mut increased_love = 0;
+        for lock in account.locks.values_mut() {
+            let mut lock_pool = self.internal_unwrap_lock_pool(&lock.token_id);
+            if lock.slashed_lpt_per_share_paid == lock_pool.slashed_lpt_per_share {
+                continue;
+            }
+            let prev_ve_state = lock.get_ve_state(timestamp);
+            let (settled, love) = lock.settle_slashed_lpt(lock_pool.slashed_lpt_per_share);
+            if settled == 0 {
+                continue;
+            }
+            let cur_ve_state = lock.get_ve_state(timestamp);
+            lock_pool.cur_lock_lpt += settled;
+            lock_pool.unsettled_slashed_lpt = lock_pool.unsettled_slashed_lpt.saturating_sub(settled);
+            self.internal_set_lock_pool(&lock_pool);
+            self.internal_sync_total_ve_lpt(&prev_ve_state, &cur_ve_state);
+            increased_ve_lpt += cur_ve_state.ve_lpt_amount - prev_ve_state.ve_lpt_amount;
+            increased_love += love;
+        }
+        if increased_love > 0 {
+            self.mint_love_token(account_id, increased_love);
+        }
+        if increased_ve_lpt > 0 {
+            self.update_impacted_proposals(account, increased_ve_lpt, true);
+        }
+    }
+
+    /// The locks of the account with the slashed lpt settled, only for views.
+    pub fn internal_preview_slashed_lpt(&self, account: &mut Account) {
+        for lock in account.locks.values_mut() {
+            let lock_pool = self.internal_unwrap_lock_pool(&lock.token_id);
+            lock.settle_slashed_lpt(lock_pool.slashed_lpt_per_share);
+        }
+    }
+
     pub fn internal_get_account(&self, account_id: &AccountId) -> Option<Account> {
-        self.data().accounts.get(account_id).map(|o| o.into())
+        self.data().accounts.get(account_id).map(|o| o.into_current(account_id, &self.data().lptoken_id))
     }
 
     pub fn internal_unwrap_account(&self, account_id: &AccountId) -> Account {
@@ -219,7 +635,8 @@ impl Contract {
             .expect(E100_ACC_NOT_REGISTERED)
     }
 
-    pub fn internal_set_account(&mut self, account_id: &AccountId, account: Account) {
+    pub fn internal_set_account(&mut self, account_id: &AccountId, mut account: Account) {
+        account.seed_ve_checkpoint();
         self.data_mut().accounts.insert(account_id, &account.into());
     }
 
@@ -232,23 +649,38 @@ impl Contract {
         }
     }
 
+    /// The account sender_id locks for, an unregistered one is created with sender_id as sponsor
+    /// if the lock_receiver_policy allows.
+    pub fn internal_unwrap_or_sponsored_account(&mut self, sender_id: &AccountId, account_id: &AccountId) -> Account {
+        if sender_id == account_id {
+            return self.internal_unwrap_or_default_account(account_id);
+        }
+        if let Some(account) = self.internal_get_account(account_id) {
+            account
+        } else {
+            require!(self.internal_config().lock_receiver_policy == LockReceiverPolicy::AllowUnregistered, E100_ACC_NOT_REGISTERED);
+            self.data_mut().account_count += 1;
+            Account::new(account_id, sender_id)
+        }
+    }
+
     pub fn internal_remove_account(&mut self, account_id: &AccountId) {
+        if let Some(mut account) = self.internal_get_account(account_id) {
+            account.ve_checkpoints.clear();
+        }
         self.ft.accounts.remove(account_id);
         self.data_mut().accounts.remove(account_id);
         self.data_mut().account_count -= 1;
     }
 }
 
-fn compute_ve_lpt_amount(config: &Config, amount: u128, duration_sec: u32, lptoken_decimals: u8) -> u128 {
-    let amount = match lptoken_decimals.cmp(&LOVE_DECIMAL) {
-        Ordering::Greater => amount / 10u128.pow((lptoken_decimals - LOVE_DECIMAL) as u32),
-        Ordering::Less => amount * 10u128.pow((LOVE_DECIMAL - lptoken_decimals) as u32),
+fn compute_ve_lpt_amount(config: &Config, lock_pool: &LockPool, amount: u128, duration_sec: u32) -> u128 {
+    let amount = match lock_pool.decimals.cmp(&LOVE_DECIMAL) {
+        Ordering::Greater => amount / 10u128.pow((lock_pool.decimals - LOVE_DECIMAL) as u32),
+        Ordering::Less => amount * 10u128.pow((LOVE_DECIMAL - lock_pool.decimals) as u32),
         Ordering::Equal => amount,
     };
-    amount
-        + u128_ratio(
-            amount,
-            u128::from(config.max_locking_multiplier - MIN_LOCKING_REWARD_RATIO) * u128::from(to_nano(duration_sec)),
-            u128::from(to_nano(config.max_locking_duration_sec)) * MIN_LOCKING_REWARD_RATIO as u128,
-        )
+    let (extra_multiplier_num, extra_multiplier_denom) = config.get_extra_locking_multiplier(duration_sec);
+    let ve_lpt_amount = amount + u128_ratio(amount, extra_multiplier_num, extra_multiplier_denom);
+    u128_ratio(ve_lpt_amount, lock_pool.ve_weight as u128, BASE_VE_WEIGHT as u128)
 }
\ No newline at end of file
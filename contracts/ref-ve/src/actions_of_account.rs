@@ -3,33 +3,180 @@ use crate::*;
 #[near_bindgen]
 impl Contract {
 
+    /// Withdraw lpt from the position of lock_id, 0 by default.
     #[payable]
-    pub fn withdraw_lpt(&mut self, amount: Option<U128>)  -> Promise {
+    pub fn withdraw_lpt(&mut self, amount: Option<U128>, lock_id: Option<u32>)  -> Promise {
         assert_one_yocto();
 
+        let account_id = env::predecessor_account_id();
+        let (lock_pool, amount) = self.internal_withdraw_lpt(&account_id, amount, lock_id);
+
+        self.transfer_lpt_token(&account_id, &lock_pool, amount)
+    }
+
+    /// Withdraw lpt from the position of lock_id (0 by default) directly into receiver_id 
+    /// through transfer_call with msg, the refunded part would be sent back to the caller.
+    #[payable]
+    pub fn withdraw_lpt_and_call(&mut self, amount: Option<U128>, lock_id: Option<u32>, receiver_id: AccountId, msg: String) -> Promise {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let (lock_pool, amount) = self.internal_withdraw_lpt(&account_id, amount, lock_id);
+
+        self.internal_transfer_lock_token_call(&lock_pool, &receiver_id, amount, msg)
+        .then(ext_self::callback_withdraw_lpt_and_call(
+            account_id,
+            lock_pool.token_id,
+            receiver_id,
+            amount.into(),
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_LPT_TRANSFER_CALL,
+        ))
+    }
+
+    /// Push out the unlock time of the position of lock_id (0 by default) by append_duration_sec
+    /// without depositing more lpt, the position is relocked with its new remaining duration.
+    #[payable]
+    pub fn extend_lock(&mut self, append_duration_sec: u32, lock_id: Option<u32>) {
+        assert_one_yocto();
+        require!(append_duration_sec > 0, E302_INVALID_DURATION);
+
         let account_id = env::predecessor_account_id();
         let mut account = self.internal_unwrap_account(&account_id);
+        self.internal_settle_slashed_lpt(&account_id, &mut account);
+        let lock_id = lock_id.unwrap_or(0);
+        let timestamp = env::block_timestamp();
+        let config = self.internal_config();
+        let duration_sec = account.unwrap_lock(lock_id).get_remaining_sec(timestamp, &config) + append_duration_sec;
+        require!(duration_sec >= config.min_locking_duration_sec, E302_INVALID_DURATION);
+        require!(duration_sec <= config.max_locking_duration_sec, E302_INVALID_DURATION);
+
+        let mut lock = account.take_lock(lock_id);
+        let lock_pool = self.internal_unwrap_lock_pool(&lock.token_id);
+        let prev_ve_state = lock.get_ve_state(timestamp);
+        let (increased_love, decreased_love) = lock.lock_lpt(0, duration_sec, &config, &lock_pool);
+        if increased_love > 0 {
+            self.mint_love_token(&account_id, increased_love);
+        }
+        if decreased_love > 0 {
+            self.burn_love_token(&account_id, decreased_love);
+        }
+
+        let cur_ve_state = lock.get_ve_state(timestamp);
+        account.put_lock(lock_id, lock);
+        let increased_ve_lpt = cur_ve_state.ve_lpt_amount - prev_ve_state.ve_lpt_amount;
+        self.internal_sync_total_ve_lpt(&prev_ve_state, &cur_ve_state);
+
+        self.update_impacted_proposals(&mut account, increased_ve_lpt, true);
+
+        account.checkpoint_ve_lpt();
+        self.internal_set_account(&account_id, account);
+
+        Event::LockExtend {
+            caller_id: &account_id,
+            token_id: &lock_pool.token_id,
+            lock_id,
+            increased_ve_lpt: &U128(increased_ve_lpt),
+            duration: duration_sec,
+        }
+        .emit();
+    }
+
+    /// Enable or disable auto relock of the position of lock_id, 0 by default.
+    /// An auto relocked position keeps the veLPT of max locking duration and can not be withdrawn,
+    /// disabling it starts the countdown of max locking duration from now on.
+    #[payable]
+    pub fn set_auto_relock(&mut self, auto_relock: bool, lock_id: Option<u32>) {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let mut account = self.internal_unwrap_account(&account_id);
+        self.internal_settle_slashed_lpt(&account_id, &mut account);
+        let lock_id = lock_id.unwrap_or(0);
+        require!(account.unwrap_lock(lock_id).auto_relock != auto_relock, E111_AUTO_RELOCK_UNCHANGED);
+
+        let timestamp = env::block_timestamp();
+        let config = self.internal_config();
+        let mut lock = account.take_lock(lock_id);
+        let lock_pool = self.internal_unwrap_lock_pool(&lock.token_id);
+        let prev_ve_state = lock.get_ve_state(timestamp);
+        let (increased_love, decreased_love) = lock.set_auto_relock(auto_relock, &config, &lock_pool);
+        if increased_love > 0 {
+            self.mint_love_token(&account_id, increased_love);
+        }
+        if decreased_love > 0 {
+            self.burn_love_token(&account_id, decreased_love);
+        }
+
+        let cur_ve_state = lock.get_ve_state(timestamp);
+        account.put_lock(lock_id, lock);
+        let increased_ve_lpt = cur_ve_state.ve_lpt_amount - prev_ve_state.ve_lpt_amount;
+        self.internal_sync_total_ve_lpt(&prev_ve_state, &cur_ve_state);
+
+        if increased_ve_lpt > 0 {
+            self.update_impacted_proposals(&mut account, increased_ve_lpt, true);
+        }
+
+        account.checkpoint_ve_lpt();
+        self.internal_set_account(&account_id, account);
+
+        Event::LockAutoRelock {
+            caller_id: &account_id,
+            token_id: &lock_pool.token_id,
+            lock_id,
+            auto_relock,
+            increased_ve_lpt: &U128(increased_ve_lpt),
+        }
+        .emit();
+    }
+
+    /// Withdraw lpt before unlock, a part of the amount would be slashed 
+    /// according to the remaining locking time of the position.
+    #[payable]
+    pub fn withdraw_lpt_early(&mut self, amount: Option<U128>, lock_id: Option<u32>) -> Promise {
+        assert_one_yocto();
+
+        let account_id = env::predecessor_account_id();
+        let mut account = self.internal_unwrap_account(&account_id);
+        self.internal_settle_slashed_lpt(&account_id, &mut account);
+        let lock_id = lock_id.unwrap_or(0);
+        let lock_lpt_amount = account.unwrap_lock(lock_id).lpt_amount;
         let amount = if let Some(request) = amount {
             request.0
         } else {
-            account.lpt_amount
+            lock_lpt_amount
         };
-        let decreased_ve_lpt = account.withdraw_lpt(amount);
-        self.burn_love_token(&account_id, decreased_ve_lpt);
+        let config = self.internal_config();
+        let mut lock = account.take_lock(lock_id);
+        let token_id = lock.token_id.clone();
+        let prev_ve_state = lock.get_ve_state(env::block_timestamp());
+        let (decreased_love, slashed_amount) = lock.withdraw_lpt_early(amount, &config);
+        self.burn_love_token(&account_id, decreased_love);
 
-        self.data_mut().cur_lock_lpt -= amount;
-        self.data_mut().cur_total_ve_lpt -= decreased_ve_lpt;
+        let cur_ve_state = lock.get_ve_state(env::block_timestamp());
+        account.put_lock(lock_id, lock);
+        let decreased_ve_lpt = prev_ve_state.ve_lpt_amount - cur_ve_state.ve_lpt_amount;
+        let mut lock_pool = self.internal_unwrap_lock_pool(&token_id);
+        lock_pool.cur_lock_lpt -= amount;
+        self.internal_sync_total_ve_lpt(&prev_ve_state, &cur_ve_state);
 
         self.update_impacted_proposals(&mut account, decreased_ve_lpt, false);
-        
+
+        account.checkpoint_ve_lpt();
         self.internal_set_account(&account_id, account);
 
-        self.transfer_lpt_token(&account_id, amount)
+        if slashed_amount > 0 {
+            self.internal_distribute_slashed_lpt(&mut lock_pool, slashed_amount, &config.early_unlock_penalty_sink);
+        }
+        self.internal_set_lock_pool(&lock_pool);
+
+        self.transfer_slashed_lpt_token(&account_id, &lock_pool, amount - slashed_amount, slashed_amount)
     }
 
 
     #[private]
-    pub fn callback_withdraw_lpt(&mut self, sender_id: AccountId, amount: U128) {
+    pub fn callback_withdraw_lpt(&mut self, sender_id: AccountId, token_id: String, amount: U128) {
         require!(
             env::promise_results_count() == 1,
             E001_PROMISE_RESULT_COUNT_INVALID
@@ -39,10 +186,13 @@ impl Contract {
             PromiseResult::NotReady => unreachable!(),
             PromiseResult::Failed => {
                 // all token amount go to lostfound
-                self.data_mut().lostfound += amount;
+                let mut lock_pool = self.internal_unwrap_lock_pool(&token_id);
+                lock_pool.lostfound += amount;
+                self.internal_set_lock_pool(&lock_pool);
 
                 Event::LptWithdraw {
                     caller_id: &sender_id,
+                    token_id: &token_id,
                     withdraw_amount: &U128(amount),
                     success: false,
                 }
@@ -51,6 +201,7 @@ impl Contract {
             PromiseResult::Successful(_) => {
                 Event::LptWithdraw {
                     caller_id: &sender_id,
+                    token_id: &token_id,
                     withdraw_amount: &U128(amount),
                     success: true,
                 }
@@ -58,29 +209,272 @@ impl Contract {
             }
         }
     }
+
+    /// The refunded lpt is sent back to the sender, the whole amount goes to lostfound if the call failed.
+    #[private]
+    pub fn callback_withdraw_lpt_and_call(&mut self, sender_id: AccountId, token_id: String, receiver_id: AccountId, amount: U128) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let amount: Balance = amount.into();
+        let mut lock_pool = self.internal_unwrap_lock_pool(&token_id);
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Failed => {
+                // all token amount go to lostfound
+                lock_pool.lostfound += amount;
+                self.internal_set_lock_pool(&lock_pool);
+
+                Event::LptWithdrawCall {
+                    caller_id: &sender_id,
+                    token_id: &token_id,
+                    receiver_id: &receiver_id,
+                    delivered_amount: &U128(0),
+                    refunded_amount: &U128(0),
+                    success: false,
+                }
+                .emit();
+            }
+            PromiseResult::Successful(value) => {
+                let result = near_sdk::serde_json::from_slice::<U128>(&value).map(|v| v.0).unwrap_or(0);
+                match lock_pool.kind {
+                    // mft_transfer_call returns the unused amount, which may not all come back
+                    // if the receiver has moved it away, so the refund is checked against the balance
+                    LockPoolKind::MultiFungibleToken if result > 0 => {
+                        ext_multi_fungible_token::mft_balance_of(
+                            token_id.clone(),
+                            env::current_account_id(),
+                            self.data().lptoken_contract_id.clone(),
+                            0,
+                            GAS_FOR_LPT_BALANCE_OF,
+                        )
+                        .then(ext_self::callback_refund_lpt_and_call(
+                            sender_id,
+                            token_id,
+                            receiver_id,
+                            amount.into(),
+                            std::cmp::min(result, amount).into(),
+                            env::current_account_id(),
+                            0,
+                            GAS_FOR_RESOLVE_LPT_REFUND,
+                        ));
+                    },
+                    LockPoolKind::MultiFungibleToken => {
+                        self.internal_refund_lpt_and_call(&sender_id, &lock_pool, &receiver_id, amount, 0);
+                    },
+                    // ft_transfer_call returns the used amount, the rest has come back
+                    LockPoolKind::FungibleToken => {
+                        let refunded_amount = amount - std::cmp::min(result, amount);
+                        self.internal_refund_lpt_and_call(&sender_id, &lock_pool, &receiver_id, amount, refunded_amount);
+                    },
+                }
+            }
+        }
+    }
+
+    /// Refunds the unused lpt of withdraw_lpt_and_call as far as it has come back,
+    /// that is what the contract holds beyond the lpt it is accountable for.
+    #[private]
+    pub fn callback_refund_lpt_and_call(&mut self, sender_id: AccountId, token_id: String, receiver_id: AccountId, amount: U128, unused_amount: U128) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let lock_pool = self.internal_unwrap_lock_pool(&token_id);
+        let balance = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(value) => near_sdk::serde_json::from_slice::<U128>(&value).map(|v| v.0).unwrap_or(0),
+            PromiseResult::Failed => 0,
+        };
+        let refunded_amount = std::cmp::min(unused_amount.0, balance.saturating_sub(lock_pool.held_lpt()));
+        self.internal_refund_lpt_and_call(&sender_id, &lock_pool, &receiver_id, amount.0, refunded_amount);
+    }
+
+    #[private]
+    pub fn callback_withdraw_lpt_slashed(&mut self, sender_id: AccountId, token_id: String, amount: U128, slashed_amount: U128) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let amount: Balance = amount.into();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Failed => {
+                // all token amount go to lostfound
+                let mut lock_pool = self.internal_unwrap_lock_pool(&token_id);
+                lock_pool.lostfound += amount;
+                self.internal_set_lock_pool(&lock_pool);
+
+                Event::LptWithdrawSlashed {
+                    caller_id: &sender_id,
+                    token_id: &token_id,
+                    withdraw_amount: &U128(amount),
+                    slashed_amount: &slashed_amount,
+                    success: false,
+                }
+                .emit();
+            }
+            PromiseResult::Successful(_) => {
+                Event::LptWithdrawSlashed {
+                    caller_id: &sender_id,
+                    token_id: &token_id,
+                    withdraw_amount: &U128(amount),
+                    slashed_amount: &slashed_amount,
+                    success: true,
+                }
+                .emit();
+            }
+        }
+    }
+
+    #[private]
+    pub fn callback_transfer_slashed_lpt(&mut self, receiver_id: AccountId, token_id: String, amount: U128) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let amount: Balance = amount.into();
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Failed => {
+                // all token amount go to lostfound
+                let mut lock_pool = self.internal_unwrap_lock_pool(&token_id);
+                lock_pool.lostfound += amount;
+                self.internal_set_lock_pool(&lock_pool);
+
+                Event::SlashedLptTransfer {
+                    receiver_id: &receiver_id,
+                    token_id: &token_id,
+                    amount: &U128(amount),
+                    success: false,
+                }
+                .emit();
+            }
+            PromiseResult::Successful(_) => {
+                Event::SlashedLptTransfer {
+                    receiver_id: &receiver_id,
+                    token_id: &token_id,
+                    amount: &U128(amount),
+                    success: true,
+                }
+                .emit();
+            }
+        }
+    }
 }
 
 impl Contract {
+    /// Send the refunded lpt of withdraw_lpt_and_call back to the sender.
+    fn internal_refund_lpt_and_call(&mut self, sender_id: &AccountId, lock_pool: &LockPool, receiver_id: &AccountId, amount: Balance, refunded_amount: Balance) {
+        if refunded_amount > 0 {
+            self.transfer_lpt_token(sender_id, lock_pool, refunded_amount);
+        }
+
+        Event::LptWithdrawCall {
+            caller_id: sender_id,
+            token_id: &lock_pool.token_id,
+            receiver_id,
+            delivered_amount: &U128(amount - refunded_amount),
+            refunded_amount: &U128(refunded_amount),
+            success: true,
+        }
+        .emit();
+    }
+
+    /// Remove unlocked lpt from the position, return the lock pool and the amount to transfer out.
+    fn internal_withdraw_lpt(&mut self, account_id: &AccountId, amount: Option<U128>, lock_id: Option<u32>) -> (LockPool, Balance) {
+        let mut account = self.internal_unwrap_account(account_id);
+        self.internal_settle_slashed_lpt(account_id, &mut account);
+        let lock_id = lock_id.unwrap_or(0);
+        let lock_lpt_amount = account.unwrap_lock(lock_id).lpt_amount;
+        let amount = if let Some(request) = amount {
+            request.0
+        } else {
+            lock_lpt_amount
+        };
+        let mut lock = account.take_lock(lock_id);
+        let token_id = lock.token_id.clone();
+        let prev_ve_state = lock.get_ve_state(env::block_timestamp());
+        let decreased_love = lock.withdraw_lpt(amount);
+        self.burn_love_token(account_id, decreased_love);
+
+        let cur_ve_state = lock.get_ve_state(env::block_timestamp());
+        account.put_lock(lock_id, lock);
+        let decreased_ve_lpt = prev_ve_state.ve_lpt_amount - cur_ve_state.ve_lpt_amount;
+        let mut lock_pool = self.internal_unwrap_lock_pool(&token_id);
+        lock_pool.cur_lock_lpt -= amount;
+        self.internal_set_lock_pool(&lock_pool);
+        self.internal_sync_total_ve_lpt(&prev_ve_state, &cur_ve_state);
+
+        self.update_impacted_proposals(&mut account, decreased_ve_lpt, false);
+        
+        account.checkpoint_ve_lpt();
+        self.internal_set_account(account_id, account);
+
+        (lock_pool, amount)
+    }
+
     fn transfer_lpt_token(
         &self,
         account_id: &AccountId,
+        lock_pool: &LockPool,
         amount: Balance,
     ) -> Promise {
-        ext_multi_fungible_token::mft_transfer(
-            self.data().lptoken_id.clone(),
+        self.internal_transfer_lock_token(lock_pool, account_id, amount)
+        .then(ext_self::callback_withdraw_lpt(
             account_id.clone(),
+            lock_pool.token_id.clone(),
             amount.into(),
-            None,
-            self.data().lptoken_contract_id.clone(),
-            1, // one yocto near
-            GAS_FOR_LPT_TRANSFER,
-        )
-        .then(ext_self::callback_withdraw_lpt(
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_LPT_TRANSFER,
+        ))
+    }
+
+    fn transfer_slashed_lpt_token(
+        &self,
+        account_id: &AccountId,
+        lock_pool: &LockPool,
+        amount: Balance,
+        slashed_amount: Balance,
+    ) -> Promise {
+        self.internal_transfer_lock_token(lock_pool, account_id, amount)
+        .then(ext_self::callback_withdraw_lpt_slashed(
             account_id.clone(),
+            lock_pool.token_id.clone(),
             amount.into(),
+            slashed_amount.into(),
             env::current_account_id(),
             0,
             GAS_FOR_RESOLVE_LPT_TRANSFER,
         ))
     }
+
+    fn internal_distribute_slashed_lpt(&mut self, lock_pool: &mut LockPool, amount: Balance, sink: &PenaltySink) {
+        match sink {
+            PenaltySink::Treasury { account_id } => {
+                self.internal_transfer_lock_token(lock_pool, account_id, amount)
+                .then(ext_self::callback_transfer_slashed_lpt(
+                    account_id.clone(),
+                    lock_pool.token_id.clone(),
+                    amount.into(),
+                    env::current_account_id(),
+                    0,
+                    GAS_FOR_RESOLVE_LPT_TRANSFER,
+                ));
+            }
+            PenaltySink::Lockers => {
+                if lock_pool.cur_lock_lpt > 0 {
+                    // slashed lpt stays locked and is settled into each lock of the pool lazily,
+                    // shared by the settled lpt only as the locks are credited on it
+                    lock_pool.slashed_lpt_per_share += u128_ratio(amount, SLASHED_LPT_PER_SHARE_PRECISION, lock_pool.cur_lock_lpt);
+                    lock_pool.unsettled_slashed_lpt += amount;
+                } else {
+                    // nobody left to share, the lpt would go to lostfound
+                    lock_pool.lostfound += amount;
+                }
+            }
+        }
+    }
 }
\ No newline at end of file
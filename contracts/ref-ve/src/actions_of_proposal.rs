@@ -2,6 +2,8 @@ use crate::*;
 
 #[near_bindgen]
 impl Contract {
+    /// A whitelisted account attaches 1 yocto, others attach the NEAR bond of the permissionless policy
+    /// plus the storage of the proposal, the rest is refunded.
     #[payable]
     pub fn create_proposal(
         &mut self,
@@ -9,61 +11,92 @@ impl Contract {
         description: String,
         start_at: u32,
         duration_sec: u32,
+        voting_mode: Option<VotingMode>,
+        poll_mode: Option<PollMode>,
     ) -> u32 {
-        assert_one_yocto();
-
         let proposer = env::predecessor_account_id();
-        require!(self.data().whitelisted_accounts.contains(&proposer) , E002_NOT_ALLOWED);
-        require!(description.len() <= DESCRIPTION_LIMIT , E208_DESCRIPTION_TOO_LONG);
-        
-        self.internal_unwrap_account(&proposer);
+        if self.data().whitelisted_accounts.contains(&proposer) {
+            assert_one_yocto();
+            return self.internal_create_proposal(&proposer, kind, description, start_at, duration_sec, voting_mode, poll_mode, None);
+        }
+        let (bond, storage_deposit) = self.internal_post_bond(&proposer, &kind, BondAsset::Near, env::attached_deposit());
+        let prev_storage = env::storage_usage();
+        let proposal_id = self.internal_create_proposal(&proposer, kind, description, start_at, duration_sec, voting_mode, poll_mode, Some(Box::new(bond)));
+        let storage_cost = Balance::from(env::storage_usage() - prev_storage) * env::storage_byte_cost();
+        require!(storage_deposit >= storage_cost, E102_INSUFFICIENT_STORAGE);
+        if storage_deposit > storage_cost {
+            Promise::new(proposer).transfer(storage_deposit - storage_cost);
+        }
+        proposal_id
+    }
 
-        let config = self.internal_config();
+    /// The proposer can change the description, the kind within the same variant and the duration
+    /// before the voting begins, the farms holding incentives must stay unchanged.
+    /// The storage grown by a bonded proposal is paid from the available storage of the proposer.
+    #[payable]
+    pub fn update_proposal(
+        &mut self,
+        proposal_id: u32,
+        description: Option<String>,
+        kind: Option<ProposalKind>,
+        duration_sec: Option<u32>,
+    ) -> bool {
+        assert_one_yocto();
 
-        require!(duration_sec >= config.min_voting_duration_sec && duration_sec <= config.max_voting_duration_sec
-            , E302_INVALID_DURATION);
-        require!(start_at - nano_to_sec(env::block_timestamp()) >= config.min_proposal_start_vote_offset_sec, E402_INVALID_START_TIME);
+        let proposer = env::predecessor_account_id();
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        require!(proposal.proposer == proposer, E002_NOT_ALLOWED);
+        require!(proposal.status == Some(ProposalStatus::WarmUp), E415_PROPOSAL_NOT_UPDATABLE);
 
-        let votes: Vec<VoteInfo> = match &kind {
-            ProposalKind::FarmingReward{ farm_list, .. } => {
-                vec![Default::default(); farm_list.len()]
-            },
-            ProposalKind::Poll{ options, .. } => {
-                vec![Default::default(); options.len()]
-            },
-            ProposalKind::Common{ .. } => {
-                vec![Default::default(); 3]
+        if let Some(description) = description {
+            require!(description.len() <= DESCRIPTION_LIMIT , E208_DESCRIPTION_TOO_LONG);
+            proposal.description = description;
+        }
+        if let Some(kind) = kind {
+            require!(std::mem::discriminant(&kind) == std::mem::discriminant(&proposal.kind), E416_PROPOSAL_KIND_CHANGED);
+            self.internal_assert_valid_kind(&kind, &proposal.poll_mode);
+            for (incentive_key, incentive) in proposal.incentive.iter() {
+                for token_id in incentive.incentive_token_ids.iter() {
+                    self.internal_assert_valid_incentive(&kind, *incentive_key, token_id);
+                }
             }
-        };
-
-        let id = self.data().last_proposal_id;
-        let proposal = Proposal{
-            id,
-            description,
-            proposer: proposer.clone(),
-            kind: kind.clone(),
-            votes,
-            ve_amount_at_last_action: self.data().cur_total_ve_lpt,
-            incentive: HashMap::new(),
-            start_at: to_nano(start_at),
-            end_at: to_nano(start_at + duration_sec),
-            participants: 0,
-            status: None,
-            is_nonsense: None
-        };
-        self.internal_set_proposal(id, proposal.into());
+            // the depositors put their incentives on the farm as it was
+            if let (ProposalKind::FarmingReward { farm_list: prev_farm_list, .. }, ProposalKind::FarmingReward { farm_list, .. }) = (&proposal.kind, &kind) {
+                for incentive_key in proposal.incentive.keys() {
+                    let index = *incentive_key as usize;
+                    require!(prev_farm_list[index] == farm_list[index], E420_INCENTIVE_FARM_CHANGED);
+                }
+            }
+            proposal.votes = vec![Default::default(); kind.votes_len()];
+            proposal.kind = kind;
+        }
+        if let Some(duration_sec) = duration_sec {
+            let config = self.internal_config();
+            require!(duration_sec >= config.min_voting_duration_sec && duration_sec <= config.max_voting_duration_sec
+                , E302_INVALID_DURATION);
+            let end_at = proposal.start_at + to_nano(duration_sec);
+            // keeps the timelock taken at creation
+            if let Some(execution) = proposal.execution.as_mut() {
+                execution.executable_at = execution.executable_at - proposal.end_at + end_at;
+            }
+            proposal.end_at = end_at;
+        }
 
-        Event::ProposalCreate {
+        Event::ProposalUpdate {
             proposer_id: &proposer,
-            proposal_id: id,
-            kind: &format!("{:?}", kind),
-            start_at: to_nano(start_at),
-            duration_sec
+            proposal_id,
+            kind: &format!("{:?}", proposal.kind),
+            end_at: proposal.end_at,
         }
         .emit();
-        
-        self.data_mut().last_proposal_id += 1;
-        id
+
+        let is_bonded = proposal.bond.is_some();
+        let prev_storage = env::storage_usage();
+        self.internal_set_proposal(proposal_id, proposal);
+        if is_bonded {
+            self.internal_charge_storage(&proposer, prev_storage);
+        }
+        true
     }
 
     #[payable]
@@ -72,23 +105,28 @@ impl Contract {
        
         let proposer = env::predecessor_account_id();
 
-        let proposal = self.internal_unwrap_proposal(proposal_id);
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
         require!(proposal.proposer == proposer, E002_NOT_ALLOWED);
 
-        match proposal.status.unwrap() {
-            ProposalStatus::WarmUp => {
+        match proposal.status {
+            Some(ProposalStatus::WarmUp) => {
                 self.data_mut().proposals.remove(&proposal_id);
 
-                for item in proposal.incentive.values() {
-                    for index in 0..item.incentive_token_ids.len() {
-                        let current_amount = self.data().removed_proposal_assets.get(&item.incentive_token_ids[index]).unwrap_or(0_u128);
-                        self.data_mut().removed_proposal_assets.insert(
-                            &item.incentive_token_ids[index],
-                            &(item.incentive_amounts[index] + current_amount),
-                        );
-                    }
+                if let Some(bond) = proposal.bond.take() {
+                    self.internal_transfer_bond(&bond.asset, &proposer, bond.amount)
+                        .then(ext_self::callback_transfer_bond(
+                            proposal_id,
+                            bond.asset,
+                            proposer.clone(),
+                            bond.amount.into(),
+                            env::current_account_id(),
+                            0,
+                            GAS_FOR_RESOLVE_BOND_TRANSFER,
+                        ));
                 }
 
+                self.internal_remove_incentives(&mut proposal);
+
                 Event::ProposalRemove {
                     proposer_id: &proposer,
                     proposal_id,
@@ -101,6 +139,202 @@ impl Contract {
         }
     }
 
+    /// A guardian stops an inprogress proposal, the incentives go back to the depositors
+    /// to withdraw, the votes are freed without rewards on the next claim,
+    /// and the bond is forfeited. A passed execution can also be stopped during its timelock,
+    /// where the voting stays as it was.
+    #[payable]
+    pub fn veto_proposal(&mut self, proposal_id: u32) {
+        assert_one_yocto();
+
+        let guardian = env::predecessor_account_id();
+        require!(self.data().guardians.contains(&guardian), E002_NOT_ALLOWED);
+
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        if proposal.status == Some(ProposalStatus::InProgress) {
+            proposal.vetoed_by = Some(guardian.clone());
+            self.internal_remove_incentives(&mut proposal);
+        } else {
+            let is_passed = self.internal_proposal_outcome(&proposal) == Some(ProposalOutcome::Passed);
+            let execution = proposal.execution.as_mut().filter(|execution|
+                is_passed && execution.status == ExecutionStatus::Pending && env::block_timestamp() < execution.executable_at
+            ).expect(E417_PROPOSAL_NOT_VETOABLE);
+            execution.status = ExecutionStatus::Vetoed;
+        }
+        self.internal_set_proposal(proposal_id, proposal);
+
+        Event::ProposalVeto {
+            guardian_id: &guardian,
+            proposal_id,
+        }
+        .emit();
+    }
+
+    /// Anyone can fund the execution treasury, the only NEAR the execution actions can attach.
+    /// Returns the treasury after it.
+    #[payable]
+    pub fn deposit_execution_treasury(&mut self) -> U128 {
+        let amount = env::attached_deposit();
+        require!(amount > 0, E101_INSUFFICIENT_BALANCE);
+        self.data_mut().execution_treasury += amount;
+        self.data().execution_treasury.into()
+    }
+
+    /// Anyone can execute the passed executable or config change proposal once its timelock elapsed,
+    /// the attached gas must cover the gas of all the actions. Returns whether it succeeded.
+    pub fn execute_proposal(&mut self, proposal_id: u32) -> PromiseOrValue<bool> {
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        require!(proposal.execution.is_some(), E407_NOT_EXECUTABLE);
+        require!(self.internal_proposal_outcome(&proposal) == Some(ProposalOutcome::Passed), E408_PROPOSAL_NOT_PASSED);
+        let execution = proposal.execution.as_mut().unwrap();
+        require!(execution.status == ExecutionStatus::Pending, E407_NOT_EXECUTABLE);
+        require!(env::block_timestamp() >= execution.executable_at, E409_TIMELOCK_NOT_ELAPSED);
+
+        Event::ProposalExecute {
+            executor_id: &env::predecessor_account_id(),
+            proposal_id,
+        }
+        .emit();
+
+        match proposal.kind.clone() {
+            ProposalKind::Executable { actions } => {
+                self.internal_assert_valid_execution_actions(&actions);
+                proposal.execution.as_mut().unwrap().status = ExecutionStatus::Executing;
+                self.internal_set_proposal(proposal_id, proposal);
+                PromiseOrValue::Promise(self.internal_execute_actions(proposal_id, &actions))
+            },
+            ProposalKind::ConfigChange { config, whitelists } => {
+                self.internal_apply_config_change(&config, &whitelists);
+                proposal.execution.as_mut().unwrap().status = ExecutionStatus::Executed;
+                self.internal_set_proposal(proposal_id, proposal);
+                Event::ProposalExecuteResult {
+                    proposal_id,
+                    success: true,
+                }
+                .emit();
+                PromiseOrValue::Value(true)
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    /// Anyone can emit the expiry event of the proposal with its outcome,
+    /// otherwise it's emitted when the first voter claims. Returns whether it's newly settled.
+    pub fn settle_proposal(&mut self, proposal_id: u32) -> bool {
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        let settled = self.internal_settle_proposal(&mut proposal);
+        if settled {
+            self.internal_set_proposal(proposal_id, proposal);
+        }
+        settled
+    }
+
+    /// Anyone can send the bond of the expired proposal back to the proposer, or to the treasury if voted as nonsense,
+    /// or a nonsense voter claims its share if the forfeited bond goes to them. Returns the amount sent.
+    pub fn claim_proposal_bond(&mut self, proposal_id: u32) -> U128 {
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        let (receiver_id, amount) = self.internal_claim_bond(&mut proposal, &env::predecessor_account_id());
+        let asset = proposal.bond.as_ref().unwrap().asset.clone();
+        self.internal_set_proposal(proposal_id, proposal);
+
+        if amount > 0 {
+            self.internal_transfer_bond(&asset, &receiver_id, amount)
+                .then(ext_self::callback_transfer_bond(
+                    proposal_id,
+                    asset,
+                    receiver_id,
+                    amount.into(),
+                    env::current_account_id(),
+                    0,
+                    GAS_FOR_RESOLVE_BOND_TRANSFER,
+                ));
+        }
+        amount.into()
+    }
+
+    #[private]
+    pub fn callback_transfer_bond(&mut self, proposal_id: u32, asset: BondAsset, receiver_id: AccountId, amount: U128) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let success = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => true,
+            PromiseResult::Failed => {
+                match self.internal_get_proposal(proposal_id) {
+                    // reverts the claim so that it can be claimed again
+                    Some(mut proposal) => {
+                        let bond = proposal.bond.as_mut().unwrap();
+                        if bond.claimed_voters.remove(&receiver_id) {
+                            bond.claimed_amount -= amount.0;
+                        } else {
+                            bond.status = BondStatus::Held;
+                        }
+                        self.internal_set_proposal(proposal_id, proposal);
+                        if let BondAsset::Lpt { token_id } = &asset {
+                            let mut lock_pool = self.internal_unwrap_lock_pool(token_id);
+                            lock_pool.bonded_lpt += amount.0;
+                            self.internal_set_lock_pool(&lock_pool);
+                        }
+                    },
+                    // the bond refunded on removal, the lpt would go to lostfound
+                    None => {
+                        if let BondAsset::Lpt { token_id } = &asset {
+                            let mut lock_pool = self.internal_unwrap_lock_pool(token_id);
+                            lock_pool.lostfound += amount.0;
+                            self.internal_set_lock_pool(&lock_pool);
+                        }
+                    },
+                }
+                false
+            },
+        };
+
+        Event::ProposalBondTransfer {
+            proposal_id,
+            receiver_id: &receiver_id,
+            asset: &asset,
+            amount: &amount,
+            success,
+        }
+        .emit();
+    }
+
+    #[private]
+    pub fn callback_execute_proposal(&mut self, proposal_id: u32) -> bool {
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        let actions = match &proposal.kind {
+            ProposalKind::Executable { actions } => actions.clone(),
+            _ => env::panic_str(E407_NOT_EXECUTABLE),
+        };
+        require!(
+            env::promise_results_count() == actions.len() as u64,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let mut success = true;
+        for (index, action) in actions.iter().enumerate() {
+            if !matches!(env::promise_result(index as u64), PromiseResult::Successful(_)) {
+                // the deposit of the failed action is refunded to this contract
+                self.data_mut().execution_treasury += action.deposit.0;
+                success = false;
+            }
+        }
+        proposal.execution.as_mut().unwrap().status = if success {
+            ExecutionStatus::Executed
+        } else {
+            ExecutionStatus::Failed
+        };
+        self.internal_set_proposal(proposal_id, proposal);
+
+        Event::ProposalExecuteResult {
+            proposal_id,
+            success,
+        }
+        .emit();
+        success
+    }
+
     #[payable]
     pub fn action_proposal(&mut self, proposal_id: u32, action: Action, memo: Option<String>) -> U128 {
         assert_one_yocto();
@@ -143,4 +377,156 @@ impl Contract {
 
         vote_detail.amount.into()
     }
+
+    /// Delegate the veLPT of caller to delegatee_id, who then votes with it until undelegate.
+    /// Delegating again refreshes the delegated veLPT with the current one.
+    #[payable]
+    pub fn delegate(&mut self, delegatee_id: AccountId) -> U128 {
+        assert_one_yocto();
+        let delegator_id = env::predecessor_account_id();
+        require!(delegator_id != delegatee_id, E115_INVALID_DELEGATEE);
+        self.internal_unwrap_account(&delegatee_id);
+        self.internal_undelegate(&delegator_id);
+
+        let mut account = self.internal_unwrap_account(&delegator_id);
+        self.internal_claim_all(&mut account);
+        let ve_lpt_amount = account.get_ve_lpt_amount(env::block_timestamp());
+        account.delegatee = Some(delegatee_id.clone());
+        account.delegated_ve_lpt = ve_lpt_amount;
+        account.checkpoint_ve_lpt();
+        let direct_voted: Vec<u32> = account.proposals.keys().cloned().collect();
+        self.internal_set_account(&delegator_id, account);
+        self.internal_update_delegation(&delegatee_id, &direct_voted, ve_lpt_amount, true);
+
+        Event::Delegate {
+            delegator_id: &delegator_id,
+            delegatee_id: &delegatee_id,
+            ve_lpt_amount: &U128(ve_lpt_amount),
+        }
+        .emit();
+
+        ve_lpt_amount.into()
+    }
+
+    #[payable]
+    pub fn undelegate(&mut self) -> U128 {
+        assert_one_yocto();
+        let delegator_id = env::predecessor_account_id();
+        let (delegatee_id, ve_lpt_amount) = self.internal_undelegate(&delegator_id).expect(E114_NOT_DELEGATED);
+
+        Event::Undelegate {
+            delegator_id: &delegator_id,
+            delegatee_id: &delegatee_id,
+            ve_lpt_amount: &U128(ve_lpt_amount),
+        }
+        .emit();
+
+        ve_lpt_amount.into()
+    }
+}
+
+impl Contract {
+    pub fn internal_assert_valid_kind(&self, kind: &ProposalKind, poll_mode: &PollMode) {
+        match (kind, poll_mode) {
+            (_, PollMode::Single) => {},
+            (ProposalKind::Poll { options }, PollMode::MultiSelect { max_choices }) => {
+                require!(*max_choices > 0 && *max_choices as usize <= options.len(), E412_INVALID_POLL_MODE);
+            },
+            (ProposalKind::Poll { options }, PollMode::RankedChoice) => {
+                require!(options.len() <= MAX_RANKED_CHOICE_OPTIONS, E412_INVALID_POLL_MODE);
+            },
+            _ => env::panic_str(E412_INVALID_POLL_MODE),
+        }
+        match kind {
+            ProposalKind::Executable { actions } => self.internal_assert_valid_execution_actions(actions),
+            ProposalKind::ConfigChange { config, .. } => self.internal_assert_valid_config_change(config),
+            _ => {}
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn internal_create_proposal(
+        &mut self,
+        proposer: &AccountId,
+        kind: ProposalKind,
+        description: String,
+        start_at: u32,
+        duration_sec: u32,
+        voting_mode: Option<VotingMode>,
+        poll_mode: Option<PollMode>,
+        bond: Option<Box<ProposalBond>>,
+    ) -> u32 {
+        require!(description.len() <= DESCRIPTION_LIMIT , E208_DESCRIPTION_TOO_LONG);
+        
+        self.internal_unwrap_account(proposer);
+
+        let config = self.internal_config();
+
+        require!(duration_sec >= config.min_voting_duration_sec && duration_sec <= config.max_voting_duration_sec
+            , E302_INVALID_DURATION);
+        require!(start_at - nano_to_sec(env::block_timestamp()) >= config.min_proposal_start_vote_offset_sec, E402_INVALID_START_TIME);
+
+        let votes: Vec<VoteInfo> = vec![Default::default(); kind.votes_len()];
+        let poll_mode = poll_mode.unwrap_or(PollMode::Single);
+        self.internal_assert_valid_kind(&kind, &poll_mode);
+        let execution = matches!(kind, ProposalKind::Executable { .. } | ProposalKind::ConfigChange { .. }).then(|| ProposalExecution {
+            executable_at: to_nano(start_at + duration_sec + config.execution_timelock_sec),
+            status: ExecutionStatus::Pending,
+        });
+
+        let id = self.data().last_proposal_id;
+        let proposal = Proposal{
+            id,
+            description,
+            proposer: proposer.clone(),
+            threshold: config.proposal_thresholds.get(&kind).clone(),
+            kind: kind.clone(),
+            voting_mode: voting_mode.unwrap_or(VotingMode::Live),
+            poll_mode,
+            votes,
+            voted_amount: 0,
+            ranked_ballots: vec![],
+            ve_amount_at_last_action: self.internal_cur_total_ve_lpt(),
+            incentive: HashMap::new(),
+            start_at: to_nano(start_at),
+            end_at: to_nano(start_at + duration_sec),
+            participants: 0,
+            status: None,
+            is_nonsense: None,
+            execution,
+            bond,
+            vetoed_by: None,
+            sweeps: vec![],
+            is_settled: false,
+            outcome: None,
+            ranked_choice_result: None,
+        };
+        self.internal_set_proposal(id, proposal.into());
+
+        Event::ProposalCreate {
+            proposer_id: proposer,
+            proposal_id: id,
+            kind: &format!("{:?}", kind),
+            start_at: to_nano(start_at),
+            duration_sec
+        }
+        .emit();
+        
+        self.data_mut().last_proposal_id += 1;
+        id
+    }
+
+    /// Take back the delegated veLPT, return the previous delegatee and the amount if there was one.
+    fn internal_undelegate(&mut self, delegator_id: &AccountId) -> Option<(AccountId, Balance)> {
+        let mut account = self.internal_unwrap_account(delegator_id);
+        let delegatee_id = account.delegatee.take()?;
+        self.internal_claim_all(&mut account);
+        let ve_lpt_amount = account.delegated_ve_lpt;
+        account.delegated_ve_lpt = 0;
+        account.checkpoint_ve_lpt();
+        let direct_voted: Vec<u32> = account.proposals.keys().cloned().collect();
+        self.internal_set_account(delegator_id, account);
+        self.internal_update_delegation(&delegatee_id, &direct_voted, ve_lpt_amount, false);
+        Some((delegatee_id, ve_lpt_amount))
+    }
 }
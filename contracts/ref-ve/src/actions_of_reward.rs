@@ -21,8 +21,9 @@ impl Contract {
         let account_id = env::predecessor_account_id();
         let mut account = self.internal_unwrap_account(&account_id);
         let mut proposal = self.internal_unwrap_proposal(proposal_id);
-        if proposal.status == Some(ProposalStatus::Expired) {
+        if proposal.is_finished() {
             if let Some(vote_detail) = account.proposals.remove(&proposal_id) {
+                self.internal_settle_proposal(&mut proposal);
                 if let Some(reward_details) = proposal.claim_reward(&vote_detail) {
                     account.add_rewards(&HashMap::from_iter(reward_details));
                 }
@@ -97,6 +98,75 @@ impl Contract {
             }
         }
     }
+
+    /// Once the claim grace period passed after the proposal ended, a depositor, the treasury of the sink,
+    /// or the owner and operators can sweep the incentives no voter can claim,
+    /// the voters yet to claim still get their shares after that.
+    pub fn sweep_incentives(&mut self, proposal_id: u32) {
+        let caller_id = env::predecessor_account_id();
+        let mut proposal = self.internal_unwrap_proposal(proposal_id);
+        self.internal_sweep_incentives(&mut proposal, &caller_id);
+        self.internal_set_proposal(proposal_id, proposal);
+    }
+
+    /// Withdraws given token of the incentives the caller deposited into removed or vetoed proposals,
+    /// or swept to it.
+    /// when amount is None, withdraw all balance of the token.
+    pub fn withdraw_removed_incentive(&mut self, token_id: AccountId, amount: Option<U128>) {
+        let depositor_id = env::predecessor_account_id();
+        let total = self.internal_get_removed_incentives(&depositor_id).get(&token_id).cloned().unwrap_or(0);
+        let amount: u128 = amount.map(|v| v.into()).unwrap_or(total);
+
+        if amount > 0 {
+            // Note: subtraction, will be reverted if the promise fails.
+            self.internal_sub_removed_incentive(&depositor_id, &token_id, amount);
+            ext_fungible_token::ft_transfer(
+                depositor_id.clone(),
+                amount.into(),
+                None,
+                token_id.clone(),
+                1,
+                GAS_FOR_REWARD_TRANSFER,
+            )
+            .then(ext_self::callback_withdraw_removed_incentive(
+                token_id,
+                depositor_id,
+                amount.into(),
+                env::current_account_id(),
+                0,
+                GAS_FOR_RESOLVE_REWARD_TRANSFER,
+            ));
+        }
+    }
+
+    #[private]
+    pub fn callback_withdraw_removed_incentive(
+        &mut self,
+        token_id: AccountId,
+        sender_id: AccountId,
+        amount: U128,
+    ) {
+        require!(
+            env::promise_results_count() == 1,
+            E001_PROMISE_RESULT_COUNT_INVALID
+        );
+        let success = match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => true,
+            PromiseResult::Failed => {
+                // This reverts the changes from withdraw function.
+                self.internal_add_removed_incentive(&sender_id, &token_id, amount.0);
+                false
+            }
+        };
+        Event::RemovedIncentiveWithdraw {
+            caller_id: &sender_id,
+            token_id: &token_id,
+            withdraw_amount: &amount,
+            success,
+        }
+        .emit();
+    }
 }
 
 impl Contract {
@@ -125,7 +195,8 @@ impl Contract {
         let mut history = HashMap::new();
         account.proposals.retain(|proposal_id, vote_detail| {
             let mut proposal = self.internal_unwrap_proposal(*proposal_id);
-            if proposal.status == Some(ProposalStatus::Expired) {
+            if proposal.is_finished() {
+                self.internal_settle_proposal(&mut proposal);
                 if let Some(reward_details) = proposal.claim_reward(vote_detail){
                     reward_details.into_iter().for_each(|(reward_token, reward_amount)| {
                         rewards.insert(reward_token.clone(), reward_amount + rewards.get(&reward_token).unwrap_or(&0_u128));
@@ -147,19 +218,19 @@ impl Contract {
         let account = self.internal_unwrap_account(account_id);
         let mut rewards = HashMap::new();
         for (proposal_id, vote_detail) in account.proposals {
-            let proposal = self.internal_unwrap_proposal(proposal_id);
+            let mut proposal = self.internal_unwrap_proposal(proposal_id);
             if proposal.status == Some(ProposalStatus::Expired) {
-                let incentive_key = if let ProposalKind::FarmingReward { .. } = proposal.kind {
-                    vote_detail.action.get_index() as u32
-                } else {
-                    0
-                };
-                if let Some(incentive) = proposal.incentive.get(&incentive_key) {
-                    let votes_total_amount = proposal.get_votes_total_amount_for_reward_calc(incentive_key);
-                    let reward_details = incentive.calc_reward(vote_detail.amount, votes_total_amount);
-                    reward_details.into_iter().for_each(|(reward_token, reward_amount)| {
-                        rewards.insert(reward_token.clone(), reward_amount + rewards.get(&reward_token).unwrap_or(&0_u128));
-                    });
+                if !proposal.is_settled {
+                    proposal.take_failed_conditional_deposits();
+                }
+                for (incentive_key, amount) in proposal.get_incentive_votes(&vote_detail) {
+                    if let Some(incentive) = proposal.incentive.get(&incentive_key) {
+                        let votes_total_amount = proposal.get_votes_total_amount_for_reward_calc(incentive_key);
+                        let reward_details = incentive.calc_reward(amount, votes_total_amount);
+                        reward_details.into_iter().for_each(|(reward_token, reward_amount)| {
+                            rewards.insert(reward_token.clone(), reward_amount + rewards.get(&reward_token).unwrap_or(&0_u128));
+                        });
+                    }
                 }
             }
         }
@@ -0,0 +1,171 @@
+use crate::*;
+
+pub trait Checkpoint {
+    fn timestamp_sec(&self) -> u32;
+}
+
+/// The total veLPT at a moment, rolled forward by ve_slope_changes until the next checkpoint.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct TotalVeCheckpoint {
+    pub timestamp_sec: u32,
+    pub ve_lpt_amount: Balance,
+    pub ve_slope: Balance,
+}
+
+impl Checkpoint for TotalVeCheckpoint {
+    fn timestamp_sec(&self) -> u32 {
+        self.timestamp_sec
+    }
+}
+
+/// The veLPT of an account at a moment, with (end_sec, slope) of each of its decaying locks,
+/// and the delegated and received veLPT then.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+pub struct AccountVeCheckpoint {
+    pub timestamp_sec: u32,
+    pub ve_lpt_amount: Balance,
+    pub ve_slopes: Vec<(u32, Balance)>,
+    pub delegated_ve_lpt: Balance,
+    pub received_ve_lpt: Balance,
+}
+
+impl Checkpoint for AccountVeCheckpoint {
+    fn timestamp_sec(&self) -> u32 {
+        self.timestamp_sec
+    }
+}
+
+impl AccountVeCheckpoint {
+    /// The veLPT at timestamp_sec, no earlier than the checkpoint and no later than the next one.
+    pub fn get_ve_lpt_amount(&self, timestamp_sec: u32) -> Balance {
+        let decayed: Balance = self.ve_slopes.iter()
+            .map(|(end_sec, slope)| slope * std::cmp::min(*end_sec, timestamp_sec).saturating_sub(self.timestamp_sec) as u128)
+            .sum();
+        self.ve_lpt_amount - decayed
+    }
+}
+
+/// Append the checkpoint, the one made in the same second is replaced.
+pub fn push_checkpoint<T: Checkpoint + BorshSerialize + BorshDeserialize>(checkpoints: &mut Vector<T>, checkpoint: T) {
+    let len = checkpoints.len();
+    if len > 0 && checkpoints.get(len - 1).unwrap().timestamp_sec() == checkpoint.timestamp_sec() {
+        checkpoints.replace(len - 1, &checkpoint);
+    } else {
+        checkpoints.push(&checkpoint);
+    }
+}
+
+/// Append the checkpoint into the ring of at most max_len checkpoints, where the oldest one sits at dropped % len,
+/// once full, the oldest one is overwritten and counted into dropped.
+pub fn push_ring_checkpoint<T: Checkpoint + BorshSerialize + BorshDeserialize>(checkpoints: &mut Vector<T>, dropped: &mut u64, max_len: u64, checkpoint: T) {
+    let len = checkpoints.len();
+    if len > 0 {
+        let last = (*dropped + len - 1) % len;
+        if checkpoints.get(last).unwrap().timestamp_sec() == checkpoint.timestamp_sec() {
+            checkpoints.replace(last, &checkpoint);
+            return;
+        }
+    }
+    if len < max_len {
+        checkpoints.push(&checkpoint);
+    } else {
+        checkpoints.replace(*dropped % len, &checkpoint);
+        *dropped += 1;
+    }
+}
+
+/// Binary search the last checkpoint made at or before timestamp_sec.
+pub fn find_checkpoint<T: Checkpoint + BorshSerialize + BorshDeserialize>(checkpoints: &Vector<T>, timestamp_sec: u32) -> Option<T> {
+    find_ring_checkpoint(checkpoints, 0, timestamp_sec)
+}
+
+/// Binary search the last checkpoint made at or before timestamp_sec in the ring starting from dropped % len.
+pub fn find_ring_checkpoint<T: Checkpoint + BorshSerialize + BorshDeserialize>(checkpoints: &Vector<T>, dropped: u64, timestamp_sec: u32) -> Option<T> {
+    let len = checkpoints.len();
+    let get = |index: u64| checkpoints.get((dropped + index) % len).unwrap();
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = (low + high) / 2;
+        if get(mid).timestamp_sec() <= timestamp_sec {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low == 0 {
+        None
+    } else {
+        Some(get(low - 1))
+    }
+}
+
+impl Account {
+    /// Record the veLPT of a legacy account as it was before the upgrade, 
+    /// so that the history keeps it once the locks change.
+    pub fn seed_ve_checkpoint(&mut self) {
+        if let Some(ve_lpt_amount) = self.legacy_ve_lpt.take() {
+            if self.ve_checkpoints.is_empty() {
+                self.ve_checkpoints.push(&AccountVeCheckpoint { timestamp_sec: 0, ve_lpt_amount, ve_slopes: vec![], delegated_ve_lpt: 0, received_ve_lpt: 0 });
+            }
+        }
+    }
+
+    /// Record the veLPT from now on, should be called once the locks or the delegation changed.
+    pub fn checkpoint_ve_lpt(&mut self) {
+        self.seed_ve_checkpoint();
+        let timestamp = env::block_timestamp();
+        let timestamp_sec = nano_to_sec(timestamp);
+        let ve_slopes = self.locks.values()
+            .filter(|lock| lock.ve_slope > 0 && nano_to_sec(lock.unlock_timestamp) > timestamp_sec)
+            .map(|lock| (nano_to_sec(lock.unlock_timestamp), lock.ve_slope))
+            .collect();
+        let checkpoint = AccountVeCheckpoint {
+            timestamp_sec,
+            ve_lpt_amount: self.get_ve_lpt_amount(timestamp),
+            ve_slopes,
+            delegated_ve_lpt: self.delegated_ve_lpt,
+            received_ve_lpt: self.received_ve_lpt,
+        };
+        push_ring_checkpoint(&mut self.ve_checkpoints, &mut self.ve_checkpoints_dropped, MAX_ACCOUNT_VE_CHECKPOINTS, checkpoint);
+    }
+
+    /// The veLPT at timestamp_sec from the checkpoints,
+    /// an account without any checkpoint keeps its locks unchanged since before checkpoints exist,
+    /// None if timestamp_sec is older than the kept checkpoints.
+    pub fn get_ve_lpt_amount_at(&self, timestamp_sec: u32) -> Option<Balance> {
+        self.get_ve_state_at(timestamp_sec).map(|(ve_lpt_amount, _, _)| ve_lpt_amount)
+    }
+
+    /// The veLPT this account votes with on a Snapshot proposal started at timestamp_sec,
+    /// the delegation then is taken as is, so the delegated veLPT goes with the delegatee.
+    pub fn get_snapshot_voting_ve_lpt_amount(&self, timestamp_sec: u32) -> Option<Balance> {
+        self.get_ve_state_at(timestamp_sec)
+            .map(|(ve_lpt_amount, delegated_ve_lpt, received_ve_lpt)| (ve_lpt_amount + received_ve_lpt).saturating_sub(delegated_ve_lpt))
+    }
+
+    /// (own, delegated, received) veLPT at timestamp_sec.
+    fn get_ve_state_at(&self, timestamp_sec: u32) -> Option<(Balance, Balance, Balance)> {
+        if self.ve_checkpoints.is_empty() {
+            return Some((self.get_ve_lpt_amount(to_nano(timestamp_sec)), self.delegated_ve_lpt, self.received_ve_lpt));
+        }
+        match find_ring_checkpoint(&self.ve_checkpoints, self.ve_checkpoints_dropped, timestamp_sec) {
+            Some(checkpoint) => Some((checkpoint.get_ve_lpt_amount(timestamp_sec), checkpoint.delegated_ve_lpt, checkpoint.received_ve_lpt)),
+            None if self.ve_checkpoints_dropped > 0 => None,
+            None => Some((0, 0, 0)),
+        }
+    }
+}
+
+impl Contract {
+    /// The total veLPT at timestamp_sec, the future one is projected by the slope schedule.
+    pub fn internal_get_total_ve_lpt_at(&self, timestamp_sec: u32) -> Balance {
+        if timestamp_sec >= self.data().ve_checkpoint_sec {
+            return self.internal_total_ve_lpt_at(timestamp_sec).0;
+        }
+        find_checkpoint(&self.data().ve_checkpoints, timestamp_sec)
+            .map(|checkpoint| self.internal_roll_total_ve_lpt(checkpoint.ve_lpt_amount, checkpoint.ve_slope, checkpoint.timestamp_sec, timestamp_sec).0)
+            .unwrap_or(0)
+    }
+}
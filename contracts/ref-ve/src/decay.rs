@@ -0,0 +1,74 @@
+use crate::*;
+use std::ops::Bound;
+
+/// The veLPT a lock holds at a moment and how it declines from then on.
+#[derive(Clone, Default)]
+pub struct VeState {
+    pub ve_lpt_amount: Balance,
+    /// decreasing ve lpt per second until end_sec
+    pub slope: Balance,
+    pub end_sec: u32,
+}
+
+impl Contract {
+    /// Roll the total ve lpt from last checkpoint forward to timestamp_sec,
+    /// return (total ve lpt, ve slope) at that time.
+    pub fn internal_total_ve_lpt_at(&self, timestamp_sec: u32) -> (Balance, Balance) {
+        self.internal_roll_total_ve_lpt(self.data().cur_total_ve_lpt, self.data().ve_slope, self.data().ve_checkpoint_sec, timestamp_sec)
+    }
+
+    /// Roll the total ve lpt and ve slope at last_sec forward to timestamp_sec,
+    /// one step per DECAY_PERIOD_SEC at most as the decaying locks unlock at the period starts.
+    pub fn internal_roll_total_ve_lpt(&self, mut total: Balance, mut slope: Balance, mut last_sec: u32, timestamp_sec: u32) -> (Balance, Balance) {
+        if timestamp_sec <= last_sec {
+            return (total, slope);
+        }
+        for (end_sec, slope_change) in self.data().ve_slope_changes.range((Bound::Excluded(last_sec), Bound::Included(timestamp_sec))) {
+            total -= slope * (end_sec - last_sec) as u128;
+            slope -= slope_change;
+            last_sec = end_sec;
+        }
+        total -= slope * (timestamp_sec - last_sec) as u128;
+        (total, slope)
+    }
+
+    pub fn internal_cur_total_ve_lpt(&self) -> Balance {
+        self.internal_total_ve_lpt_at(nano_to_sec(env::block_timestamp())).0
+    }
+
+    fn internal_checkpoint_total_ve_lpt(&mut self) -> u32 {
+        let now_sec = nano_to_sec(env::block_timestamp());
+        let (total, slope) = self.internal_total_ve_lpt_at(now_sec);
+        self.data_mut().cur_total_ve_lpt = total;
+        self.data_mut().ve_slope = slope;
+        self.data_mut().ve_checkpoint_sec = std::cmp::max(now_sec, self.data().ve_checkpoint_sec);
+        now_sec
+    }
+
+    /// Replace a lock's contribution to the total ve lpt from prev to cur, both taken at current time.
+    pub fn internal_sync_total_ve_lpt(&mut self, prev: &VeState, cur: &VeState) {
+        let now_sec = self.internal_checkpoint_total_ve_lpt();
+        self.data_mut().cur_total_ve_lpt -= prev.ve_lpt_amount;
+        self.data_mut().cur_total_ve_lpt += cur.ve_lpt_amount;
+        if prev.slope > 0 && prev.end_sec > now_sec {
+            self.data_mut().ve_slope -= prev.slope;
+            let remain = self.data().ve_slope_changes.get(&prev.end_sec).unwrap() - prev.slope;
+            if remain > 0 {
+                self.data_mut().ve_slope_changes.insert(&prev.end_sec, &remain);
+            } else {
+                self.data_mut().ve_slope_changes.remove(&prev.end_sec);
+            }
+        }
+        if cur.slope > 0 && cur.end_sec > now_sec {
+            self.data_mut().ve_slope += cur.slope;
+            let total = self.data().ve_slope_changes.get(&cur.end_sec).unwrap_or(0) + cur.slope;
+            self.data_mut().ve_slope_changes.insert(&cur.end_sec, &total);
+        }
+        let checkpoint = TotalVeCheckpoint {
+            timestamp_sec: now_sec,
+            ve_lpt_amount: self.data().cur_total_ve_lpt,
+            ve_slope: self.data().ve_slope,
+        };
+        push_checkpoint(&mut self.data_mut().ve_checkpoints, checkpoint);
+    }
+}
@@ -11,6 +11,17 @@ pub const E103_STILL_HAS_REWARD: &str = "E103: still has reward";
 pub const E104_STILL_HAS_LPT: &str = "E104: still has lpt";
 pub const E105_ACC_NOT_LOCKED: &str = "E105: account not locked";
 pub const E106_STILL_HAS_LOVE_TOKEN: &str = "E106: still has love token";
+pub const E107_TOO_MANY_LOCK_POSITIONS: &str = "E107: too many lock positions";
+pub const E108_LOCK_POOL_MISMATCH: &str = "E108: lock position belongs to another lptoken";
+pub const E109_EXTEND_OTHERS_LOCK: &str = "E109: can not extend lock of another account";
+pub const E110_AUTO_RELOCKED: &str = "E110: lock is auto relocked";
+pub const E111_AUTO_RELOCK_UNCHANGED: &str = "E111: auto relock unchanged";
+pub const E112_LOVE_NOT_TRANSFERABLE: &str = "E112: love token not transferable";
+pub const E113_LOVE_RECEIVER_NOT_WHITELISTED: &str = "E113: love token receiver not whitelisted";
+pub const E114_NOT_DELEGATED: &str = "E114: not delegated";
+pub const E115_INVALID_DELEGATEE: &str = "E115: invalid delegatee";
+pub const E116_LOVE_NOT_MOVABLE: &str = "E116: love amount can not be moved with the locks";
+pub const E117_VE_LPT_HISTORY_DROPPED: &str = "E117: veLPT at the time is no longer recorded";
 
 pub const E200_ALREADY_VOTED: &str = "E200: already voted";
 pub const E201_INVALID_VOTE: &str = "E201: invalid vote";
@@ -21,6 +32,7 @@ pub const E205_NOT_VOTABLE: &str = "E205: not votable";
 pub const E206_NO_VOTED: &str = "E206: no voted";
 pub const E207_INVALID_INCENTIVE_KEY: &str = "E207: invalid incentive key";
 pub const E208_DESCRIPTION_TOO_LONG: &str = "E208: description len need less than or equal to 2048";
+pub const E209_INCENTIVE_DEPOSIT_TOO_SMALL: &str = "E209: incentive deposit too small";
 
 pub const E301_INVALID_RATIO: &str = "E301: invalid ratio";
 pub const E302_INVALID_DURATION: &str = "E302: invalid duration";
@@ -29,10 +41,31 @@ pub const E304_CAUSE_PRE_UNLOCK: &str = "E304: would cause pre unlock";
 pub const E305_STILL_IN_LOCK: &str = "E305: still in locking";
 pub const E306_INVALID_LOCK_DURATION_LIMIT: &str = "E306: invalid lock duration limit";
 pub const E307_INVALID_VOTING_DURATION_LIMIT: &str = "E307: invalid voting duration limit";
+pub const E308_INVALID_PENALTY_RATE: &str = "E308: invalid penalty rate";
+pub const E309_INVALID_VE_WEIGHT: &str = "E309: invalid ve weight";
+pub const E310_INVALID_MULTIPLIER_CURVE: &str = "E310: invalid locking multiplier curve";
+pub const E311_INVALID_PROPOSAL_THRESHOLD: &str = "E311: invalid proposal threshold";
+pub const E312_INVALID_PROPOSAL_BOND: &str = "E312: invalid proposal bond";
+pub const E313_INVALID_CLAIM_GRACE_PERIOD: &str = "E313: invalid claim grace period";
 
 pub const E402_INVALID_START_TIME: &str = "E402: invalid start time";
 pub const E404_PROPOSAL_NOT_EXIST: &str = "E404: proposal not exist";
 pub const E406_EXPIRED_PROPOSAL: &str = "E406: expired proposal";
+pub const E407_NOT_EXECUTABLE: &str = "E407: proposal not executable";
+pub const E408_PROPOSAL_NOT_PASSED: &str = "E408: proposal not passed";
+pub const E409_TIMELOCK_NOT_ELAPSED: &str = "E409: timelock not elapsed";
+pub const E410_INVALID_EXECUTION_ACTION: &str = "E410: invalid execution action";
+pub const E411_INSUFFICIENT_EXECUTION_GAS: &str = "E411: insufficient gas for execution";
+pub const E412_INVALID_POLL_MODE: &str = "E412: invalid poll mode";
+pub const E413_NOT_PERMISSIONLESS_KIND: &str = "E413: proposal kind needs whitelisted proposer";
+pub const E414_BOND_NOT_CLAIMABLE: &str = "E414: bond not claimable";
+pub const E415_PROPOSAL_NOT_UPDATABLE: &str = "E415: proposal not updatable";
+pub const E416_PROPOSAL_KIND_CHANGED: &str = "E416: proposal kind can not change";
+pub const E417_PROPOSAL_NOT_VETOABLE: &str = "E417: proposal not vetoable";
+pub const E418_INCENTIVE_NOT_SWEEPABLE: &str = "E418: incentive not sweepable";
+pub const E419_EXECUTION_GAS_EXCEEDED: &str = "E419: execution gas exceeds the transaction limit";
+pub const E420_INCENTIVE_FARM_CHANGED: &str = "E420: farm with incentives can not change";
+pub const E421_INSUFFICIENT_EXECUTION_TREASURY: &str = "E421: insufficient execution treasury";
 
 pub const E500_INVALID_MSG: &str = "E500: invalid msg";
 pub const E501_INVALID_FARM_INFO: &str = "E501: invalid farm info";
@@ -40,4 +73,7 @@ pub const E502_INVALID_TOKEN_ID: &str = "E502: invalid token id";
 pub const E503_FIRST_LOCK_TOO_FEW: &str = "E503: first lock too few";
 
 pub const E600_MFT_INVALID_LPTOKEN_ID: &str = "E600: MFT lptoken id is invalid";
-pub const E601_MFT_INVALID_LPTOKEN_CONTRACT: &str = "E601: MFT lptoken contract id is invalid";
\ No newline at end of file
+pub const E601_MFT_INVALID_LPTOKEN_CONTRACT: &str = "E601: MFT lptoken contract id is invalid";
+pub const E602_LOCK_POOL_ALREADY_EXIST: &str = "E602: lptoken already registered";
+pub const E603_FT_INVALID_LOCK_TOKEN: &str = "E603: FT token is not lockable";
+pub const E604_INVALID_LOCK_POOL_DECIMALS: &str = "E604: invalid lock pool decimals";
@@ -4,7 +4,8 @@ use near_sdk::serde_json::json;
 const EVENT_STANDARD: &str = "ref-ve";
 const EVENT_STANDARD_VERSION: &str = "1.0.0";
 
-#[derive(Serialize, Debug, Clone)]
+#[derive(Serialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
 #[serde(crate = "near_sdk::serde")]
 #[serde(tag = "event", content = "data")]
 #[serde(rename_all = "snake_case")]
@@ -20,6 +21,50 @@ pub enum Event<'a> {
         proposer_id: &'a AccountId,
         proposal_id: u32,
     },
+    ProposalUpdate {
+        proposer_id: &'a AccountId,
+        proposal_id: u32,
+        kind: &'a String,
+        end_at: u64,
+    },
+    ProposalVeto {
+        guardian_id: &'a AccountId,
+        proposal_id: u32,
+    },
+    ProposalExpire {
+        proposal_id: u32,
+        outcome: &'a Option<ProposalOutcome>,
+        ranked_choice_result: &'a Option<RankedChoiceResult>,
+    },
+    ProposalExecute {
+        executor_id: &'a AccountId,
+        proposal_id: u32,
+    },
+    ProposalExecuteResult {
+        proposal_id: u32,
+        success: bool,
+    },
+    ProposalBondTransfer {
+        proposal_id: u32,
+        receiver_id: &'a AccountId,
+        asset: &'a BondAsset,
+        amount: &'a U128,
+        success: bool,
+    },
+    IncentiveSweep {
+        caller_id: &'a AccountId,
+        proposal_id: u32,
+        incentive_key: u32,
+        token_id: &'a AccountId,
+        amount: &'a U128,
+    },
+    IncentiveRefund {
+        depositor_id: &'a AccountId,
+        proposal_id: u32,
+        incentive_key: u32,
+        token_id: &'a AccountId,
+        amount: &'a U128,
+    },
     RemovedProposalAssets {
         receiver_id: &'a AccountId,
         token_id: &'a AccountId,
@@ -38,12 +83,27 @@ pub enum Event<'a> {
     },
     LptWithdraw {
         caller_id: &'a AccountId,
+        token_id: &'a String,
         withdraw_amount: &'a U128,
         success: bool,
     },
     LptWithdrawLostfound {
         receiver_id: &'a AccountId,
+        token_id: &'a String,
+        withdraw_amount: &'a U128,
+        success: bool,
+    },
+    LptWithdrawSlashed {
+        caller_id: &'a AccountId,
+        token_id: &'a String,
         withdraw_amount: &'a U128,
+        slashed_amount: &'a U128,
+        success: bool,
+    },
+    SlashedLptTransfer {
+        receiver_id: &'a AccountId,
+        token_id: &'a String,
+        amount: &'a U128,
         success: bool,
     },
     RewardWithdraw {
@@ -57,6 +117,12 @@ pub enum Event<'a> {
         token_id: &'a AccountId,
         withdraw_amount: &'a U128,
     },
+    RemovedIncentiveWithdraw {
+        caller_id: &'a AccountId,
+        token_id: &'a AccountId,
+        withdraw_amount: &'a U128,
+        success: bool,
+    },
     
     RewardDeposit {
         caller_id: &'a AccountId,
@@ -66,19 +132,63 @@ pub enum Event<'a> {
         deposit_amount: &'a U128,
         total_amount: &'a U128,
         start_at: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        condition: &'a Option<IncentiveCondition>,
     },
     LptLock {
         caller_id: &'a AccountId,
+        token_id: &'a String,
+        lock_id: u32,
         deposit_amount: &'a U128,
         increased_ve_lpt: &'a U128,
         duration: u32,
     },
     LptAppend {
         caller_id: &'a AccountId,
+        token_id: &'a String,
+        lock_id: u32,
         deposit_amount: &'a U128,
         increased_ve_lpt: &'a U128,
         duration: u32,
     },
+    LptWithdrawCall {
+        caller_id: &'a AccountId,
+        token_id: &'a String,
+        receiver_id: &'a AccountId,
+        delivered_amount: &'a U128,
+        refunded_amount: &'a U128,
+        success: bool,
+    },
+    LockMove {
+        sender_id: &'a AccountId,
+        receiver_id: &'a AccountId,
+        love_amount: &'a U128,
+        ve_lpt_amount: &'a U128,
+    },
+    LockExtend {
+        caller_id: &'a AccountId,
+        token_id: &'a String,
+        lock_id: u32,
+        increased_ve_lpt: &'a U128,
+        duration: u32,
+    },
+    LockAutoRelock {
+        caller_id: &'a AccountId,
+        token_id: &'a String,
+        lock_id: u32,
+        auto_relock: bool,
+        increased_ve_lpt: &'a U128,
+    },
+    Delegate {
+        delegator_id: &'a AccountId,
+        delegatee_id: &'a AccountId,
+        ve_lpt_amount: &'a U128,
+    },
+    Undelegate {
+        delegator_id: &'a AccountId,
+        delegatee_id: &'a AccountId,
+        ve_lpt_amount: &'a U128,
+    },
 }
 
 impl Event<'_> {
@@ -133,6 +243,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_proposal_create_config_change() {
+        let kind = &format!("{:?}", ProposalKind::ConfigChange { config: Default::default(), whitelists: Default::default() });
+        Event::ProposalCreate { proposer_id: &alice(), proposal_id: 0, kind, start_at: 1000, duration_sec: 500 }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_create","data":[{"proposer_id":"alice","proposal_id":0,"kind":"ConfigChange { .. }","start_at":1000,"duration_sec":500}]}"#
+        );
+    }
+
     #[test]
     fn event_proposal_remove() {
         let proposer_id = &alice();
@@ -184,24 +304,53 @@ mod tests {
     #[test]
     fn event_lpt_withdraw() {
         let caller_id = &alice();
+        let token_id = &":0".to_string();
         let withdraw_amount = &U128(100);
         let success = true;
-        Event::LptWithdraw { caller_id, withdraw_amount, success }.emit();
+        Event::LptWithdraw { caller_id, token_id, withdraw_amount, success }.emit();
         assert_eq!(
             test_utils::get_logs()[0],
-            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lpt_withdraw","data":[{"caller_id":"alice","withdraw_amount":"100","success":true}]}"#
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lpt_withdraw","data":[{"caller_id":"alice","token_id":":0","withdraw_amount":"100","success":true}]}"#
         );
     }
 
     #[test]
     fn event_lpt_withdraw_lostfound() {
         let receiver_id = &alice();
+        let token_id = &":0".to_string();
         let withdraw_amount = &U128(100);
         let success = true;
-        Event::LptWithdrawLostfound { receiver_id, withdraw_amount, success }.emit();
+        Event::LptWithdrawLostfound { receiver_id, token_id, withdraw_amount, success }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lpt_withdraw_lostfound","data":[{"receiver_id":"alice","token_id":":0","withdraw_amount":"100","success":true}]}"#
+        );
+    }
+
+    #[test]
+    fn event_lpt_withdraw_slashed() {
+        let caller_id = &alice();
+        let token_id = &":0".to_string();
+        let withdraw_amount = &U128(90);
+        let slashed_amount = &U128(10);
+        let success = true;
+        Event::LptWithdrawSlashed { caller_id, token_id, withdraw_amount, slashed_amount, success }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lpt_withdraw_slashed","data":[{"caller_id":"alice","token_id":":0","withdraw_amount":"90","slashed_amount":"10","success":true}]}"#
+        );
+    }
+
+    #[test]
+    fn event_slashed_lpt_transfer() {
+        let receiver_id = &alice();
+        let token_id = &":0".to_string();
+        let amount = &U128(10);
+        let success = true;
+        Event::SlashedLptTransfer { receiver_id, token_id, amount, success }.emit();
         assert_eq!(
             test_utils::get_logs()[0],
-            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lpt_withdraw_lostfound","data":[{"receiver_id":"alice","withdraw_amount":"100","success":true}]}"#
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"slashed_lpt_transfer","data":[{"receiver_id":"alice","token_id":":0","amount":"10","success":true}]}"#
         );
     }
 
@@ -239,7 +388,7 @@ mod tests {
         let deposit_amount = &U128(100);
         let total_amount = &U128(1000);
         let start_at = 1000000;
-        Event::RewardDeposit { caller_id, proposal_id, incentive_key, token_id, deposit_amount, total_amount, start_at }.emit();
+        Event::RewardDeposit { caller_id, proposal_id, incentive_key, token_id, deposit_amount, total_amount, start_at, condition: &None }.emit();
         assert_eq!(
             test_utils::get_logs()[0],
             r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"reward_deposit","data":[{"caller_id":"alice","proposal_id":0,"incentive_key":0,"token_id":"ref","deposit_amount":"100","total_amount":"1000","start_at":1000000}]}"#
@@ -249,26 +398,217 @@ mod tests {
     #[test]
     fn event_lpt_deposit() {
         let caller_id = &alice();
+        let token_id = &":0".to_string();
+        let lock_id = 1;
         let deposit_amount = &U128(100);
         let increased_ve_lpt = &U128(200);
         let duration = 1000000;
-        Event::LptLock { caller_id, deposit_amount, increased_ve_lpt, duration }.emit();
+        Event::LptLock { caller_id, token_id, lock_id, deposit_amount, increased_ve_lpt, duration }.emit();
         assert_eq!(
             test_utils::get_logs()[0],
-            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lpt_lock","data":[{"caller_id":"alice","deposit_amount":"100","increased_ve_lpt":"200","duration":1000000}]}"#
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lpt_lock","data":[{"caller_id":"alice","token_id":":0","lock_id":1,"deposit_amount":"100","increased_ve_lpt":"200","duration":1000000}]}"#
         );
     }
 
     #[test]
     fn event_lpt_append() {
         let caller_id = &alice();
+        let token_id = &":0".to_string();
+        let lock_id = 1;
         let deposit_amount = &U128(100);
         let increased_ve_lpt = &U128(200);
         let duration = 1000000;
-        Event::LptAppend { caller_id, deposit_amount, increased_ve_lpt, duration }.emit();
+        Event::LptAppend { caller_id, token_id, lock_id, deposit_amount, increased_ve_lpt, duration }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lpt_append","data":[{"caller_id":"alice","token_id":":0","lock_id":1,"deposit_amount":"100","increased_ve_lpt":"200","duration":1000000}]}"#
+        );
+    }
+
+    #[test]
+    fn event_lock_extend() {
+        let caller_id = &alice();
+        let token_id = &":0".to_string();
+        let lock_id = 1;
+        let increased_ve_lpt = &U128(200);
+        let duration = 1000000;
+        Event::LockExtend { caller_id, token_id, lock_id, increased_ve_lpt, duration }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lock_extend","data":[{"caller_id":"alice","token_id":":0","lock_id":1,"increased_ve_lpt":"200","duration":1000000}]}"#
+        );
+    }
+
+    #[test]
+    fn event_lock_auto_relock() {
+        let caller_id = &alice();
+        let token_id = &":0".to_string();
+        let lock_id = 1;
+        let auto_relock = true;
+        let increased_ve_lpt = &U128(200);
+        Event::LockAutoRelock { caller_id, token_id, lock_id, auto_relock, increased_ve_lpt }.emit();
         assert_eq!(
             test_utils::get_logs()[0],
-            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lpt_append","data":[{"caller_id":"alice","deposit_amount":"100","increased_ve_lpt":"200","duration":1000000}]}"#
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lock_auto_relock","data":[{"caller_id":"alice","token_id":":0","lock_id":1,"auto_relock":true,"increased_ve_lpt":"200"}]}"#
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn event_lpt_withdraw_call() {
+        let caller_id = &alice();
+        let token_id = &":0".to_string();
+        let receiver_id = &AccountId::new_unchecked("farm".to_string());
+        let delivered_amount = &U128(70);
+        let refunded_amount = &U128(30);
+        let success = true;
+        Event::LptWithdrawCall { caller_id, token_id, receiver_id, delivered_amount, refunded_amount, success }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lpt_withdraw_call","data":[{"caller_id":"alice","token_id":":0","receiver_id":"farm","delivered_amount":"70","refunded_amount":"30","success":true}]}"#
+        );
+    }
+
+    #[test]
+    fn event_lock_move() {
+        let sender_id = &alice();
+        let receiver_id = &AccountId::new_unchecked("bob".to_string());
+        let love_amount = &U128(100);
+        let ve_lpt_amount = &U128(100);
+        Event::LockMove { sender_id, receiver_id, love_amount, ve_lpt_amount }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"lock_move","data":[{"sender_id":"alice","receiver_id":"bob","love_amount":"100","ve_lpt_amount":"100"}]}"#
+        );
+    }
+
+    #[test]
+    fn event_delegate() {
+        let delegator_id = &alice();
+        let delegatee_id = &AccountId::new_unchecked("bob".to_string());
+        let ve_lpt_amount = &U128(100);
+        Event::Delegate { delegator_id, delegatee_id, ve_lpt_amount }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"delegate","data":[{"delegator_id":"alice","delegatee_id":"bob","ve_lpt_amount":"100"}]}"#
+        );
+    }
+
+    #[test]
+    fn event_undelegate() {
+        let delegator_id = &alice();
+        let delegatee_id = &AccountId::new_unchecked("bob".to_string());
+        let ve_lpt_amount = &U128(100);
+        Event::Undelegate { delegator_id, delegatee_id, ve_lpt_amount }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"undelegate","data":[{"delegator_id":"alice","delegatee_id":"bob","ve_lpt_amount":"100"}]}"#
+        );
+    }
+
+    #[test]
+    fn event_proposal_execute() {
+        let executor_id = &alice();
+        Event::ProposalExecute { executor_id, proposal_id: 0 }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_execute","data":[{"executor_id":"alice","proposal_id":0}]}"#
+        );
+    }
+
+    #[test]
+    fn event_proposal_execute_result() {
+        Event::ProposalExecuteResult { proposal_id: 0, success: false }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_execute_result","data":[{"proposal_id":0,"success":false}]}"#
+        );
+    }
+
+    #[test]
+    fn event_proposal_expire() {
+        let ranked_choice_result = &Some(RankedChoiceResult { winner: Some(1), rounds: vec![vec![U128(1), U128(2)]] });
+        Event::ProposalExpire { proposal_id: 0, outcome: &Some(ProposalOutcome::Passed), ranked_choice_result }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_expire","data":[{"proposal_id":0,"outcome":"Passed","ranked_choice_result":{"winner":1,"rounds":[["1","2"]]}}]}"#
+        );
+    }
+
+    #[test]
+    fn event_proposal_bond_transfer() {
+        let receiver_id = &alice();
+        let asset = &BondAsset::Lpt { token_id: ":0".to_string() };
+        Event::ProposalBondTransfer { proposal_id: 0, receiver_id, asset, amount: &U128(100), success: true }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_bond_transfer","data":[{"proposal_id":0,"receiver_id":"alice","asset":{"Lpt":{"token_id":":0"}},"amount":"100","success":true}]}"#
+        );
+    }
+
+    #[test]
+    fn event_proposal_update() {
+        let proposer_id = &alice();
+        let kind = &format!("{:?}", ProposalKind::Poll { options: vec!["topic1".to_string()] });
+        Event::ProposalUpdate { proposer_id, proposal_id: 0, kind, end_at: 1000 }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_update","data":[{"proposer_id":"alice","proposal_id":0,"kind":"Poll { options: [\"topic1\"] }","end_at":1000}]}"#
+        );
+    }
+
+    #[test]
+    fn event_proposal_veto() {
+        let guardian_id = &alice();
+        Event::ProposalVeto { guardian_id, proposal_id: 0 }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"proposal_veto","data":[{"guardian_id":"alice","proposal_id":0}]}"#
+        );
+    }
+
+    #[test]
+    fn event_removed_incentive_withdraw() {
+        let caller_id = &alice();
+        let token_id = &token_id();
+        let withdraw_amount = &U128(100);
+        Event::RemovedIncentiveWithdraw { caller_id, token_id, withdraw_amount, success: true }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"removed_incentive_withdraw","data":[{"caller_id":"alice","token_id":"ref","withdraw_amount":"100","success":true}]}"#
+        );
+    }
+
+    #[test]
+    fn event_incentive_sweep() {
+        let caller_id = &alice();
+        let token_id = &token_id();
+        Event::IncentiveSweep { caller_id, proposal_id: 0, incentive_key: 1, token_id, amount: &U128(100) }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"incentive_sweep","data":[{"caller_id":"alice","proposal_id":0,"incentive_key":1,"token_id":"ref","amount":"100"}]}"#
+        );
+    }
+
+    #[test]
+    fn event_incentive_refund() {
+        let depositor_id = &alice();
+        let token_id = &token_id();
+        Event::IncentiveRefund { depositor_id, proposal_id: 0, incentive_key: 1, token_id, amount: &U128(100) }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"incentive_refund","data":[{"depositor_id":"alice","proposal_id":0,"incentive_key":1,"token_id":"ref","amount":"100"}]}"#
+        );
+    }
+
+    #[test]
+    fn event_reward_deposit_with_condition() {
+        let caller_id = &alice();
+        let token_id = &token_id();
+        let condition = &Some(IncentiveCondition::TopRank { rank: 1 });
+        Event::RewardDeposit { caller_id, proposal_id: 0, incentive_key: 0, token_id, deposit_amount: &U128(100), total_amount: &U128(100), start_at: 1000000, condition }.emit();
+        assert_eq!(
+            test_utils::get_logs()[0],
+            r#"EVENT_JSON:{"standard":"ref-ve","version":"1.0.0","event":"reward_deposit","data":[{"caller_id":"alice","proposal_id":0,"incentive_key":0,"token_id":"ref","deposit_amount":"100","total_amount":"100","start_at":1000000,"condition":{"TopRank":{"rank":1}}}]}"#
+        );
+    }
+}
@@ -8,7 +8,7 @@ pub struct ConfigV0100 {
     pub max_locking_multiplier: u32,
 }
 
-impl From<ConfigV0100> for Config {
+impl From<ConfigV0100> for ConfigV0200 {
     fn from(a: ConfigV0100) -> Self {
         Self { 
             min_proposal_start_vote_offset_sec: a.min_proposal_start_vote_offset_sec,
@@ -42,7 +42,7 @@ pub struct ContractDataV0100 {
     pub removed_proposal_assets: UnorderedMap<AccountId, Balance>
 }
 
-impl From<ContractDataV0100> for ContractData {
+impl From<ContractDataV0100> for ContractDataV0200 {
     fn from(a: ContractDataV0100) -> Self {
         let ContractDataV0100 {
             owner_id,
@@ -83,4 +83,270 @@ impl From<ContractDataV0100> for ContractData {
             removed_proposal_assets
         }
     }
-}
\ No newline at end of file
+}
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ConfigV0200 {
+    pub min_proposal_start_vote_offset_sec: u32,
+    pub min_locking_duration_sec: DurationSec,
+    pub max_locking_duration_sec: DurationSec,
+    pub max_locking_multiplier: u32,
+    pub min_voting_duration_sec: DurationSec,
+    pub max_voting_duration_sec: DurationSec,
+}
+
+impl From<ConfigV0200> for Config {
+    fn from(a: ConfigV0200) -> Self {
+        Self { 
+            min_proposal_start_vote_offset_sec: a.min_proposal_start_vote_offset_sec,
+            min_locking_duration_sec: a.min_locking_duration_sec,
+            max_locking_duration_sec: a.max_locking_duration_sec,
+            max_locking_multiplier: a.max_locking_multiplier,
+            locking_multiplier_curve: vec![],
+            min_voting_duration_sec: a.min_voting_duration_sec,
+            max_voting_duration_sec: a.max_voting_duration_sec,
+            max_early_unlock_penalty_rate: DEFAULT_MAX_EARLY_UNLOCK_PENALTY_RATE,
+            early_unlock_penalty_sink: PenaltySink::Lockers,
+            decay_mode: DecayMode::Disabled,
+            lock_receiver_policy: LockReceiverPolicy::RegisteredOnly,
+            love_transfer_policy: LoveTransferPolicy::Transferable,
+            proposal_thresholds: ProposalThresholds::default(),
+            execution_timelock_sec: DEFAULT_EXECUTION_TIMELOCK_SEC,
+            permissionless_proposal: None,
+            incentive_sweep_policy: IncentiveSweepPolicy::default(),
+        }
+    }
+}
+
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct ContractDataV0200 {
+    pub owner_id: AccountId,
+    pub operators: UnorderedSet<AccountId>,
+    pub whitelisted_accounts: UnorderedSet<AccountId>,
+    pub whitelisted_incentive_tokens: HashSet<AccountId>,
+    pub config: LazyOption<ConfigV0200>,
+    pub symbol: String,
+    pub lptoken_contract_id: AccountId,
+    pub lptoken_id: String,
+    pub lptoken_decimals: u8,
+    pub last_proposal_id: u32,
+    pub proposals: UnorderedMap<u32, VProposal>,
+    pub accounts: LookupMap<AccountId, VAccount>,
+    pub account_count: u64,
+    pub cur_total_ve_lpt: Balance,
+    pub cur_lock_lpt: Balance,
+    pub lostfound: Balance,
+    pub removed_proposal_assets: UnorderedMap<AccountId, Balance>
+}
+
+impl From<ContractDataV0200> for ContractData {
+    fn from(a: ContractDataV0200) -> Self {
+        let ContractDataV0200 {
+            owner_id,
+            operators,
+            whitelisted_accounts,
+            whitelisted_incentive_tokens,
+            config,
+            symbol,
+            lptoken_contract_id,
+            lptoken_id,
+            lptoken_decimals,
+            last_proposal_id,
+            proposals,
+            accounts,
+            account_count,
+            cur_total_ve_lpt,
+            cur_lock_lpt,
+            lostfound,
+            removed_proposal_assets
+        } = a;
+        // the only lptoken becomes the first lock pool
+        let mut lock_pools = UnorderedMap::new(StorageKeys::LockPools);
+        let mut lock_pool = LockPool::new(lptoken_id.clone(), LockPoolKind::MultiFungibleToken, lptoken_decimals, BASE_VE_WEIGHT);
+        lock_pool.cur_lock_lpt = cur_lock_lpt;
+        lock_pool.lostfound = lostfound;
+        lock_pools.insert(&lptoken_id, &lock_pool);
+        // the legacy total has been unchanged until the upgrade
+        let mut ve_checkpoints = Vector::new(StorageKeys::VeCheckpoints);
+        ve_checkpoints.push(&TotalVeCheckpoint { timestamp_sec: 0, ve_lpt_amount: cur_total_ve_lpt, ve_slope: 0 });
+        // the tokens may still be held for voters, even if no longer whitelisted
+        let mut incentive_tokens = whitelisted_incentive_tokens.clone();
+        incentive_tokens.extend(removed_proposal_assets.keys());
+        for proposal in proposals.values() {
+            let proposal: Proposal = proposal.into();
+            for incentive in proposal.incentive.values() {
+                incentive_tokens.extend(incentive.incentive_token_ids.iter().cloned());
+            }
+        }
+        Self {
+            owner_id,
+            operators,
+            whitelisted_accounts,
+            whitelisted_incentive_tokens,
+            incentive_tokens,
+            min_incentive_deposits: HashMap::new(),
+            whitelisted_love_receivers: HashSet::new(),
+            config: LazyOption::new(StorageKeys::Config, Some(&config.get().unwrap().into())),
+            symbol,
+            lptoken_contract_id,
+            lptoken_id,
+            lock_pools,
+            last_proposal_id,
+            proposals,
+            accounts,
+            account_count,
+            cur_total_ve_lpt,
+            ve_slope: 0,
+            ve_checkpoint_sec: 0,
+            ve_slope_changes: TreeMap::new(StorageKeys::VeSlopeChanges),
+            ve_checkpoints,
+            removed_proposal_assets,
+            removed_incentives: LookupMap::new(StorageKeys::RemovedIncentives),
+            guardians: UnorderedSet::new(StorageKeys::Guardian),
+            execution_treasury: 0,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct AccountV0200 {
+    pub sponsor_id: AccountId,
+    pub lpt_amount: Balance,
+    pub ve_lpt_amount: Balance,
+    pub unlock_timestamp: u64,
+    pub duration_sec: u32,
+    pub proposals: HashMap<u32, VoteDetail>,
+    pub proposals_history: UnorderedMap<u32, VoteDetail>,
+    pub rewards: HashMap<AccountId, Balance>,
+}
+
+impl AccountV0200 {
+    pub fn into_current(self, account_id: &AccountId, lptoken_id: &str) -> Account {
+        let AccountV0200 {
+            sponsor_id,
+            lpt_amount,
+            ve_lpt_amount,
+            unlock_timestamp,
+            duration_sec,
+            proposals,
+            proposals_history,
+            rewards
+        } = self;
+        let mut locks = HashMap::new();
+        if lpt_amount > 0 {
+            // the legacy lock becomes the default position
+            locks.insert(0, LockPosition {
+                token_id: lptoken_id.to_string(),
+                lpt_amount,
+                ve_lpt_amount,
+                unlock_timestamp,
+                duration_sec,
+                ve_slope: 0,
+                // the accumulator starts from zero at the upgrade, 
+                // so a legacy lock has been locking since then.
+                slashed_lpt_per_share_paid: 0,
+                auto_relock: false,
+            });
+        }
+        Account {
+            storage_paid: sponsor_id != env::current_account_id(),
+            storage_available: 0,
+            sponsor_id,
+            locks,
+            proposals,
+            proposals_history,
+            rewards,
+            delegatee: None,
+            delegated_ve_lpt: 0,
+            received_ve_lpt: 0,
+            delegation_overrides: HashMap::new(),
+            ve_checkpoints: Vector::new(StorageKeys::AccountVeCheckpoints { account_id: account_id.clone() }),
+            ve_checkpoints_dropped: 0,
+            // the history can not be written here as the account may be loaded by a view
+            legacy_ve_lpt: Some(ve_lpt_amount),
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ProposalV0200 {
+    pub id: u32,
+    pub description: String,
+    pub proposer: AccountId,
+    pub kind: ProposalKind,
+    pub votes: Vec<VoteInfo>,
+    pub ve_amount_at_last_action: u128,
+    pub start_at: Timestamp,
+    pub end_at: Timestamp,
+    pub participants: u64,
+    pub incentive: HashMap<u32, ProposalIncentiveV0200>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ProposalIncentiveV0200 {
+    pub incentive_token_ids: Vec<AccountId>,
+    pub incentive_amounts: Vec<Balance>,
+    pub claimed_amounts: Vec<Balance>,
+}
+
+impl From<ProposalIncentiveV0200> for ProposalIncentive {
+    fn from(a: ProposalIncentiveV0200) -> Self {
+        let ProposalIncentiveV0200 {
+            incentive_token_ids,
+            incentive_amounts,
+            claimed_amounts,
+        } = a;
+        Self {
+            incentive_token_ids,
+            incentive_amounts,
+            swept_amounts: vec![0; claimed_amounts.len()],
+            claimed_amounts,
+            claimed_ballots: 0,
+            deposits: vec![],
+        }
+    }
+}
+
+impl From<ProposalV0200> for Proposal {
+    fn from(a: ProposalV0200) -> Self {
+        let ProposalV0200 {
+            id,
+            description,
+            proposer,
+            kind,
+            votes,
+            ve_amount_at_last_action,
+            start_at,
+            end_at,
+            participants,
+            incentive,
+        } = a;
+        let threshold = ProposalThresholds::default().get(&kind).clone();
+        let voted_amount = votes.iter().map(|v| v.total_ballots).sum();
+        Self {
+            id,
+            description,
+            proposer,
+            kind,
+            threshold,
+            voting_mode: VotingMode::Live,
+            poll_mode: PollMode::Single,
+            votes,
+            voted_amount,
+            ranked_ballots: vec![],
+            ve_amount_at_last_action,
+            start_at,
+            end_at,
+            participants,
+            incentive: incentive.into_iter().map(|(key, incentive)| (key, incentive.into())).collect(),
+            status: None,
+            is_nonsense: None,
+            execution: None,
+            bond: None,
+            vetoed_by: None,
+            sweeps: vec![],
+            is_settled: false,
+            outcome: None,
+            ranked_choice_result: None,
+        }
+    }
+}
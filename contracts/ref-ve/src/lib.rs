@@ -12,6 +12,9 @@ mod actions_of_reward;
 mod proposals;
 mod proposals_action;
 mod proposals_incentive;
+mod proposals_execution;
+mod proposals_poll;
+mod proposals_bond;
 mod token_receiver;
 mod storage_impl;
 mod errors;
@@ -19,6 +22,10 @@ mod events;
 mod utils;
 mod views;
 mod legacy;
+mod decay;
+mod pool;
+mod love_token;
+mod checkpoint;
 
 pub use crate::owner::*;
 pub use crate::account::*;
@@ -29,6 +36,9 @@ pub use crate::actions_of_reward::*;
 pub use crate::proposals::*;
 pub use crate::proposals_action::*;
 pub use crate::proposals_incentive::*;
+pub use crate::proposals_execution::*;
+pub use crate::proposals_poll::*;
+pub use crate::proposals_bond::*;
 pub use crate::token_receiver::*;
 pub use crate::storage_impl::*;
 pub use crate::errors::*;
@@ -36,6 +46,10 @@ pub use crate::events::*;
 pub use crate::utils::*;
 pub use crate::views::*;
 pub use crate::legacy::*;
+pub use crate::decay::*;
+pub use crate::pool::*;
+pub use crate::love_token::*;
+pub use crate::checkpoint::*;
 
 use near_contract_standards::fungible_token::metadata::{
     FungibleTokenMetadata, FungibleTokenMetadataProvider, FT_METADATA_SPEC,
@@ -43,8 +57,8 @@ use near_contract_standards::fungible_token::metadata::{
 use near_contract_standards::fungible_token::FungibleToken;
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::{LazyOption, LookupMap, UnorderedMap, UnorderedSet};
-use near_sdk::json_types::U128;
+use near_sdk::collections::{LazyOption, LookupMap, TreeMap, UnorderedMap, UnorderedSet, Vector};
+use near_sdk::json_types::{Base64VecU8, U128, U64};
 use near_sdk::serde::{Deserialize, Serialize};
 use near_sdk::BorshStorageKey;
 use near_sdk::{
@@ -62,7 +76,129 @@ pub(crate) enum StorageKeys {
     WhitelistedAccounts,
     Proposals,
     AccountProposalHistory { account_id: AccountId },
-    RemovedProposalAssets
+    RemovedProposalAssets,
+    VeSlopeChanges,
+    LockPools,
+    VeCheckpoints,
+    AccountVeCheckpoints { account_id: AccountId },
+    Guardian,
+    RemovedIncentives,
+}
+
+/// Where the LPT slashed from an early unlock goes.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum PenaltySink {
+    /// Transfer the slashed LPT to the given treasury account.
+    Treasury { account_id: AccountId },
+    /// Share the slashed LPT among remaining lockers, pro rata to their locked LPT.
+    Lockers,
+}
+
+/// How the veLPT of a new lock declines as its unlock_timestamp approaches.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum DecayMode {
+    /// veLPT keeps unchanged until unlock.
+    Disabled,
+    /// veLPT declines linearly to the amount of LPT locked (1x multiplier).
+    ToBase,
+    /// veLPT declines linearly to zero.
+    ToZero,
+}
+
+/// How the love token can be transferred.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum LoveTransferPolicy {
+    /// The love token can be transferred freely, the locking stays with the sender.
+    Transferable,
+    /// The love token can not be transferred.
+    NonTransferable,
+    /// The love token can only be transferred to whitelisted_love_receivers.
+    WhitelistedReceivers,
+    /// Transferring love token moves a proportional slice of the sender's locks,
+    /// together with the veLPT and vote weight, to the receiver.
+    MoveLock,
+}
+
+/// A breakpoint of the locking multiplier curve.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct LockingMultiplierPoint {
+    pub duration_sec: DurationSec,
+    /// E.g. 15000 means 150% multiplier (or 1.5X).
+    pub multiplier: u32,
+}
+
+/// What a proposal needs to pass, in BP.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalThreshold {
+    /// The min share of all ballots in the total veLPT at start_at.
+    pub quorum: u32,
+    /// The share of approval ballots (or the leading option for a poll and farming reward)
+    /// that must be exceeded.
+    pub approval: u32,
+}
+
+impl ProposalThreshold {
+    pub fn assert_valid(&self) {
+        require!(
+            self.quorum <= MAX_PROPOSAL_THRESHOLD && self.approval < MAX_PROPOSAL_THRESHOLD,
+            E311_INVALID_PROPOSAL_THRESHOLD
+        );
+    }
+}
+
+/// The threshold of each proposal kind.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalThresholds {
+    pub farming_reward: ProposalThreshold,
+    pub poll: ProposalThreshold,
+    pub common: ProposalThreshold,
+    /// For executable and config change proposals.
+    pub executable: ProposalThreshold,
+}
+
+impl ProposalThresholds {
+    pub fn get(&self, kind: &ProposalKind) -> &ProposalThreshold {
+        match kind {
+            ProposalKind::FarmingReward { .. } => &self.farming_reward,
+            ProposalKind::Poll { .. } => &self.poll,
+            ProposalKind::Common => &self.common,
+            ProposalKind::Executable { .. } | ProposalKind::ConfigChange { .. } => &self.executable,
+        }
+    }
+}
+
+impl Default for ProposalThresholds {
+    fn default() -> Self {
+        ProposalThresholds {
+            farming_reward: ProposalThreshold { quorum: 0, approval: 0 },
+            poll: ProposalThreshold { quorum: 0, approval: 0 },
+            common: ProposalThreshold { quorum: 0, approval: DEFAULT_APPROVAL_THRESHOLD },
+            executable: ProposalThreshold { quorum: DEFAULT_EXECUTABLE_QUORUM, approval: DEFAULT_APPROVAL_THRESHOLD },
+        }
+    }
+}
+
+/// Who can be the receiver when locking on behalf of another account.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum LockReceiverPolicy {
+    /// The receiver must have registered itself.
+    RegisteredOnly,
+    /// An unregistered receiver would be registered with the sender as sponsor.
+    AllowUnregistered,
 }
 
 /// Contract config
@@ -79,10 +215,34 @@ pub struct Config {
     /// Assuming the 100% multiplier at the 0 duration. Should be no less than 100%.
     /// E.g. 20000 means 200% multiplier (or 2X).
     pub max_locking_multiplier: u32,
+    /// Breakpoints of the locking multiplier curve between (0, 100%) and
+    /// (max_locking_duration_sec, max_locking_multiplier), ordered by duration.
+    /// The multiplier is interpolated linearly between neighboring points, empty means linear all the way.
+    pub locking_multiplier_curve: Vec<LockingMultiplierPoint>,
     /// The min duration to voting in seconds.
     pub min_voting_duration_sec: DurationSec,
     /// The max duration to voting in seconds.
     pub max_voting_duration_sec: DurationSec,
+    /// The rate of LPT slashed when unlocking early with the max locking duration remaining,
+    /// decreasing linearly with the remaining locking time. E.g. 5000 means 50%.
+    pub max_early_unlock_penalty_rate: u32,
+    /// Where the slashed LPT goes.
+    pub early_unlock_penalty_sink: PenaltySink,
+    /// Decay mode applied to locks made from now on.
+    pub decay_mode: DecayMode,
+    /// Whether locking on behalf of an unregistered account is allowed.
+    pub lock_receiver_policy: LockReceiverPolicy,
+    /// How the love token can be transferred.
+    pub love_transfer_policy: LoveTransferPolicy,
+    /// What a proposal needs to pass, taken when the proposal is created.
+    pub proposal_thresholds: ProposalThresholds,
+    /// The delay after an executable proposal ends before it can be executed,
+    /// taken when the proposal is created.
+    pub execution_timelock_sec: DurationSec,
+    /// How an account out of whitelisted_accounts can create a proposal, disabled if None.
+    pub permissionless_proposal: Option<PermissionlessProposalPolicy>,
+    /// When and where the incentives left unclaimed after a proposal ends can be swept.
+    pub incentive_sweep_policy: IncentiveSweepPolicy,
 }
 
 impl Config {
@@ -98,6 +258,46 @@ impl Config {
         require!(self.min_voting_duration_sec < self.max_voting_duration_sec, 
             E307_INVALID_VOTING_DURATION_LIMIT
         );
+        require!(
+            self.max_early_unlock_penalty_rate <= MAX_EARLY_UNLOCK_PENALTY_RATE,
+            E308_INVALID_PENALTY_RATE
+        );
+        let mut prev = &LockingMultiplierPoint { duration_sec: 0, multiplier: MIN_LOCKING_REWARD_RATIO };
+        for point in self.locking_multiplier_curve.iter() {
+            require!(
+                prev.duration_sec < point.duration_sec && point.duration_sec < self.max_locking_duration_sec
+                    && prev.multiplier <= point.multiplier && point.multiplier <= self.max_locking_multiplier,
+                E310_INVALID_MULTIPLIER_CURVE
+            );
+            prev = point;
+        }
+        self.proposal_thresholds.farming_reward.assert_valid();
+        self.proposal_thresholds.poll.assert_valid();
+        self.proposal_thresholds.common.assert_valid();
+        self.proposal_thresholds.executable.assert_valid();
+        if let Some(policy) = &self.permissionless_proposal {
+            policy.assert_valid();
+        }
+        self.incentive_sweep_policy.assert_valid();
+    }
+
+    /// The multiplier beyond 100% for locking duration_sec, in (numerator, denominator).
+    pub fn get_extra_locking_multiplier(&self, duration_sec: DurationSec) -> (u128, u128) {
+        let duration_sec = std::cmp::min(duration_sec, self.max_locking_duration_sec);
+        let last = LockingMultiplierPoint { duration_sec: self.max_locking_duration_sec, multiplier: self.max_locking_multiplier };
+        let mut prev = &LockingMultiplierPoint { duration_sec: 0, multiplier: MIN_LOCKING_REWARD_RATIO };
+        for point in self.locking_multiplier_curve.iter().chain(std::iter::once(&last)) {
+            if duration_sec <= point.duration_sec {
+                let span = u128::from(point.duration_sec - prev.duration_sec);
+                return (
+                    u128::from(prev.multiplier - MIN_LOCKING_REWARD_RATIO) * span
+                        + u128::from(point.multiplier - prev.multiplier) * u128::from(duration_sec - prev.duration_sec),
+                    span * u128::from(MIN_LOCKING_REWARD_RATIO),
+                );
+            }
+            prev = point;
+        }
+        unreachable!()
     }
 }
 
@@ -108,8 +308,18 @@ impl Default for Config {
             min_locking_duration_sec: DEFAULT_MIN_LOCKING_DURATION_SEC,
             max_locking_duration_sec: DEFAULT_MAX_LOCKING_DURATION_SEC,
             max_locking_multiplier: DEFAULT_MAX_LOCKING_REWARD_RATIO,
+            locking_multiplier_curve: vec![],
             min_voting_duration_sec: DEFAULT_MIN_VOTING_DURATION_SEC,
             max_voting_duration_sec: DEFAULT_MAX_VOTING_DURATION_SEC,
+            max_early_unlock_penalty_rate: DEFAULT_MAX_EARLY_UNLOCK_PENALTY_RATE,
+            early_unlock_penalty_sink: PenaltySink::Lockers,
+            decay_mode: DecayMode::Disabled,
+            lock_receiver_policy: LockReceiverPolicy::RegisteredOnly,
+            love_transfer_policy: LoveTransferPolicy::Transferable,
+            proposal_thresholds: ProposalThresholds::default(),
+            execution_timelock_sec: DEFAULT_EXECUTION_TIMELOCK_SEC,
+            permissionless_proposal: None,
+            incentive_sweep_policy: IncentiveSweepPolicy::default(),
         }
     }
 }
@@ -120,6 +330,12 @@ pub struct ContractData {
     pub operators: UnorderedSet<AccountId>,
     pub whitelisted_accounts: UnorderedSet<AccountId>,
     pub whitelisted_incentive_tokens: HashSet<AccountId>,
+    // every token ever taken as an incentive, which may still be held for voters or depositors
+    pub incentive_tokens: HashSet<AccountId>,
+    // the min amount of each token an incentive deposit takes, keeping the deposits of a proposal small
+    pub min_incentive_deposits: HashMap<AccountId, Balance>,
+    // accounts can receive love token under LoveTransferPolicy::WhitelistedReceivers
+    pub whitelisted_love_receivers: HashSet<AccountId>,
 
     pub config: LazyOption<Config>,
 
@@ -127,9 +343,10 @@ pub struct ContractData {
     pub symbol: String,
     // where lptoken_id from
     pub lptoken_contract_id: AccountId,
-    // which lptoken used for locking
+    // the lptoken registered at init, legacy locks belong to it
     pub lptoken_id: String,
-    pub lptoken_decimals: u8,
+    // lptokens can be locked, keyed by lptoken id
+    pub lock_pools: UnorderedMap<String, LockPool>,
     
     /// Last available id for the proposals.
     pub last_proposal_id: u32,
@@ -139,22 +356,44 @@ pub struct ContractData {
     pub accounts: LookupMap<AccountId, VAccount>,
     pub account_count: u64,
 
-    // total ve lpt amount
+    // total ve lpt amount at ve_checkpoint_sec,
+    // use internal_cur_total_ve_lpt to get the current one
     pub cur_total_ve_lpt: Balance,
-    // total lock lpt amount
-    pub cur_lock_lpt: Balance,
+    // total decreasing ve lpt per second at ve_checkpoint_sec
+    pub ve_slope: Balance,
+    pub ve_checkpoint_sec: u32,
+    // slope to remove from ve_slope when locks reach unlock time, keyed by unlock time in seconds
+    pub ve_slope_changes: TreeMap<u32, Balance>,
+    // total ve lpt and ve slope after each change, ordered by time
+    pub ve_checkpoints: Vector<TotalVeCheckpoint>,
 
-    // if withdraw lpt encounter error, the lpt would go to here
-    pub lostfound: Balance,
-
-    pub removed_proposal_assets: UnorderedMap<AccountId, Balance>
+    pub removed_proposal_assets: UnorderedMap<AccountId, Balance>,
+    // incentives of removed or vetoed proposals, keyed by depositor then token
+    pub removed_incentives: LookupMap<AccountId, HashMap<AccountId, Balance>>,
+    // accounts can veto an inprogress proposal
+    pub guardians: UnorderedSet<AccountId>,
+    // the NEAR the execution actions can attach, kept apart from the bonds and storage deposits
+    pub execution_treasury: Balance,
 }
 
 /// Versioned contract data. Allows to easily upgrade contracts.
-#[derive(BorshSerialize, BorshDeserialize)]
+#[derive(BorshSerialize)]
 pub enum VersionedContractData {
     V0100(ContractDataV0100),
-    V0200(ContractData),
+    V0200(ContractDataV0200),
+    V0300(Box<ContractData>),
+}
+
+// derived BorshDeserialize on Box<T> needs T: Clone, which the collections don't implement
+impl BorshDeserialize for VersionedContractData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        match <u8 as BorshDeserialize>::deserialize(buf)? {
+            0 => Ok(VersionedContractData::V0100(ContractDataV0100::deserialize(buf)?)),
+            1 => Ok(VersionedContractData::V0200(ContractDataV0200::deserialize(buf)?)),
+            2 => Ok(VersionedContractData::V0300(Box::new(ContractData::deserialize(buf)?))),
+            _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Unexpected variant index")),
+        }
+    }
 }
 
 #[near_bindgen]
@@ -169,27 +408,37 @@ impl Contract {
     #[init]
     pub fn new(owner_id: AccountId, symbol: String, lptoken_contract_id: AccountId, lptoken_id: String, lptoken_decimals: u8) -> Self {
         require!(!env::state_exists(), E000_ALREADY_INIT);
+        let mut lock_pools = UnorderedMap::new(StorageKeys::LockPools);
+        lock_pools.insert(&lptoken_id, &LockPool::new(lptoken_id.clone(), LockPoolKind::MultiFungibleToken, lptoken_decimals, BASE_VE_WEIGHT));
         Self {
             ft: FungibleToken::new(b"a".to_vec()),
-            data: VersionedContractData::V0200(ContractData {
+            data: VersionedContractData::V0300(Box::new(ContractData {
                 owner_id,
                 operators: UnorderedSet::new(StorageKeys::Operator),
                 whitelisted_accounts: UnorderedSet::new(StorageKeys::WhitelistedAccounts),
                 whitelisted_incentive_tokens: HashSet::new(),
+                incentive_tokens: HashSet::new(),
+                min_incentive_deposits: HashMap::new(),
+                whitelisted_love_receivers: HashSet::new(),
                 config: LazyOption::new(StorageKeys::Config, Some(&Config::default())),
                 symbol,
                 lptoken_contract_id,
                 lptoken_id,
-                lptoken_decimals,
+                lock_pools,
                 last_proposal_id: 0,
                 proposals: UnorderedMap::new(StorageKeys::Proposals),
                 accounts: LookupMap::new(StorageKeys::Accounts),
                 account_count: 0,
                 cur_total_ve_lpt: 0,
-                cur_lock_lpt: 0,
-                lostfound: 0,
+                ve_slope: 0,
+                ve_checkpoint_sec: 0,
+                ve_slope_changes: TreeMap::new(StorageKeys::VeSlopeChanges),
+                ve_checkpoints: Vector::new(StorageKeys::VeCheckpoints),
                 removed_proposal_assets: UnorderedMap::new(StorageKeys::RemovedProposalAssets),
-            }),
+                removed_incentives: LookupMap::new(StorageKeys::RemovedIncentives),
+                guardians: UnorderedSet::new(StorageKeys::Guardian),
+                execution_treasury: 0,
+            })),
         }
     }
 }
@@ -202,7 +451,7 @@ impl Contract {
     #[allow(unreachable_patterns)]
     fn data(&self) -> &ContractData {
         match &self.data {
-            VersionedContractData::V0200(data) => data,
+            VersionedContractData::V0300(data) => data,
             _ => unimplemented!(),
         }
     }
@@ -210,7 +459,7 @@ impl Contract {
     #[allow(unreachable_patterns)]
     fn data_mut(&mut self) -> &mut ContractData {
         match &mut self.data {
-            VersionedContractData::V0200(data) => data,
+            VersionedContractData::V0300(data) => data,
             _ => unimplemented!(),
         }
     }
@@ -235,8 +484,6 @@ impl Contract {
     }
 }
 
-near_contract_standards::impl_fungible_token_core!(Contract, ft);
-
 #[near_bindgen]
 impl FungibleTokenMetadataProvider for Contract {
     fn ft_metadata(&self) -> FungibleTokenMetadata {
@@ -0,0 +1,135 @@
+use crate::*;
+use near_contract_standards::fungible_token::core::FungibleTokenCore;
+use near_contract_standards::fungible_token::resolver::FungibleTokenResolver;
+
+#[near_bindgen]
+impl FungibleTokenCore for Contract {
+    #[payable]
+    fn ft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+    ) {
+        match self.internal_config().love_transfer_policy {
+            LoveTransferPolicy::Transferable => self.ft.ft_transfer(receiver_id, amount, memo),
+            LoveTransferPolicy::NonTransferable => env::panic_str(E112_LOVE_NOT_TRANSFERABLE),
+            LoveTransferPolicy::WhitelistedReceivers => {
+                require!(self.data().whitelisted_love_receivers.contains(&receiver_id), E113_LOVE_RECEIVER_NOT_WHITELISTED);
+                self.ft.ft_transfer(receiver_id, amount, memo)
+            }
+            LoveTransferPolicy::MoveLock => {
+                let sender_id = env::predecessor_account_id();
+                self.ft.ft_transfer(receiver_id.clone(), amount, memo);
+                self.internal_move_lock(&sender_id, &receiver_id, amount.into());
+            }
+        }
+    }
+
+    #[payable]
+    fn ft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        amount: U128,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        match self.internal_config().love_transfer_policy {
+            LoveTransferPolicy::Transferable => {}
+            LoveTransferPolicy::WhitelistedReceivers => {
+                require!(self.data().whitelisted_love_receivers.contains(&receiver_id), E113_LOVE_RECEIVER_NOT_WHITELISTED);
+            }
+            // the refund could not carry the moved lock back
+            _ => env::panic_str(E112_LOVE_NOT_TRANSFERABLE),
+        }
+        self.ft.ft_transfer_call(receiver_id, amount, memo, msg)
+    }
+
+    fn ft_total_supply(&self) -> U128 {
+        self.ft.ft_total_supply()
+    }
+
+    fn ft_balance_of(&self, account_id: AccountId) -> U128 {
+        self.ft.ft_balance_of(account_id)
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenResolver for Contract {
+    #[private]
+    fn ft_resolve_transfer(
+        &mut self,
+        sender_id: AccountId,
+        receiver_id: AccountId,
+        amount: U128,
+    ) -> U128 {
+        let (used_amount, _) = self.ft.internal_ft_resolve_transfer(&sender_id, receiver_id, amount);
+        used_amount.into()
+    }
+}
+
+impl Contract {
+    /// Move the slice of sender's locks backing amount of love token to the receiver,
+    /// each position is split in proportion and the slice becomes a new position of the receiver.
+    fn internal_move_lock(&mut self, sender_id: &AccountId, receiver_id: &AccountId, amount: Balance) {
+        let mut sender = self.internal_unwrap_account(sender_id);
+        self.internal_settle_slashed_lpt(sender_id, &mut sender);
+        let total_love: Balance = sender.locks.values().map(|lock| lock.ve_lpt_amount).sum();
+        require!(amount <= total_love, E101_INSUFFICIENT_BALANCE);
+
+        let timestamp = env::block_timestamp();
+        let mut lock_ids: Vec<u32> = sender.locks.keys().cloned().collect();
+        lock_ids.sort_unstable();
+        let mut remain = amount;
+        let mut decreased_ve_lpt = 0;
+        let mut slices = vec![];
+        for (index, lock_id) in lock_ids.iter().enumerate() {
+            let mut lock = sender.take_lock(*lock_id);
+            // the last position takes the rounding remainder
+            let love = if index + 1 == lock_ids.len() {
+                std::cmp::min(remain, lock.ve_lpt_amount)
+            } else {
+                u128_ratio(lock.ve_lpt_amount, amount, total_love)
+            };
+            if love == 0 || u128_ratio(lock.lpt_amount, love, lock.ve_lpt_amount) == 0 {
+                sender.put_lock(*lock_id, lock);
+                continue;
+            }
+            remain -= love;
+            let prev_ve_state = lock.get_ve_state(timestamp);
+            slices.push(lock.split(love));
+            let cur_ve_state = lock.get_ve_state(timestamp);
+            sender.put_lock(*lock_id, lock);
+            self.internal_sync_total_ve_lpt(&prev_ve_state, &cur_ve_state);
+            decreased_ve_lpt += prev_ve_state.ve_lpt_amount - cur_ve_state.ve_lpt_amount;
+        }
+        // positions too small to slice leave part of the amount without locks behind it
+        require!(remain == 0, E116_LOVE_NOT_MOVABLE);
+        self.update_impacted_proposals(&mut sender, decreased_ve_lpt, false);
+        sender.checkpoint_ve_lpt();
+        self.internal_set_account(sender_id, sender);
+
+        // the receiver is loaded after the sender saved, in case one is the delegatee of the other
+        let mut receiver = self.internal_unwrap_account(receiver_id);
+        self.internal_settle_slashed_lpt(receiver_id, &mut receiver);
+        let mut increased_ve_lpt = 0;
+        for slice in slices {
+            require!(receiver.locks.len() < MAX_LOCK_POSITIONS, E107_TOO_MANY_LOCK_POSITIONS);
+            let slice_ve_state = slice.get_ve_state(timestamp);
+            receiver.put_lock(receiver.next_lock_id(), slice);
+            self.internal_sync_total_ve_lpt(&VeState::default(), &slice_ve_state);
+            increased_ve_lpt += slice_ve_state.ve_lpt_amount;
+        }
+        self.update_impacted_proposals(&mut receiver, increased_ve_lpt, true);
+        receiver.checkpoint_ve_lpt();
+        self.internal_set_account(receiver_id, receiver);
+
+        Event::LockMove {
+            sender_id,
+            receiver_id,
+            love_amount: &U128(amount),
+            ve_lpt_amount: &U128(increased_ve_lpt),
+        }
+        .emit();
+    }
+}
@@ -40,6 +40,20 @@ impl Contract {
         }
     }
 
+    /// Set the min amount of each token an incentive deposit takes, 0 to remove the min.
+    #[payable]
+    pub fn modify_min_incentive_deposits(&mut self, min_deposits: Vec<(AccountId, U128)>) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        for (token_id, min_deposit) in min_deposits {
+            if min_deposit.0 == 0 {
+                self.data_mut().min_incentive_deposits.remove(&token_id);
+            } else {
+                self.data_mut().min_incentive_deposits.insert(token_id, min_deposit.0);
+            }
+        }
+    }
+
     #[payable]
     pub fn modify_min_start_vote_offset_sec(&mut self, min_start_vote_offset_sec: u32) {
         assert_one_yocto();
@@ -78,19 +92,181 @@ impl Contract {
         self.data_mut().config.set(&config);
     }
 
+    /// An empty curve falls back to the default linear one.
+    #[payable]
+    pub fn modify_locking_multiplier_curve(&mut self, multiplier_curve: Vec<LockingMultiplierPoint>) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        
+        let mut config =  self.data().config.get().unwrap();
+        config.locking_multiplier_curve = multiplier_curve;
+        
+        config.assert_valid();
+        self.data_mut().config.set(&config);
+    }
+
+    #[payable]
+    pub fn modify_early_unlock_penalty(&mut self, max_penalty_rate: u32, penalty_sink: PenaltySink) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        
+        let mut config =  self.data().config.get().unwrap();
+        config.max_early_unlock_penalty_rate = max_penalty_rate;
+        config.early_unlock_penalty_sink = penalty_sink;
+        
+        config.assert_valid();
+        self.data_mut().config.set(&config);
+    }
+
+    /// Only affects locks made or appended after it.
+    #[payable]
+    pub fn modify_decay_mode(&mut self, decay_mode: DecayMode) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        
+        let mut config =  self.data().config.get().unwrap();
+        config.decay_mode = decay_mode;
+        
+        self.data_mut().config.set(&config);
+    }
+
+    /// Only affects proposals created after it.
+    #[payable]
+    pub fn modify_proposal_thresholds(&mut self, proposal_thresholds: ProposalThresholds) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+
+        let mut config =  self.data().config.get().unwrap();
+        config.proposal_thresholds = proposal_thresholds;
+        config.assert_valid();
+
+        self.data_mut().config.set(&config);
+    }
+
+    /// Only affects proposals created after it.
+    #[payable]
+    pub fn modify_execution_timelock_sec(&mut self, execution_timelock_sec: u32) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+
+        let mut config =  self.data().config.get().unwrap();
+        config.execution_timelock_sec = execution_timelock_sec;
+
+        self.data_mut().config.set(&config);
+    }
+
+    /// None disables it, only affects proposals created after it.
+    #[payable]
+    pub fn modify_permissionless_proposal_policy(&mut self, policy: Option<PermissionlessProposalPolicy>) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+
+        let mut config =  self.data().config.get().unwrap();
+        config.permissionless_proposal = policy;
+        config.assert_valid();
+
+        self.data_mut().config.set(&config);
+    }
+
+    #[payable]
+    pub fn modify_incentive_sweep_policy(&mut self, policy: IncentiveSweepPolicy) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+
+        let mut config =  self.data().config.get().unwrap();
+        config.incentive_sweep_policy = policy;
+        config.assert_valid();
+
+        self.data_mut().config.set(&config);
+    }
+
+    #[payable]
+    pub fn extend_whitelisted_love_receivers(&mut self, accounts: Vec<AccountId>) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        for account in accounts {
+            self.data_mut().whitelisted_love_receivers.insert(account);
+        }
+    }
+
+    #[payable]
+    pub fn remove_whitelisted_love_receivers(&mut self, accounts: Vec<AccountId>) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        for account in accounts {
+            self.data_mut().whitelisted_love_receivers.remove(&account);
+        }
+    }
+
+    #[payable]
+    pub fn modify_love_transfer_policy(&mut self, policy: LoveTransferPolicy) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        
+        let mut config =  self.data().config.get().unwrap();
+        config.love_transfer_policy = policy;
+        
+        self.data_mut().config.set(&config);
+    }
+
+    #[payable]
+    pub fn modify_lock_receiver_policy(&mut self, policy: LockReceiverPolicy) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        
+        let mut config =  self.data().config.get().unwrap();
+        config.lock_receiver_policy = policy;
+        
+        self.data_mut().config.set(&config);
+    }
+
+    /// Register an lptoken of lptoken_contract_id as lock pool.
+    #[payable]
+    pub fn register_lock_pool(&mut self, token_id: String, decimals: u8, ve_weight: u32) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        require!(self.internal_get_lock_pool(&token_id).is_none(), E602_LOCK_POOL_ALREADY_EXIST);
+
+        self.internal_set_lock_pool(&LockPool::new(token_id, LockPoolKind::MultiFungibleToken, decimals, ve_weight));
+    }
+
+    /// Register a NEP-141 token as lock pool.
+    #[payable]
+    pub fn register_ft_lock_pool(&mut self, token_id: AccountId, decimals: u8, ve_weight: u32) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        require!(self.internal_get_lock_pool(&token_id.to_string()).is_none(), E602_LOCK_POOL_ALREADY_EXIST);
+
+        self.internal_set_lock_pool(&LockPool::new(token_id.to_string(), LockPoolKind::FungibleToken, decimals, ve_weight));
+    }
+
+    /// Only affects locks made or appended after it.
+    #[payable]
+    pub fn modify_lock_pool_ve_weight(&mut self, token_id: String, ve_weight: u32) {
+        assert_one_yocto();
+        require!(self.is_owner_or_operators(), E002_NOT_ALLOWED);
+        require!(ve_weight > 0, E309_INVALID_VE_WEIGHT);
+
+        let mut lock_pool = self.internal_unwrap_lock_pool(&token_id);
+        lock_pool.ve_weight = ve_weight;
+        self.internal_set_lock_pool(&lock_pool);
+    }
+
     /// owner help to return those who lost lpt when withdraw,
     /// It's owner's responsibility to verify amount and token id before calling
     #[payable]
-    pub fn return_lpt_lostfound(&mut self, account_id: AccountId, amount: U128) -> Promise {
+    pub fn return_lpt_lostfound(&mut self, account_id: AccountId, amount: U128, token_id: Option<String>) -> Promise {
         assert_one_yocto();
         self.assert_owner();
 
         // update inner state
-        let max_amount = self.data().lostfound;
-        require!(amount.0 <= max_amount, E101_INSUFFICIENT_BALANCE);
-        self.data_mut().lostfound -= amount.0;
+        let token_id = token_id.unwrap_or_else(|| self.data().lptoken_id.clone());
+        let mut lock_pool = self.internal_unwrap_lock_pool(&token_id);
+        require!(amount.0 <= lock_pool.lostfound, E101_INSUFFICIENT_BALANCE);
+        lock_pool.lostfound -= amount.0;
+        self.internal_set_lock_pool(&lock_pool);
 
-        self.transfer_lpt_lostfound(&account_id, amount.0)
+        self.transfer_lpt_lostfound(&account_id, &lock_pool, amount.0)
     }
 
     #[payable]
@@ -105,8 +281,20 @@ impl Contract {
         self.transfer_removed_proposal_assets(&token_id, &account_id, amount.0)
     }
 
+    /// Take the NEAR no proposal needs anymore back from the execution treasury.
+    #[payable]
+    pub fn withdraw_execution_treasury(&mut self, receiver_id: AccountId, amount: U128) -> Promise {
+        assert_one_yocto();
+        self.assert_owner();
+
+        require!(amount.0 <= self.data().execution_treasury, E421_INSUFFICIENT_EXECUTION_TREASURY);
+        self.data_mut().execution_treasury -= amount.0;
+
+        Promise::new(receiver_id).transfer(amount.0)
+    }
+
     #[private]
-    pub fn callback_withdraw_lpt_lostfound(&mut self, receiver_id: AccountId, amount: U128) {
+    pub fn callback_withdraw_lpt_lostfound(&mut self, receiver_id: AccountId, token_id: String, amount: U128) {
         require!(
             env::promise_results_count() == 1,
             E001_PROMISE_RESULT_COUNT_INVALID
@@ -116,10 +304,13 @@ impl Contract {
             PromiseResult::NotReady => unreachable!(),
             PromiseResult::Failed => {
                 // all seed amount go to lostfound
-                self.data_mut().lostfound += amount;
+                let mut lock_pool = self.internal_unwrap_lock_pool(&token_id);
+                lock_pool.lostfound += amount;
+                self.internal_set_lock_pool(&lock_pool);
 
                 Event::LptWithdrawLostfound {
                     receiver_id: &receiver_id,
+                    token_id: &token_id,
                     withdraw_amount: &U128(amount),
                     success: false,
                 }
@@ -128,6 +319,7 @@ impl Contract {
             PromiseResult::Successful(_) => {
                 Event::LptWithdrawLostfound {
                     receiver_id: &receiver_id,
+                    token_id: &token_id,
                     withdraw_amount: &U128(amount),
                     success: true,
                 }
@@ -180,18 +372,11 @@ impl Contract {
 }
 
 impl Contract {
-    fn transfer_lpt_lostfound(&mut self, account_id: &AccountId, amount: Balance) -> Promise {
-        ext_multi_fungible_token::mft_transfer(
-            self.data().lptoken_id.clone(),
-            account_id.clone(),
-            amount.into(),
-            None,
-            self.data().lptoken_contract_id.clone(),
-            1, // one yocto near
-            GAS_FOR_LPT_TRANSFER,
-        )
+    fn transfer_lpt_lostfound(&mut self, account_id: &AccountId, lock_pool: &LockPool, amount: Balance) -> Promise {
+        self.internal_transfer_lock_token(lock_pool, account_id, amount)
         .then(ext_self::callback_withdraw_lpt_lostfound(
             account_id.clone(),
+            lock_pool.token_id.clone(),
             amount.into(),
             env::current_account_id(),
             0,
@@ -38,6 +38,26 @@ impl Contract {
         }
     }
 
+    /// Extend guardians. Only can be called by owner.
+    #[payable]
+    pub fn extend_guardians(&mut self, guardians: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_owner();
+        for guardian in guardians {
+            self.data_mut().guardians.insert(&guardian);
+        }
+    }
+
+    /// Remove guardians. Only can be called by owner.
+    #[payable]
+    pub fn remove_guardians(&mut self, guardians: Vec<AccountId>) {
+        assert_one_yocto();
+        self.assert_owner();
+        for guardian in guardians {
+            self.data_mut().guardians.remove(&guardian);
+        }
+    }
+
     /// Should only be called by this contract on migration.
     /// This is NOOP implementation. KEEP IT if you haven't changed contract state.
     /// If you have, you need to implement migration from old state 
@@ -50,8 +70,9 @@ impl Contract {
         // see if ContractData need upgrade
         contract.data = 
         match contract.data {
-            VersionedContractData::V0100(data) => VersionedContractData::V0200(data.into()),
-            VersionedContractData::V0200(data) => VersionedContractData::V0200(data),
+            VersionedContractData::V0100(data) => VersionedContractData::V0300(Box::new(ContractDataV0200::from(data).into())),
+            VersionedContractData::V0200(data) => VersionedContractData::V0300(Box::new(data.into())),
+            VersionedContractData::V0300(data) => VersionedContractData::V0300(data),
         };
         contract
     }
@@ -0,0 +1,143 @@
+use crate::*;
+use near_contract_standards::fungible_token::core_impl::ext_fungible_token;
+use std::cmp::Ordering;
+
+/// Which token standard the lptoken of a lock pool follows.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum LockPoolKind {
+    /// token_id is the inner id in lptoken_contract_id, locked through mft_transfer_call.
+    MultiFungibleToken,
+    /// token_id is a NEP-141 token contract, locked through ft_transfer_call.
+    FungibleToken,
+}
+
+/// A whitelisted lptoken that can be locked for veLPT.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize, PartialEq))]
+#[serde(crate = "near_sdk::serde")]
+pub struct LockPool {
+    pub token_id: String,
+    pub kind: LockPoolKind,
+    pub decimals: u8,
+    /// BP multiplier on the veLPT of locks in this pool, BASE_VE_WEIGHT is 1x
+    pub ve_weight: u32,
+    /// total lock lpt amount settled into the locks
+    #[serde(with = "u128_dec_format")]
+    pub cur_lock_lpt: Balance,
+    /// slashed lpt shared to lockers but not yet settled into their locks
+    #[serde(with = "u128_dec_format")]
+    pub unsettled_slashed_lpt: Balance,
+    /// if withdraw lpt encounter error, the lpt would go to here
+    #[serde(with = "u128_dec_format")]
+    pub lostfound: Balance,
+    /// lpt held as the bonds of proposals
+    #[serde(with = "u128_dec_format")]
+    pub bonded_lpt: Balance,
+    /// accumulated slashed lpt per locked lpt shared to lockers,
+    /// scaled by SLASHED_LPT_PER_SHARE_PRECISION
+    #[serde(with = "u128_dec_format")]
+    pub slashed_lpt_per_share: Balance,
+}
+
+impl LockPool {
+    pub fn new(token_id: String, kind: LockPoolKind, decimals: u8, ve_weight: u32) -> Self {
+        require!(ve_weight > 0, E309_INVALID_VE_WEIGHT);
+        require!(decimals <= MAX_LOCK_POOL_DECIMALS, E604_INVALID_LOCK_POOL_DECIMALS);
+        LockPool {
+            token_id,
+            kind,
+            decimals,
+            ve_weight,
+            cur_lock_lpt: 0,
+            unsettled_slashed_lpt: 0,
+            lostfound: 0,
+            bonded_lpt: 0,
+            slashed_lpt_per_share: 0,
+        }
+    }
+
+    /// All the lpt of this pool the contract is accountable for.
+    pub fn held_lpt(&self) -> Balance {
+        self.cur_lock_lpt + self.unsettled_slashed_lpt + self.lostfound + self.bonded_lpt
+    }
+
+    /// MIN_FIRST_LOCK is counted in 24 decimals
+    pub fn min_first_lock(&self) -> Balance {
+        match self.decimals.cmp(&24) {
+            Ordering::Greater => MIN_FIRST_LOCK * 10u128.pow((self.decimals - 24) as u32),
+            Ordering::Less => MIN_FIRST_LOCK / 10u128.pow((24 - self.decimals) as u32),
+            Ordering::Equal => MIN_FIRST_LOCK,
+        }
+    }
+}
+
+impl Contract {
+    pub fn internal_get_lock_pool(&self, token_id: &String) -> Option<LockPool> {
+        self.data().lock_pools.get(token_id)
+    }
+
+    pub fn internal_unwrap_lock_pool(&self, token_id: &String) -> LockPool {
+        self.internal_get_lock_pool(token_id)
+            .expect(E600_MFT_INVALID_LPTOKEN_ID)
+    }
+
+    pub fn internal_unwrap_ft_lock_pool(&self, token_id: &AccountId) -> LockPool {
+        self.internal_get_lock_pool(&token_id.to_string())
+            .filter(|lock_pool| lock_pool.kind == LockPoolKind::FungibleToken)
+            .expect(E603_FT_INVALID_LOCK_TOKEN)
+    }
+
+    pub fn internal_set_lock_pool(&mut self, lock_pool: &LockPool) {
+        self.data_mut().lock_pools.insert(&lock_pool.token_id, lock_pool);
+    }
+
+    /// Transfer the lptoken of the pool out, the caller should attach a callback to handle failure.
+    pub fn internal_transfer_lock_token(&self, lock_pool: &LockPool, receiver_id: &AccountId, amount: Balance) -> Promise {
+        match lock_pool.kind {
+            LockPoolKind::MultiFungibleToken => ext_multi_fungible_token::mft_transfer(
+                lock_pool.token_id.clone(),
+                receiver_id.clone(),
+                amount.into(),
+                None,
+                self.data().lptoken_contract_id.clone(),
+                1, // one yocto near
+                GAS_FOR_LPT_TRANSFER,
+            ),
+            LockPoolKind::FungibleToken => ext_fungible_token::ft_transfer(
+                receiver_id.clone(),
+                amount.into(),
+                None,
+                lock_pool.token_id.parse().unwrap(),
+                1, // one yocto near
+                GAS_FOR_LPT_TRANSFER,
+            ),
+        }
+    }
+
+    /// Transfer the lptoken of the pool out through transfer_call, the caller should attach a callback
+    /// to handle failure and the refund, which is counted differently by the two standards.
+    pub fn internal_transfer_lock_token_call(&self, lock_pool: &LockPool, receiver_id: &AccountId, amount: Balance, msg: String) -> Promise {
+        match lock_pool.kind {
+            LockPoolKind::MultiFungibleToken => ext_multi_fungible_token::mft_transfer_call(
+                lock_pool.token_id.clone(),
+                receiver_id.clone(),
+                amount.into(),
+                msg,
+                self.data().lptoken_contract_id.clone(),
+                1, // one yocto near
+                GAS_FOR_LPT_TRANSFER_CALL,
+            ),
+            LockPoolKind::FungibleToken => ext_fungible_token::ft_transfer_call(
+                receiver_id.clone(),
+                amount.into(),
+                None,
+                msg,
+                lock_pool.token_id.parse().unwrap(),
+                1, // one yocto near
+                GAS_FOR_LPT_TRANSFER_CALL,
+            ),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::*;
 
-#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone)]
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
 #[serde(crate = "near_sdk::serde")]
 pub enum ProposalKind {
     FarmingReward { 
@@ -11,6 +11,48 @@ pub enum ProposalKind {
         options: Vec<String>,
     },
     Common,
+    /// Voted as a common proposal, the actions can be executed once passed and the timelock elapsed.
+    Executable {
+        actions: Vec<FunctionCallAction>,
+    },
+    /// Voted as a common proposal, the config is changed once passed and the timelock elapsed.
+    ConfigChange {
+        config: Box<PartialConfig>,
+        whitelists: WhitelistDeltas,
+    },
+}
+
+/// Makes the kind in the proposal events, the actions and config carried are left out,
+/// so they don't need Debug in wasm.
+impl std::fmt::Debug for ProposalKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProposalKind::FarmingReward { farm_list, total_reward } => f.debug_struct("FarmingReward")
+                .field("farm_list", farm_list)
+                .field("total_reward", total_reward)
+                .finish(),
+            ProposalKind::Poll { options } => f.debug_struct("Poll").field("options", options).finish(),
+            ProposalKind::Common => f.write_str("Common"),
+            ProposalKind::Executable { .. } => f.debug_struct("Executable").finish_non_exhaustive(),
+            ProposalKind::ConfigChange { .. } => f.debug_struct("ConfigChange").finish_non_exhaustive(),
+        }
+    }
+}
+
+impl ProposalKind {
+    /// Voted by approve, reject and nonsense.
+    pub fn is_common_vote(&self) -> bool {
+        matches!(self, ProposalKind::Common | ProposalKind::Executable { .. } | ProposalKind::ConfigChange { .. })
+    }
+
+    /// One vote for each farm or option, or for approve, reject and nonsense.
+    pub fn votes_len(&self) -> usize {
+        match self {
+            ProposalKind::FarmingReward { farm_list, .. } => farm_list.len(),
+            ProposalKind::Poll { options } => options.len(),
+            _ => 3,
+        }
+    }
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, PartialEq)]
@@ -21,6 +63,32 @@ pub enum ProposalStatus {
     InProgress,
     /// Expired after period of time.
     Expired,
+    /// Stopped by a guardian while in progress, never expires.
+    Vetoed,
+}
+
+/// The result of an expired proposal.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum ProposalOutcome {
+    Passed,
+    Rejected,
+    /// Too few ballots compared to the total veLPT at start.
+    QuorumNotMet,
+    /// More nonsense ballots than approval and reject ones, only for common proposal.
+    Nonsense,
+}
+
+/// How the voting power on a proposal is counted.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum VotingMode {
+    /// Vote with the veLPT when voting, the votes follow later lock changes until the end.
+    Live,
+    /// Vote with the veLPT at start_at, later lock changes don't touch the votes.
+    Snapshot,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, Default)]
@@ -42,8 +110,19 @@ pub struct Proposal {
     pub proposer: AccountId,
     /// Kind of proposal with relevant information.
     pub kind: ProposalKind,
+    pub voting_mode: VotingMode,
+    /// Only a poll can be other than Single.
+    pub poll_mode: PollMode,
+    /// What it needs to pass.
+    pub threshold: ProposalThreshold,
     /// Result of proposal with relevant information.
     pub votes: Vec<VoteInfo>,
+    /// The veLPT of all the voters, each voter counted once however many options voted.
+    #[serde(with = "u128_dec_format")]
+    pub voted_amount: Balance,
+    /// Only for ranked choice poll, the votes only count the first option of each ballot.
+    #[serde(skip)]
+    pub ranked_ballots: Vec<RankedBallot>,
     #[serde(with = "u128_dec_format")]
     pub ve_amount_at_last_action: u128,
     /// the nano seconds of voting begin time,
@@ -63,16 +142,35 @@ pub struct Proposal {
     pub status: Option<ProposalStatus>,
     #[borsh_skip] 
     pub is_nonsense: Option<bool>,
+    /// Only for executable proposal.
+    pub execution: Option<ProposalExecution>,
+    /// Only for proposal created by an account out of whitelisted_accounts.
+    pub bond: Option<Box<ProposalBond>>,
+    /// Only for proposal vetoed by a guardian.
+    pub vetoed_by: Option<AccountId>,
+    /// The incentives no voter can claim, swept once the claim grace period passed,
+    /// the later claims still succeed.
+    pub sweeps: Vec<IncentiveSweep>,
+    /// Whether the expiry has been settled and its event emitted.
+    pub is_settled: bool,
+    /// Filled in views once expired.
+    #[borsh_skip]
+    pub outcome: Option<ProposalOutcome>,
+    /// Filled in views once a ranked choice poll expired.
+    #[borsh_skip]
+    pub ranked_choice_result: Option<RankedChoiceResult>,
 }
 
 #[derive(BorshSerialize, BorshDeserialize)]
 pub enum VProposal {
+    V0200(ProposalV0200),
     Current(Proposal),
 }
 
 impl From<VProposal> for Proposal {
     fn from(v: VProposal) -> Self {
         match v {
+            VProposal::V0200(c) => c.into(),
             VProposal::Current(c) => c,
         }
     }
@@ -87,7 +185,7 @@ impl From<Proposal> for VProposal {
 impl Proposal {
 
     pub fn update_result(&mut self){
-        if let ProposalKind::Common { .. } = &self.kind {
+        if self.kind.is_common_vote() {
             if self.votes[0].total_ballots + self.votes[1].total_ballots < self.votes[2].total_ballots {
                 self.is_nonsense = Some(true);
             } else {
@@ -98,7 +196,9 @@ impl Proposal {
 
     pub fn update_status(&mut self) {
         let now = env::block_timestamp(); 
-        if now < self.start_at {
+        if self.vetoed_by.is_some() {
+            self.status = Some(ProposalStatus::Vetoed);
+        } else if now < self.start_at {
             self.status = Some(ProposalStatus::WarmUp);
         } else if now >= self.start_at && now < self.end_at {
             self.status = Some(ProposalStatus::InProgress);
@@ -107,10 +207,93 @@ impl Proposal {
             self.update_result();
         }
     }
+
+    /// Votes on an expired or vetoed proposal no longer change and can be moved to history.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status, Some(ProposalStatus::Expired) | Some(ProposalStatus::Vetoed))
+    }
 }
 
 
 impl Contract {
+    /// The total veLPT the votes of the proposal are counted against.
+    pub fn internal_proposal_total_ve_lpt(&self, proposal: &Proposal) -> Balance {
+        match proposal.voting_mode {
+            VotingMode::Live => self.internal_cur_total_ve_lpt(),
+            VotingMode::Snapshot => self.internal_get_total_ve_lpt_at(nano_to_sec(proposal.start_at)),
+        }
+    }
+
+    /// The outcome of an expired proposal against its threshold,
+    /// the quorum is counted on the total veLPT at start_at.
+    pub fn internal_proposal_outcome(&self, proposal: &Proposal) -> Option<ProposalOutcome> {
+        if proposal.status != Some(ProposalStatus::Expired) {
+            return None;
+        }
+        if proposal.is_nonsense == Some(true) {
+            return Some(ProposalOutcome::Nonsense);
+        }
+        let total_ballots = proposal.voted_amount;
+        let quorum_base = self.internal_get_total_ve_lpt_at(nano_to_sec(proposal.start_at));
+        if U256::from(total_ballots) * U256::from(MAX_PROPOSAL_THRESHOLD) < U256::from(quorum_base) * U256::from(proposal.threshold.quorum) {
+            return Some(ProposalOutcome::QuorumNotMet);
+        }
+        let (supporting, counted) = if proposal.kind.is_common_vote() {
+            (proposal.votes[0].total_ballots, proposal.votes[0].total_ballots + proposal.votes[1].total_ballots)
+        } else if proposal.poll_mode == PollMode::RankedChoice {
+            // the winner's ballots in the final round, none on a tie
+            let result = proposal.tally_ranked_choice();
+            let supporting = result.winner.map(|winner| result.rounds.last().unwrap()[winner].0).unwrap_or(0);
+            (supporting, total_ballots)
+        } else {
+            let top = proposal.votes.iter().map(|v| v.total_ballots).max().unwrap_or(0);
+            // a tie for the top option picks no winner
+            if proposal.votes.iter().filter(|v| v.total_ballots == top).count() > 1 {
+                return Some(ProposalOutcome::Rejected);
+            }
+            (top, total_ballots)
+        };
+        if supporting > 0 && U256::from(supporting) * U256::from(MAX_PROPOSAL_THRESHOLD) > U256::from(counted) * U256::from(proposal.threshold.approval) {
+            Some(ProposalOutcome::Passed)
+        } else {
+            Some(ProposalOutcome::Rejected)
+        }
+    }
+
+    /// Emit the expiry event once the proposal expired, with its outcome and poll result,
+    /// and refund the conditional incentives not met before any reward claimed.
+    /// The proposal should be saved after.
+    pub fn internal_settle_proposal(&mut self, proposal: &mut Proposal) -> bool {
+        if proposal.status != Some(ProposalStatus::Expired) || proposal.is_settled {
+            return false;
+        }
+        proposal.is_settled = true;
+        for (incentive_key, deposit) in proposal.take_failed_conditional_deposits() {
+            self.internal_add_removed_incentive(&deposit.depositor_id, &deposit.token_id, deposit.amount);
+            Event::IncentiveRefund {
+                depositor_id: &deposit.depositor_id,
+                proposal_id: proposal.id,
+                incentive_key,
+                token_id: &deposit.token_id,
+                amount: &U128(deposit.amount),
+            }
+            .emit();
+        }
+        Event::ProposalExpire {
+            proposal_id: proposal.id,
+            outcome: &self.internal_proposal_outcome(proposal),
+            ranked_choice_result: &proposal.get_ranked_choice_result(),
+        }
+        .emit();
+        true
+    }
+
+    /// Fill the fields only for views.
+    pub fn internal_fill_proposal_result(&self, proposal: &mut Proposal) {
+        proposal.outcome = self.internal_proposal_outcome(proposal);
+        proposal.ranked_choice_result = proposal.get_ranked_choice_result();
+    }
+
     pub fn internal_unwrap_proposal(&self, proposal_id: u32) -> Proposal {
         let mut proposal = self.internal_get_proposal(proposal_id).expect(E404_PROPOSAL_NOT_EXIST);
         proposal.update_status();
@@ -12,7 +12,21 @@ pub enum Action {
     /// Vote to farm id given proposal
     VoteFarm { farm_id: usize },
     /// Vote to poll id given proposal
-    VotePoll { poll_id: usize }
+    VotePoll { poll_id: usize },
+    /// Vote to several farm ids given proposal, the weights sum to FULL_VOTE_WEIGHT
+    VoteFarmSplit { weights: Vec<FarmVoteWeight> },
+    /// Vote to several poll ids given multi-select poll, each with the full amount
+    VotePollMulti { poll_ids: Vec<usize> },
+    /// Vote to poll ids given ranked choice poll, in the order of preference
+    VotePollRanked { poll_ids: Vec<usize> },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "near_sdk::serde")]
+pub struct FarmVoteWeight {
+    pub farm_id: usize,
+    /// in BP
+    pub weight: u32,
 }
 
 impl Action {
@@ -28,9 +42,37 @@ impl Action {
             Action::VoteApprove => Vote::Approve as usize,
             Action::VoteReject => Vote::Reject as usize,
             Action::VoteNonsense => Vote::Nonsense as usize,
+            Action::VoteFarmSplit { .. } | Action::VotePollMulti { .. } => env::panic_str(E201_INVALID_VOTE),
+            Action::VotePollRanked { poll_ids } => poll_ids[0],
+        }
+    }
+
+    /// The indexes of the votes this action goes to.
+    pub fn get_indexes(&self) -> Vec<usize> {
+        match self {
+            Action::VoteFarmSplit { weights } => weights.iter().map(|w| w.farm_id).collect(),
+            Action::VotePollMulti { poll_ids } => poll_ids.clone(),
+            _ => vec![self.get_index()],
+        }
+    }
+
+    /// The amount of the vote going to each index, pro rata to the weights for a split vote,
+    /// with the rounding remainder to the last one, the full amount to each one for a multi-select vote,
+    /// and to the first choice for a ranked vote.
+    pub fn split_amount(&self, amount: Balance) -> Vec<(usize, Balance)> {
+        match self {
+            Action::VoteFarmSplit { weights } => {
+                let mut split: Vec<(usize, Balance)> = weights.iter()
+                    .map(|w| (w.farm_id, u128_ratio(amount, w.weight as u128, FULL_VOTE_WEIGHT as u128)))
+                    .collect();
+                let rest = amount - split.iter().map(|(_, a)| a).sum::<Balance>();
+                split.last_mut().unwrap().1 += rest;
+                split
+            },
+            Action::VotePollMulti { poll_ids } => poll_ids.iter().map(|poll_id| (*poll_id, amount)).collect(),
+            _ => vec![(self.get_index(), amount)],
         }
     }
-    
 }
 
 /// Votes recorded in the proposal.
@@ -51,23 +93,82 @@ impl From<&Action> for Vote {
             Action::VoteNonsense => Vote::Nonsense,
             Action::VoteFarm { .. } => env::panic_str(E201_INVALID_VOTE),
             Action::VotePoll { .. } => env::panic_str(E201_INVALID_VOTE),
+            Action::VoteFarmSplit { .. } => env::panic_str(E201_INVALID_VOTE),
+            Action::VotePollMulti { .. } => env::panic_str(E201_INVALID_VOTE),
+            Action::VotePollRanked { .. } => env::panic_str(E201_INVALID_VOTE),
         }
     }
 }
 
 impl Proposal {
+    /// A split vote must go to distinct farms of a farming reward proposal with weights summing to FULL_VOTE_WEIGHT,
+    /// a multi-select or ranked choice poll only takes its own vote on distinct options.
+    pub fn assert_valid_action(&self, action: &Action) {
+        if let Action::VoteFarmSplit { weights } = action {
+            let farm_count = match &self.kind {
+                ProposalKind::FarmingReward { farm_list, .. } => farm_list.len(),
+                _ => 0,
+            };
+            let farm_ids: HashSet<usize> = weights.iter().map(|w| w.farm_id).collect();
+            require!(
+                !weights.is_empty() && farm_ids.len() == weights.len()
+                    && weights.iter().all(|w| w.farm_id < farm_count && w.weight > 0)
+                    && weights.iter().map(|w| w.weight).sum::<u32>() == FULL_VOTE_WEIGHT,
+                E201_INVALID_VOTE
+            );
+        }
+        match (&self.poll_mode, action) {
+            (PollMode::Single, Action::VotePollMulti { .. } | Action::VotePollRanked { .. }) => env::panic_str(E201_INVALID_VOTE),
+            (PollMode::MultiSelect { max_choices }, Action::VotePollMulti { poll_ids }) => {
+                require!(!poll_ids.is_empty() && poll_ids.len() <= *max_choices as usize, E201_INVALID_VOTE);
+            },
+            (PollMode::RankedChoice, Action::VotePollRanked { poll_ids }) => {
+                require!(!poll_ids.is_empty() && poll_ids.len() <= MAX_RANKING_LEN, E201_INVALID_VOTE);
+            },
+            (PollMode::MultiSelect { .. } | PollMode::RankedChoice, _) => env::panic_str(E201_INVALID_VOTE),
+            _ => {}
+        }
+        if let Action::VotePollMulti { poll_ids } | Action::VotePollRanked { poll_ids } = action {
+            let distinct: HashSet<&usize> = poll_ids.iter().collect();
+            require!(
+                distinct.len() == poll_ids.len() && poll_ids.iter().all(|poll_id| *poll_id < self.votes.len()),
+                E201_INVALID_VOTE
+            );
+        }
+        for index in action.get_indexes() {
+            require!(index < self.votes.len(), E201_INVALID_VOTE);
+        }
+    }
+
+    /// The vote of action changed from prev_amount to amount, each index by the change of its split.
     pub fn update_votes(
         &mut self,
         action: &Action,
+        prev_amount: Balance,
         amount: Balance,
-        is_increased: bool
     ) {
-        let index = action.get_index();
-        require!(index < self.votes.len(), E201_INVALID_VOTE);
+        for ((index, prev), (_, cur)) in action.split_amount(prev_amount).into_iter().zip(action.split_amount(amount)) {
+            require!(index < self.votes.len(), E201_INVALID_VOTE);
+            self.votes[index].total_ballots = self.votes[index].total_ballots + cur - prev;
+        }
+        self.voted_amount = self.voted_amount + amount - prev_amount;
+        if let Action::VotePollRanked { poll_ids } = action {
+            self.update_ranked_ballots(poll_ids, prev_amount, amount);
+        }
+    }
+
+    pub fn update_participants(&mut self, action: &Action, is_increased: bool) {
+        for index in action.get_indexes() {
+            if is_increased {
+                self.votes[index].participants += 1;
+            } else {
+                self.votes[index].participants -= 1;
+            }
+        }
         if is_increased {
-            self.votes[index].total_ballots += amount;
+            self.participants += 1;
         } else {
-            self.votes[index].total_ballots -= amount;
+            self.participants -= 1;
         }
     }
 }
@@ -80,7 +181,9 @@ impl Contract {
         amount: Balance,
     ) {
         let mut proposal = self.internal_unwrap_proposal(proposal_id);
-        require!(action != &Action::VoteNonsense, E201_INVALID_VOTE);
+        // only the bonded proposal can be voted as nonsense, which forfeits the bond
+        require!(action != &Action::VoteNonsense || proposal.bond.is_some(), E201_INVALID_VOTE);
+        proposal.assert_valid_action(action);
         
         // check proposal is inprogress
         match proposal.status {
@@ -88,12 +191,11 @@ impl Contract {
                 // update proposal result
                 proposal.update_votes(
                     action,
-                    amount,
-                    true
+                    0,
+                    amount
                 );
-                proposal.ve_amount_at_last_action = self.data().cur_total_ve_lpt;
-                proposal.votes[action.get_index()].participants += 1;
-                proposal.participants += 1;
+                proposal.ve_amount_at_last_action = self.internal_proposal_total_ve_lpt(&proposal);
+                proposal.update_participants(action, true);
                 
                 self.data_mut()
                     .proposals
@@ -117,11 +219,10 @@ impl Contract {
                 proposal.update_votes(
                     &vote_detail.action,
                     vote_detail.amount,
-                    false
+                    0
                 );
-                proposal.ve_amount_at_last_action = self.data().cur_total_ve_lpt;
-                proposal.votes[vote_detail.action.get_index()].participants -= 1;
-                proposal.participants -= 1;
+                proposal.ve_amount_at_last_action = self.internal_proposal_total_ve_lpt(&proposal);
+                proposal.update_participants(&vote_detail.action, false);
                 
                 self.internal_set_proposal(proposal_id, proposal.into());
             },
@@ -0,0 +1,150 @@
+use crate::*;
+
+/// What the bond of a permissionless proposal is paid in.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum BondAsset {
+    /// Attached to create_proposal.
+    Near,
+    /// Transferred with the CreateProposal msg, token_id is of a lock pool.
+    Lpt { token_id: String },
+}
+
+/// Where the bond of a proposal voted as nonsense goes.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum BondForfeitSink {
+    /// Transfer the forfeited bond to the given treasury account.
+    Treasury { account_id: AccountId },
+    /// Share the forfeited bond among the nonsense voters, pro rata to their nonsense ballots.
+    NonsenseVoters,
+}
+
+/// How an account out of whitelisted_accounts can create a proposal.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct PermissionlessProposalPolicy {
+    /// The min veLPT of the proposer.
+    #[serde(with = "u128_dec_format")]
+    pub min_ve_lpt: Balance,
+    pub bond_asset: BondAsset,
+    #[serde(with = "u128_dec_format")]
+    pub bond_amount: Balance,
+    /// Taken when the proposal is created.
+    pub forfeit_sink: BondForfeitSink,
+}
+
+impl PermissionlessProposalPolicy {
+    pub fn assert_valid(&self) {
+        require!(self.bond_amount > 0, E312_INVALID_PROPOSAL_BOND);
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum BondStatus {
+    /// Waiting for the proposal to expire.
+    Held,
+    /// Returned to the proposer.
+    Refunded,
+    /// Sent to the treasury, or being claimed by the nonsense voters.
+    Forfeited,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalBond {
+    pub asset: BondAsset,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+    pub forfeit_sink: BondForfeitSink,
+    pub status: BondStatus,
+    /// The share of the forfeited bond claimed by the nonsense voters.
+    #[serde(with = "u128_dec_format")]
+    pub claimed_amount: Balance,
+    #[serde(skip)]
+    pub claimed_voters: HashSet<AccountId>,
+}
+
+impl Contract {
+    /// Check the proposer against the permissionless policy and take amount of asset as the bond,
+    /// returns the bond and the amount beyond it to refund.
+    pub fn internal_post_bond(&self, proposer: &AccountId, kind: &ProposalKind, asset: BondAsset, amount: Balance) -> (ProposalBond, Balance) {
+        let policy = self.internal_config().permissionless_proposal.expect(E002_NOT_ALLOWED);
+        // the bond only makes sense if the proposal can be voted as nonsense,
+        // and executing actions or changing config needs a whitelisted proposer
+        require!(matches!(kind, ProposalKind::Common), E413_NOT_PERMISSIONLESS_KIND);
+        let ve_lpt_amount = self.internal_unwrap_account(proposer).get_ve_lpt_amount(env::block_timestamp());
+        require!(ve_lpt_amount >= policy.min_ve_lpt, E303_INSUFFICIENT_VE_LPT);
+        require!(asset == policy.bond_asset && amount >= policy.bond_amount, E312_INVALID_PROPOSAL_BOND);
+        (
+            ProposalBond {
+                asset,
+                amount: policy.bond_amount,
+                forfeit_sink: policy.forfeit_sink,
+                status: BondStatus::Held,
+                claimed_amount: 0,
+                claimed_voters: HashSet::new(),
+            },
+            amount - policy.bond_amount,
+        )
+    }
+
+    /// The receiver of the bond of the expired or vetoed proposal and the amount, updating the bond status,
+    /// the bond of a vetoed proposal is forfeited the same as a nonsense one,
+    /// or goes to the owner if no nonsense voters to share it.
+    pub fn internal_claim_bond(&self, proposal: &mut Proposal, caller: &AccountId) -> (AccountId, Balance) {
+        require!(proposal.is_finished(), E414_BOND_NOT_CLAIMABLE);
+        let proposal_id = proposal.id;
+        let nonsense_ballots = proposal.votes[Vote::Nonsense as usize].total_ballots;
+        let is_forfeited = proposal.is_nonsense == Some(true) || proposal.vetoed_by.is_some();
+        let bond = proposal.bond.as_mut().expect(E414_BOND_NOT_CLAIMABLE);
+        if !is_forfeited {
+            require!(bond.status == BondStatus::Held, E414_BOND_NOT_CLAIMABLE);
+            bond.status = BondStatus::Refunded;
+            return (proposal.proposer.clone(), bond.amount);
+        }
+        match &bond.forfeit_sink {
+            BondForfeitSink::Treasury { account_id } => {
+                require!(bond.status == BondStatus::Held, E414_BOND_NOT_CLAIMABLE);
+                bond.status = BondStatus::Forfeited;
+                (account_id.clone(), bond.amount)
+            },
+            BondForfeitSink::NonsenseVoters if nonsense_ballots == 0 => {
+                require!(bond.status == BondStatus::Held, E414_BOND_NOT_CLAIMABLE);
+                bond.status = BondStatus::Forfeited;
+                (self.data().owner_id.clone(), bond.amount)
+            },
+            BondForfeitSink::NonsenseVoters => {
+                let account = self.internal_unwrap_account(caller);
+                let vote_detail = account.proposals.get(&proposal_id).cloned()
+                    .or_else(|| account.proposals_history.get(&proposal_id))
+                    .filter(|vote_detail| vote_detail.action == Action::VoteNonsense)
+                    .expect(E414_BOND_NOT_CLAIMABLE);
+                require!(bond.claimed_voters.insert(caller.clone()), E414_BOND_NOT_CLAIMABLE);
+                let share = u128_ratio(bond.amount, vote_detail.amount, nonsense_ballots);
+                bond.status = BondStatus::Forfeited;
+                bond.claimed_amount += share;
+                (caller.clone(), share)
+            },
+        }
+    }
+
+    /// Send the bond out, the caller should attach callback_transfer_bond.
+    pub fn internal_transfer_bond(&mut self, asset: &BondAsset, receiver_id: &AccountId, amount: Balance) -> Promise {
+        match asset {
+            BondAsset::Near => Promise::new(receiver_id.clone()).transfer(amount),
+            BondAsset::Lpt { token_id } => {
+                let mut lock_pool = self.internal_unwrap_lock_pool(token_id);
+                lock_pool.bonded_lpt -= amount;
+                self.internal_set_lock_pool(&lock_pool);
+                self.internal_transfer_lock_token(&lock_pool, receiver_id, amount)
+            },
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use crate::*;
+use near_sdk::Gas;
+
+/// A function call made by the contract when the executable proposal is executed.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct FunctionCallAction {
+    pub receiver_id: AccountId,
+    pub method_name: String,
+    pub args: Base64VecU8,
+    /// Attached from the execution treasury of this contract.
+    pub deposit: U128,
+    pub gas: U64,
+}
+
+/// The config fields to change, the absent ones are kept.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+#[serde(default)]
+pub struct PartialConfig {
+    pub min_proposal_start_vote_offset_sec: Option<u32>,
+    pub min_locking_duration_sec: Option<DurationSec>,
+    pub max_locking_duration_sec: Option<DurationSec>,
+    pub max_locking_multiplier: Option<u32>,
+    pub locking_multiplier_curve: Option<Vec<LockingMultiplierPoint>>,
+    pub min_voting_duration_sec: Option<DurationSec>,
+    pub max_voting_duration_sec: Option<DurationSec>,
+    pub max_early_unlock_penalty_rate: Option<u32>,
+    pub early_unlock_penalty_sink: Option<PenaltySink>,
+    pub decay_mode: Option<DecayMode>,
+    pub lock_receiver_policy: Option<LockReceiverPolicy>,
+    pub love_transfer_policy: Option<LoveTransferPolicy>,
+    pub proposal_thresholds: Option<ProposalThresholds>,
+    pub execution_timelock_sec: Option<DurationSec>,
+    /// Can only enable or replace the policy, as null is taken as absent.
+    pub permissionless_proposal: Option<Option<PermissionlessProposalPolicy>>,
+    pub incentive_sweep_policy: Option<IncentiveSweepPolicy>,
+}
+
+impl PartialConfig {
+    pub fn apply_to(&self, config: &mut Config) {
+        macro_rules! apply {
+            ($($field: ident),*) => {
+                $(if let Some(value) = &self.$field {
+                    config.$field = value.clone();
+                })*
+            };
+        }
+        apply!(
+            min_proposal_start_vote_offset_sec, min_locking_duration_sec, max_locking_duration_sec,
+            max_locking_multiplier, locking_multiplier_curve, min_voting_duration_sec, max_voting_duration_sec,
+            max_early_unlock_penalty_rate, early_unlock_penalty_sink, decay_mode, lock_receiver_policy,
+            love_transfer_policy, proposal_thresholds, execution_timelock_sec, permissionless_proposal,
+            incentive_sweep_policy
+        );
+    }
+}
+
+/// The accounts to add into or remove from each whitelist, removed after added.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Default)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+#[serde(default)]
+pub struct WhitelistDeltas {
+    pub extend_whitelisted_accounts: Vec<AccountId>,
+    pub remove_whitelisted_accounts: Vec<AccountId>,
+    pub extend_whitelisted_incentive_tokens: Vec<AccountId>,
+    pub remove_whitelisted_incentive_tokens: Vec<AccountId>,
+    pub extend_whitelisted_love_receivers: Vec<AccountId>,
+    pub remove_whitelisted_love_receivers: Vec<AccountId>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum ExecutionStatus {
+    /// Waiting for the proposal to pass and the timelock to elapse.
+    Pending,
+    /// The actions are sent, waiting for their results.
+    Executing,
+    Executed,
+    /// Some of the actions failed, the others may have taken effect, can not be executed again.
+    Failed,
+    /// Stopped by a guardian during the timelock, can not be executed.
+    Vetoed,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct ProposalExecution {
+    /// the nano seconds from which the passed proposal can be executed.
+    #[serde(with = "u64_dec_format")]
+    pub executable_at: Timestamp,
+    pub status: ExecutionStatus,
+}
+
+impl Contract {
+    /// The actions can't call this contract, nor the contracts of the tokens held for users,
+    /// and all of them together must fit in one transaction.
+    pub fn internal_assert_valid_execution_actions(&self, actions: &[FunctionCallAction]) {
+        require!(!actions.is_empty(), E410_INVALID_EXECUTION_ACTION);
+        for action in actions {
+            let receiver_id = &action.receiver_id;
+            require!(
+                receiver_id != &env::current_account_id()
+                    && receiver_id != &self.data().lptoken_contract_id
+                    && self.internal_get_lock_pool(&receiver_id.to_string()).is_none()
+                    && !self.data().whitelisted_incentive_tokens.contains(receiver_id)
+                    && !self.data().incentive_tokens.contains(receiver_id),
+                E410_INVALID_EXECUTION_ACTION
+            );
+        }
+        let total_gas = actions.iter().fold(0u64, |total, action| total.saturating_add(action.gas.0));
+        require!(
+            total_gas.saturating_add(GAS_FOR_EXECUTE_PROPOSAL.0 + GAS_FOR_RESOLVE_EXECUTE_PROPOSAL.0) <= MAX_TX_GAS.0,
+            E419_EXECUTION_GAS_EXCEEDED
+        );
+    }
+
+    /// The changed config must still be valid, checked both when created and applied.
+    pub fn internal_assert_valid_config_change(&self, config: &PartialConfig) {
+        let mut new_config = self.internal_config();
+        config.apply_to(&mut new_config);
+        new_config.assert_valid();
+    }
+
+    pub fn internal_apply_config_change(&mut self, config: &PartialConfig, whitelists: &WhitelistDeltas) {
+        let mut new_config = self.internal_config();
+        config.apply_to(&mut new_config);
+        new_config.assert_valid();
+        self.data_mut().config.set(&new_config);
+
+        let data = self.data_mut();
+        for account in whitelists.extend_whitelisted_accounts.iter() {
+            data.whitelisted_accounts.insert(account);
+        }
+        for account in whitelists.remove_whitelisted_accounts.iter() {
+            data.whitelisted_accounts.remove(account);
+        }
+        for token in whitelists.extend_whitelisted_incentive_tokens.iter() {
+            data.whitelisted_incentive_tokens.insert(token.clone());
+        }
+        for token in whitelists.remove_whitelisted_incentive_tokens.iter() {
+            data.whitelisted_incentive_tokens.remove(token);
+        }
+        for account in whitelists.extend_whitelisted_love_receivers.iter() {
+            data.whitelisted_love_receivers.insert(account.clone());
+        }
+        for account in whitelists.remove_whitelisted_love_receivers.iter() {
+            data.whitelisted_love_receivers.remove(account);
+        }
+    }
+
+    /// Send all the actions at once, each one in its own receipt,
+    /// their deposits are taken from the execution treasury.
+    pub fn internal_execute_actions(&mut self, proposal_id: u32, actions: &[FunctionCallAction]) -> Promise {
+        let total_gas: u64 = actions.iter().map(|action| action.gas.0).sum();
+        require!(
+            env::prepaid_gas() - env::used_gas() > Gas(total_gas) + GAS_FOR_RESOLVE_EXECUTE_PROPOSAL,
+            E411_INSUFFICIENT_EXECUTION_GAS
+        );
+        let total_deposit = actions.iter().fold(0 as Balance, |total, action| total.saturating_add(action.deposit.0));
+        require!(total_deposit <= self.data().execution_treasury, E421_INSUFFICIENT_EXECUTION_TREASURY);
+        self.data_mut().execution_treasury -= total_deposit;
+        actions.iter()
+            .map(|action| Promise::new(action.receiver_id.clone()).function_call(
+                action.method_name.clone(),
+                action.args.0.clone(),
+                action.deposit.0,
+                Gas(action.gas.0),
+            ))
+            .reduce(|promise, next| promise.and(next))
+            .unwrap()
+            .then(ext_self::callback_execute_proposal(
+                proposal_id,
+                env::current_account_id(),
+                0,
+                GAS_FOR_RESOLVE_EXECUTE_PROPOSAL,
+            ))
+    }
+}
@@ -8,9 +8,101 @@ pub struct ProposalIncentive{
     pub incentive_amounts: Vec<Balance>,
     #[serde(with = "u128_vec_format")]
     pub claimed_amounts: Vec<Balance>,
+    #[serde(with = "u128_vec_format")]
+    pub swept_amounts: Vec<Balance>,
+    /// The votes of the voters who claimed, 0 for the claims made before 0.3.0.
+    #[serde(with = "u128_dec_format")]
+    pub claimed_ballots: Balance,
+    /// Who deposited the incentive amounts, empty for those deposited before 0.3.0.
+    pub deposits: Vec<IncentiveDeposit>,
+}
+
+/// The amount of a token deposited by an account, the same depositor, token and condition are merged.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize))]
+#[serde(crate = "near_sdk::serde")]
+pub struct IncentiveDeposit {
+    pub depositor_id: AccountId,
+    pub token_id: AccountId,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+    /// Only paid if the farm of the incentive_key meets it once expired, otherwise refunded.
+    pub condition: Option<IncentiveCondition>,
+}
+
+/// What the farm of the incentive_key must reach for a conditional incentive to be paid,
+/// either way it needs some ballots.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum IncentiveCondition {
+    /// Among the top rank farms by ballots, 1 for the leading one, tied farms share the rank.
+    TopRank { rank: u32 },
+    /// At least share of voted_amount, in BP.
+    MinShare { share: u32 },
+}
+
+/// Where the swept incentives go.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum IncentiveSweepSink {
+    /// Back to the depositors pro rata to their deposits, those without a depositor to removed_proposal_assets.
+    Depositors,
+    /// All to the given treasury account.
+    Treasury { account_id: AccountId },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct IncentiveSweepPolicy {
+    /// How long after a proposal ends before what no voter can claim is swept.
+    pub claim_grace_sec: DurationSec,
+    pub sink: IncentiveSweepSink,
+}
+
+impl Default for IncentiveSweepPolicy {
+    fn default() -> Self {
+        IncentiveSweepPolicy {
+            claim_grace_sec: DEFAULT_INCENTIVE_CLAIM_GRACE_SEC,
+            sink: IncentiveSweepSink::Depositors,
+        }
+    }
+}
+
+impl IncentiveSweepPolicy {
+    pub fn assert_valid(&self) {
+        require!(self.claim_grace_sec > 0, E313_INVALID_CLAIM_GRACE_PERIOD);
+    }
+}
+
+/// The unclaimed amount of a token swept from an incentive.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug, Deserialize))]
+#[serde(crate = "near_sdk::serde")]
+pub struct IncentiveSweep {
+    pub caller_id: AccountId,
+    pub incentive_key: u32,
+    pub token_id: AccountId,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+    #[serde(with = "u64_dec_format")]
+    pub swept_at: Timestamp,
 }
 
 impl ProposalIncentive {
+    /// What of the token at index no voter can claim, all of it without any ballot,
+    /// otherwise the rounding residual, bounded by what the voters yet to claim may still take.
+    pub fn calc_sweepable(&self, index: usize, votes_total_amount: Balance) -> Balance {
+        if votes_total_amount == 0 {
+            return self.incentive_amounts[index];
+        }
+        let unclaimed_ballots = votes_total_amount.saturating_sub(self.claimed_ballots);
+        let reserved = self.claimed_amounts[index] + u128_ratio(self.incentive_amounts[index], unclaimed_ballots, votes_total_amount);
+        self.incentive_amounts[index].saturating_sub(reserved)
+    }
+
     pub fn calc_reward(&self, account_votes_amount: u128, votes_total_amount: Balance) -> Vec<(AccountId, Balance)> {
         let mut claimed_list = vec![];
         for (index, token_id) in self.incentive_token_ids.iter().enumerate() {
@@ -29,39 +121,110 @@ impl Proposal {
                 self.votes[incentive_key as usize].total_ballots
             },
             _ => {
-                self.votes.iter().map(|item| item.total_ballots).sum()
+                self.voted_amount
             }
         }
     }
 
-    pub fn claim_reward(&mut self, vote_detail: &VoteDetail) -> Option<Vec<(AccountId, Balance)>> {
-        let incentive_key = if let ProposalKind::FarmingReward { .. } = self.kind {
-            vote_detail.action.get_index() as u32
-        } else {
-            0
-        };
-        let votes_total_amount = self.get_votes_total_amount_for_reward_calc(incentive_key);
-        if let Some(incentive) = self.incentive.get_mut(&incentive_key) {
-            let res = incentive.calc_reward(vote_detail.amount, votes_total_amount);
-            incentive.claimed_amounts = res.iter().zip(incentive.claimed_amounts.iter()).map(|(new, old)| new.1 + old).collect();
-            Some(res)
+    /// The votes of vote_detail counted on each incentive_key,
+    /// a split vote on a farming reward proposal counts on each farm pro rata to its weight.
+    pub fn get_incentive_votes(&self, vote_detail: &VoteDetail) -> Vec<(u32, Balance)> {
+        if let ProposalKind::FarmingReward { .. } = self.kind {
+            vote_detail.action.split_amount(vote_detail.amount).into_iter()
+                .map(|(index, amount)| (index as u32, amount))
+                .collect()
         } else {
+            vec![(0, vote_detail.amount)]
+        }
+    }
+
+    /// The same token from several incentive_keys is merged into one.
+    pub fn claim_reward(&mut self, vote_detail: &VoteDetail) -> Option<Vec<(AccountId, Balance)>> {
+        let mut rewards: Vec<(AccountId, Balance)> = vec![];
+        for (incentive_key, amount) in self.get_incentive_votes(vote_detail) {
+            let votes_total_amount = self.get_votes_total_amount_for_reward_calc(incentive_key);
+            if let Some(incentive) = self.incentive.get_mut(&incentive_key) {
+                let res = incentive.calc_reward(amount, votes_total_amount);
+                incentive.claimed_amounts = res.iter().zip(incentive.claimed_amounts.iter()).map(|(new, old)| new.1 + old).collect();
+                incentive.claimed_ballots += amount;
+                for (token_id, reward) in res {
+                    match rewards.iter_mut().find(|(id, _)| id == &token_id) {
+                        Some((_, total)) => *total += reward,
+                        None => rewards.push((token_id, reward)),
+                    }
+                }
+            }
+        }
+        if rewards.is_empty() {
             None
+        } else {
+            Some(rewards)
+        }
+    }
+
+    pub fn is_incentive_condition_met(&self, incentive_key: u32, condition: &IncentiveCondition) -> bool {
+        let ballots = self.votes[incentive_key as usize].total_ballots;
+        if ballots == 0 {
+            return false;
+        }
+        match condition {
+            IncentiveCondition::TopRank { rank } => {
+                self.votes.iter().filter(|v| v.total_ballots > ballots).count() < *rank as usize
+            },
+            IncentiveCondition::MinShare { share } => {
+                U256::from(ballots) * U256::from(MAX_PROPOSAL_THRESHOLD) >= U256::from(self.voted_amount) * U256::from(*share)
+            },
+        }
+    }
+
+    /// Take the conditional deposits out of the incentives if the expired proposal doesn't meet their conditions,
+    /// returns them with their incentive_keys to be refunded.
+    pub fn take_failed_conditional_deposits(&mut self) -> Vec<(u32, IncentiveDeposit)> {
+        let mut failed = vec![];
+        for (incentive_key, item) in self.incentive.iter() {
+            for deposit in item.deposits.iter() {
+                if let Some(condition) = &deposit.condition {
+                    if !self.is_incentive_condition_met(*incentive_key, condition) {
+                        failed.push((*incentive_key, deposit.clone()));
+                    }
+                }
+            }
         }
+        failed.sort_by_key(|(incentive_key, _)| *incentive_key);
+        for (incentive_key, deposit) in failed.iter() {
+            let item = self.incentive.get_mut(incentive_key).unwrap();
+            item.deposits.retain(|d| d != deposit);
+            let index = item.incentive_token_ids.iter().position(|token_id| token_id == &deposit.token_id).unwrap();
+            item.incentive_amounts[index] -= deposit.amount;
+        }
+        failed
     }
 
-    pub fn deposit_reward(&mut self, incentive_key: u32, token_id: &AccountId, amount: Balance) -> Balance {
+    pub fn deposit_reward(&mut self, incentive_key: u32, depositor_id: &AccountId, token_id: &AccountId, amount: Balance, condition: Option<IncentiveCondition>) -> Balance {
         let proposal_incentive = self.incentive.entry(incentive_key).or_insert(ProposalIncentive{
             incentive_token_ids: vec![token_id.clone()],
             incentive_amounts: vec![0u128],
             claimed_amounts: vec![0u128],
+            swept_amounts: vec![0u128],
+            claimed_ballots: 0,
+            deposits: vec![],
         });
+        match proposal_incentive.deposits.iter_mut().find(|d| &d.depositor_id == depositor_id && &d.token_id == token_id && d.condition == condition) {
+            Some(deposit) => deposit.amount += amount,
+            None => proposal_incentive.deposits.push(IncentiveDeposit {
+                depositor_id: depositor_id.clone(),
+                token_id: token_id.clone(),
+                amount,
+                condition,
+            }),
+        }
         let index = match proposal_incentive.incentive_token_ids.iter().position(|incentive_token_id| incentive_token_id == token_id){
             Some(index) => index,
             None => {
                 proposal_incentive.incentive_token_ids.push(token_id.clone());
                 proposal_incentive.incentive_amounts.push(0);
                 proposal_incentive.claimed_amounts.push(0);
+                proposal_incentive.swept_amounts.push(0);
                 proposal_incentive.incentive_token_ids.len() - 1
             }
         };
@@ -71,25 +234,172 @@ impl Proposal {
 }
 
 impl Contract {
-    pub fn internal_deposit_reward(&mut self, proposal_id: u32, incentive_key: u32, token_id: &AccountId, amount: Balance) -> (Balance, Timestamp) {
+    /// The incentive_key must be a farm of the farming reward proposal, or 0 for the others,
+    /// the token must be whitelisted or one of the farm.
+    pub fn internal_assert_valid_incentive(&self, kind: &ProposalKind, incentive_key: u32, token_id: &AccountId) {
+        match kind {
+            ProposalKind::FarmingReward { farm_list, .. } => {
+                require!(incentive_key < farm_list.len() as u32, E207_INVALID_INCENTIVE_KEY);
+                let farm_tokens = extra_incentive_tokens(farm_list[incentive_key as usize].clone());
+                require!(
+                    self.data().whitelisted_incentive_tokens.contains(token_id) || farm_tokens.contains(token_id)
+                    , E203_INVALID_INCENTIVE_TOKEN);
+            },
+            _ => {
+                require!(incentive_key == 0, E207_INVALID_INCENTIVE_KEY);
+                require!(self.data().whitelisted_incentive_tokens.contains(token_id), E203_INVALID_INCENTIVE_TOKEN);
+            },
+        }
+    }
+
+    /// Give all the incentives of the proposal back to their depositors to withdraw,
+    /// those without a depositor go into removed_proposal_assets.
+    pub fn internal_remove_incentives(&mut self, proposal: &mut Proposal) {
+        for item in std::mem::take(&mut proposal.incentive).values() {
+            for deposit in item.deposits.iter() {
+                self.internal_add_removed_incentive(&deposit.depositor_id, &deposit.token_id, deposit.amount);
+            }
+            for index in 0..item.incentive_token_ids.len() {
+                let deposited: Balance = item.deposits.iter()
+                    .filter(|d| d.token_id == item.incentive_token_ids[index])
+                    .map(|d| d.amount)
+                    .sum();
+                let rest = item.incentive_amounts[index] - deposited;
+                if rest > 0 {
+                    let current_amount = self.data().removed_proposal_assets.get(&item.incentive_token_ids[index]).unwrap_or(0_u128);
+                    self.data_mut().removed_proposal_assets.insert(
+                        &item.incentive_token_ids[index],
+                        &(rest + current_amount),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Sweep what no voter can claim of each incentive into the withdrawable incentives of the sink,
+    /// the incentives without any ballot, and the rounding residual of the others, which grows as the voters claim,
+    /// so it can be swept again. The depositors share each token pro rata to their deposits,
+    /// with the rounding remainder to the last one.
+    pub fn internal_sweep_incentives(&mut self, proposal: &mut Proposal, caller_id: &AccountId) {
+        let policy = self.internal_config().incentive_sweep_policy;
+        require!(
+            proposal.status == Some(ProposalStatus::Expired)
+                && env::block_timestamp() >= proposal.end_at + to_nano(policy.claim_grace_sec),
+            E418_INCENTIVE_NOT_SWEEPABLE
+        );
+        // the conditional incentives not met are refunded instead of swept
+        self.internal_settle_proposal(proposal);
+        let is_depositor = proposal.incentive.values().any(|item| item.deposits.iter().any(|d| &d.depositor_id == caller_id));
+        let is_treasury = matches!(&policy.sink, IncentiveSweepSink::Treasury { account_id } if account_id == caller_id);
+        require!(is_depositor || is_treasury || self.is_owner_or_operators(), E002_NOT_ALLOWED);
+
+        let sweep_count = proposal.sweeps.len();
+        let mut incentive_keys: Vec<u32> = proposal.incentive.keys().cloned().collect();
+        incentive_keys.sort_unstable();
+        for incentive_key in incentive_keys {
+            let votes_total_amount = proposal.get_votes_total_amount_for_reward_calc(incentive_key);
+            let item = proposal.incentive.get(&incentive_key).unwrap().clone();
+            for (index, token_id) in item.incentive_token_ids.iter().enumerate() {
+                let amount = item.calc_sweepable(index, votes_total_amount).saturating_sub(item.swept_amounts[index]);
+                if amount == 0 {
+                    continue;
+                }
+                proposal.incentive.get_mut(&incentive_key).unwrap().swept_amounts[index] += amount;
+                match &policy.sink {
+                    IncentiveSweepSink::Treasury { account_id } => {
+                        self.internal_add_removed_incentive(account_id, token_id, amount);
+                    },
+                    IncentiveSweepSink::Depositors => {
+                        let deposits: Vec<&IncentiveDeposit> = item.deposits.iter().filter(|d| &d.token_id == token_id).collect();
+                        let deposited: Balance = deposits.iter().map(|d| d.amount).sum();
+                        let mut rest = u128_ratio(amount, deposited, item.incentive_amounts[index]);
+                        let rest_without_depositor = amount - rest;
+                        for (i, deposit) in deposits.iter().enumerate() {
+                            let share = if i + 1 == deposits.len() {
+                                rest
+                            } else {
+                                u128_ratio(amount, deposit.amount, item.incentive_amounts[index])
+                            };
+                            rest -= share;
+                            self.internal_add_removed_incentive(&deposit.depositor_id, token_id, share);
+                        }
+                        if rest_without_depositor > 0 {
+                            let current_amount = self.data().removed_proposal_assets.get(token_id).unwrap_or(0_u128);
+                            self.data_mut().removed_proposal_assets.insert(token_id, &(rest_without_depositor + current_amount));
+                        }
+                    },
+                }
+                Event::IncentiveSweep {
+                    caller_id,
+                    proposal_id: proposal.id,
+                    incentive_key,
+                    token_id,
+                    amount: &U128(amount),
+                }
+                .emit();
+                proposal.sweeps.push(IncentiveSweep {
+                    caller_id: caller_id.clone(),
+                    incentive_key,
+                    token_id: token_id.clone(),
+                    amount,
+                    swept_at: env::block_timestamp(),
+                });
+            }
+        }
+        require!(proposal.sweeps.len() > sweep_count, E418_INCENTIVE_NOT_SWEEPABLE);
+    }
+
+    pub fn internal_get_removed_incentives(&self, depositor_id: &AccountId) -> HashMap<AccountId, Balance> {
+        self.data().removed_incentives.get(depositor_id).unwrap_or_default()
+    }
+
+    pub fn internal_add_removed_incentive(&mut self, depositor_id: &AccountId, token_id: &AccountId, amount: Balance) {
+        let mut incentives = self.internal_get_removed_incentives(depositor_id);
+        *incentives.entry(token_id.clone()).or_insert(0) += amount;
+        self.data_mut().removed_incentives.insert(depositor_id, &incentives);
+    }
+
+    pub fn internal_sub_removed_incentive(&mut self, depositor_id: &AccountId, token_id: &AccountId, amount: Balance) {
+        let mut incentives = self.internal_get_removed_incentives(depositor_id);
+        let total = incentives.get(token_id).cloned().unwrap_or(0);
+        require!(total >= amount, E101_INSUFFICIENT_BALANCE);
+        if total == amount {
+            incentives.remove(token_id);
+        } else {
+            incentives.insert(token_id.clone(), total - amount);
+        }
+        if incentives.is_empty() {
+            self.data_mut().removed_incentives.remove(depositor_id);
+        } else {
+            self.data_mut().removed_incentives.insert(depositor_id, &incentives);
+        }
+    }
+
+    /// A conditional incentive only goes to a farm of the farming reward proposal,
+    /// the incentive of the other kinds, polls included, is shared by all the voters on key 0.
+    pub fn internal_assert_valid_incentive_condition(&self, kind: &ProposalKind, condition: &IncentiveCondition) {
+        let farm_count = match kind {
+            ProposalKind::FarmingReward { farm_list, .. } => farm_list.len() as u32,
+            _ => env::panic_str(E202_INVALID_INCENTIVE),
+        };
+        match condition {
+            IncentiveCondition::TopRank { rank } => require!(*rank > 0 && *rank <= farm_count, E202_INVALID_INCENTIVE),
+            IncentiveCondition::MinShare { share } => require!(*share > 0 && *share <= MAX_PROPOSAL_THRESHOLD, E202_INVALID_INCENTIVE),
+        }
+    }
+
+    pub fn internal_deposit_reward(&mut self, proposal_id: u32, incentive_key: u32, depositor_id: &AccountId, token_id: &AccountId, amount: Balance, condition: Option<IncentiveCondition>) -> (Balance, Timestamp) {
         let mut proposal = self.internal_unwrap_proposal(proposal_id);
         match proposal.status {
             Some(ProposalStatus::WarmUp) | Some(ProposalStatus::InProgress) => {
-                match &proposal.kind {
-                    ProposalKind::FarmingReward { farm_list, .. } => {
-                        require!(incentive_key < farm_list.len() as u32, E207_INVALID_INCENTIVE_KEY);
-                        let farm_tokens = extra_incentive_tokens(farm_list[incentive_key as usize].clone());
-                        require!(
-                            self.data().whitelisted_incentive_tokens.contains(token_id) || farm_tokens.contains(token_id)
-                            , E203_INVALID_INCENTIVE_TOKEN);
-                    },
-                    _ => {
-                        require!(incentive_key == 0, E207_INVALID_INCENTIVE_KEY);
-                        require!(self.data().whitelisted_incentive_tokens.contains(token_id), E203_INVALID_INCENTIVE_TOKEN);
-                    },
+                self.internal_assert_valid_incentive(&proposal.kind, incentive_key, token_id);
+                if let Some(condition) = &condition {
+                    self.internal_assert_valid_incentive_condition(&proposal.kind, condition);
                 }
+                require!(amount >= self.data().min_incentive_deposits.get(token_id).cloned().unwrap_or(0), E209_INCENTIVE_DEPOSIT_TOO_SMALL);
                 
-                let total_reward = proposal.deposit_reward(incentive_key, token_id, amount);
+                self.data_mut().incentive_tokens.insert(token_id.clone());
+                let total_reward = proposal.deposit_reward(incentive_key, depositor_id, token_id, amount, condition);
                 let start_at = proposal.start_at;
                 self.internal_set_proposal(proposal_id, proposal.into());
                 (total_reward, start_at)
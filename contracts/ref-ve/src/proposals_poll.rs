@@ -0,0 +1,95 @@
+use crate::*;
+
+/// How the options of a poll are voted.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub enum PollMode {
+    /// One option per voter by VotePoll.
+    Single,
+    /// Approval voting by VotePollMulti, each of up to max_choices options gets the full veLPT.
+    MultiSelect { max_choices: u32 },
+    /// Options ranked by preference with VotePollRanked, tallied by instant runoff at expiry.
+    RankedChoice,
+}
+
+/// The veLPT of all the voters ranking the options in the same order.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct RankedBallot {
+    pub ranking: Vec<usize>,
+    #[serde(with = "u128_dec_format")]
+    pub amount: Balance,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Debug))]
+#[serde(crate = "near_sdk::serde")]
+pub struct RankedChoiceResult {
+    /// None if no ballot left.
+    pub winner: Option<usize>,
+    /// The ballots on each option in each round, the eliminated ones are 0.
+    pub rounds: Vec<Vec<U128>>,
+}
+
+impl Proposal {
+    /// The vote of a ranked choice poll moved from prev_amount to amount.
+    pub fn update_ranked_ballots(&mut self, ranking: &[usize], prev_amount: Balance, amount: Balance) {
+        match self.ranked_ballots.iter().position(|ballot| ballot.ranking == ranking) {
+            Some(index) => {
+                let ballot = &mut self.ranked_ballots[index];
+                ballot.amount = ballot.amount + amount - prev_amount;
+                if ballot.amount == 0 {
+                    self.ranked_ballots.swap_remove(index);
+                }
+            },
+            None => {
+                if amount > 0 {
+                    self.ranked_ballots.push(RankedBallot { ranking: ranking.to_vec(), amount });
+                }
+            }
+        }
+    }
+
+    /// Instant runoff: each ballot counts on its first option not yet eliminated,
+    /// the option with the fewest ballots is eliminated each round (the later one on a tie),
+    /// until one has more than half of the ballots left, no winner if the final round ties for the lead.
+    pub fn tally_ranked_choice(&self) -> RankedChoiceResult {
+        let mut eliminated = vec![false; self.votes.len()];
+        let mut rounds = vec![];
+        loop {
+            let mut tally = vec![0; self.votes.len()];
+            for ballot in self.ranked_ballots.iter() {
+                if let Some(option) = ballot.ranking.iter().find(|option| !eliminated[**option]) {
+                    tally[*option] += ballot.amount;
+                }
+            }
+            rounds.push(tally.iter().map(|amount| U128(*amount)).collect());
+            let total: Balance = tally.iter().sum();
+            let remaining: Vec<usize> = (0..tally.len()).filter(|option| !eliminated[*option]).collect();
+            let leading = remaining.iter().max_by_key(|option| (tally[**option], std::cmp::Reverse(**option))).cloned();
+            if total == 0 {
+                return RankedChoiceResult { winner: None, rounds };
+            }
+            if let Some(leading) = leading {
+                if tally[leading] * 2 > total || remaining.len() <= 2 {
+                    let is_tied = remaining.iter().filter(|option| tally[**option] == tally[leading]).count() > 1;
+                    let winner = if is_tied { None } else { Some(leading) };
+                    return RankedChoiceResult { winner, rounds };
+                }
+            }
+            let last = remaining.iter().min_by_key(|option| (tally[**option], std::cmp::Reverse(**option))).unwrap();
+            eliminated[*last] = true;
+        }
+    }
+
+    /// Filled once a ranked choice poll expired.
+    pub fn get_ranked_choice_result(&self) -> Option<RankedChoiceResult> {
+        if self.poll_mode == PollMode::RankedChoice && self.status == Some(ProposalStatus::Expired) {
+            Some(self.tally_ranked_choice())
+        } else {
+            None
+        }
+    }
+}
@@ -3,10 +3,23 @@ use near_contract_standards::storage_management::{
     StorageBalance, StorageBalanceBounds, StorageManagement,
 };
 
+impl Contract {
+    /// Charge the storage grown since prev_storage from the available storage of the account.
+    pub fn internal_charge_storage(&mut self, account_id: &AccountId, prev_storage: u64) {
+        let storage_cost = Balance::from(env::storage_usage().saturating_sub(prev_storage)) * env::storage_byte_cost();
+        if storage_cost > 0 {
+            let mut account = self.internal_unwrap_account(account_id);
+            require!(account.storage_available >= storage_cost, E102_INSUFFICIENT_STORAGE);
+            account.storage_available -= storage_cost;
+            self.internal_set_account(account_id, account);
+        }
+    }
+}
+
 /// Implements users storage management for the pool.
 #[near_bindgen]
 impl StorageManagement for Contract {
-    #[allow(unused_variables)]
+    /// The deposit beyond the registration is kept as available storage, unless registration_only.
     #[payable]
     fn storage_deposit(
         &mut self,
@@ -22,27 +35,48 @@ impl StorageManagement for Contract {
             env::panic_str(E102_INSUFFICIENT_STORAGE);
         }
 
+        let registration_only = registration_only.unwrap_or(false);
         if already_registered {
-            if amount > 0 {
-                Promise::new(env::predecessor_account_id()).transfer(amount);
+            if registration_only {
+                if amount > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(amount);
+                }
+            } else {
+                let mut account = self.internal_unwrap_account(&account_id);
+                account.storage_available += amount;
+                self.internal_set_account(&account_id, account);
             }
         } else {     
             self.ft.internal_register_account(&account_id);       
-            self.internal_set_account(&account_id, Account::new(&account_id, &sponsor_id));
-            self.data_mut().account_count += 1;
+            let mut account = Account::new(&account_id, &sponsor_id);
+            account.storage_paid = true;
             let refund = amount - STORAGE_BALANCE_MIN_BOUND;
-            if refund > 0 {
-                Promise::new(env::predecessor_account_id()).transfer(refund);
+            if registration_only {
+                if refund > 0 {
+                    Promise::new(env::predecessor_account_id()).transfer(refund);
+                }
+            } else {
+                account.storage_available = refund;
             }
+            self.internal_set_account(&account_id, account);
+            self.data_mut().account_count += 1;
         }
         self.storage_balance_of(account_id).unwrap()
     }
 
-    #[allow(unused_variables)]
+    /// Only the available storage can be withdrawn, all of it by default.
     #[payable]
     fn storage_withdraw(&mut self, amount: Option<U128>) -> StorageBalance {
         assert_one_yocto();
-        env::panic_str(E101_INSUFFICIENT_BALANCE);
+        let account_id = env::predecessor_account_id();
+        let mut account = self.internal_get_account(&account_id)
+            .unwrap_or_else(|| env::panic_str(E101_INSUFFICIENT_BALANCE));
+        let amount = amount.map(|amount| amount.0).unwrap_or(account.storage_available);
+        require!(amount > 0 && amount <= account.storage_available, E101_INSUFFICIENT_BALANCE);
+        account.storage_available -= amount;
+        self.internal_set_account(&account_id, account);
+        Promise::new(account_id.clone()).transfer(amount);
+        self.storage_balance_of(account_id).unwrap()
     }
 
     #[allow(unused_variables)]
@@ -61,7 +95,7 @@ impl StorageManagement for Contract {
                 E103_STILL_HAS_REWARD
             );
             require!(
-                account.lpt_amount == 0,
+                account.locks.is_empty(),
                 E104_STILL_HAS_LPT
             );
 
@@ -71,7 +105,10 @@ impl StorageManagement for Contract {
             );
 
             self.internal_remove_account(&account_id);
-            if account.sponsor_id != env::current_account_id(){
+            if account.storage_available > 0 {
+                Promise::new(account_id).transfer(account.storage_available);
+            }
+            if account.storage_paid {
                 Promise::new(account.sponsor_id).transfer(STORAGE_BALANCE_MIN_BOUND);
             }
             true
@@ -83,18 +120,14 @@ impl StorageManagement for Contract {
     fn storage_balance_bounds(&self) -> StorageBalanceBounds {
         StorageBalanceBounds {
             min: U128(STORAGE_BALANCE_MIN_BOUND),
-            max: Some(U128(STORAGE_BALANCE_MIN_BOUND)),
+            max: None,
         }
     }
 
     fn storage_balance_of(&self, account_id: AccountId) -> Option<StorageBalance> {
-        if self.data().accounts.contains_key(&account_id) {
-            Some(StorageBalance {
-                total: U128(STORAGE_BALANCE_MIN_BOUND),
-                available: U128(0),
-            })
-        }else{
-            None
-        }
+        self.internal_get_account(&account_id).map(|account| StorageBalance {
+            total: U128(STORAGE_BALANCE_MIN_BOUND + account.storage_available),
+            available: U128(account.storage_available),
+        })
     }
 }
\ No newline at end of file
@@ -6,7 +6,23 @@ use near_sdk::{serde_json, PromiseOrValue};
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 enum FTokenReceiverMessage {
-    Reward { proposal_id: u32, incentive_key: u32 }
+    /// only paid if the farm of incentive_key meets the condition once expired,
+    /// otherwise refunded to the sender, the condition only applies to a farming reward proposal
+    Reward { proposal_id: u32, incentive_key: u32, condition: Option<IncentiveCondition> },
+    /// lock into the position of lock_id, 0 by default,
+    /// on behalf of receiver_id if given
+    Lock { duration_sec: u32, lock_id: Option<u32>, receiver_id: Option<AccountId> },
+    Append { append_duration_sec: u32, lock_id: Option<u32>, receiver_id: Option<AccountId> },
+    /// create a proposal without being whitelisted, with the lock token as the bond,
+    /// the amount beyond the bond is refunded
+    CreateProposal {
+        kind: ProposalKind,
+        description: String,
+        start_at: u32,
+        duration_sec: u32,
+        voting_mode: Option<VotingMode>,
+        poll_mode: Option<PollMode>,
+    },
 }
 
 #[near_bindgen]
@@ -21,10 +37,38 @@ impl FungibleTokenReceiver for Contract {
         let token_id = env::predecessor_account_id();
         let message =
             serde_json::from_str::<FTokenReceiverMessage>(&msg).expect(E500_INVALID_MSG);
+        let mut refund = 0;
         match message {
-            FTokenReceiverMessage::Reward { proposal_id, incentive_key } => {
+            FTokenReceiverMessage::Lock { duration_sec, lock_id, receiver_id } => {
+                let lock_pool = self.internal_unwrap_ft_lock_pool(&token_id);
+                let (lock_amount, lock_refund) = real_amount_and_refund(&lock_pool, amount);
+                require!(lock_amount > 0, E101_INSUFFICIENT_BALANCE);
+                let receiver_id = receiver_id.unwrap_or_else(|| sender_id.clone());
+                self.lock_lpt(&sender_id, &receiver_id, &lock_pool.token_id, lock_id.unwrap_or(0), lock_amount, duration_sec);
+                refund = lock_refund;
+            }
+            FTokenReceiverMessage::Append { append_duration_sec, lock_id, receiver_id } => {
+                let lock_pool = self.internal_unwrap_ft_lock_pool(&token_id);
+                let (lock_amount, lock_refund) = real_amount_and_refund(&lock_pool, amount);
+                require!(lock_amount > 0, E101_INSUFFICIENT_BALANCE);
+                let receiver_id = receiver_id.unwrap_or_else(|| sender_id.clone());
+                self.append_lpt(&sender_id, &receiver_id, &lock_pool.token_id, lock_id.unwrap_or(0), lock_amount, append_duration_sec);
+                refund = lock_refund;
+            }
+            FTokenReceiverMessage::CreateProposal { kind, description, start_at, duration_sec, voting_mode, poll_mode } => {
+                let mut lock_pool = self.internal_unwrap_ft_lock_pool(&token_id);
+                let (bond, bond_refund) = self.internal_post_bond(&sender_id, &kind, BondAsset::Lpt { token_id: lock_pool.token_id.clone() }, amount);
+                lock_pool.bonded_lpt += bond.amount;
+                self.internal_set_lock_pool(&lock_pool);
+                // a transfer call can't carry NEAR, the storage is paid from the available storage of the proposer
+                let prev_storage = env::storage_usage();
+                self.internal_create_proposal(&sender_id, kind, description, start_at, duration_sec, voting_mode, poll_mode, Some(Box::new(bond)));
+                self.internal_charge_storage(&sender_id, prev_storage);
+                refund = bond_refund;
+            }
+            FTokenReceiverMessage::Reward { proposal_id, incentive_key, condition } => {
 
-                let (total_amount, start_at) = self.internal_deposit_reward(proposal_id, incentive_key, &token_id, amount);
+                let (total_amount, start_at) = self.internal_deposit_reward(proposal_id, incentive_key, &sender_id, &token_id, amount, condition.clone());
 
                 Event::RewardDeposit {
                     caller_id: &sender_id,
@@ -34,11 +78,12 @@ impl FungibleTokenReceiver for Contract {
                     deposit_amount: &U128(amount),
                     total_amount: &U128(total_amount),
                     start_at,
+                    condition: &condition,
                 }
                 .emit();
             }
         }
-        PromiseOrValue::Value(U128(0))
+        PromiseOrValue::Value(U128(refund))
     }
 }
 
@@ -56,8 +101,20 @@ pub trait MFTTokenReceiver {
 #[derive(Serialize, Deserialize)]
 #[serde(crate = "near_sdk::serde")]
 enum MFTokenReceiverMessage {
-    Lock { duration_sec: u32 },
-    Append { append_duration_sec: u32 }
+    /// lock into the position of lock_id, 0 by default,
+    /// on behalf of receiver_id if given
+    Lock { duration_sec: u32, lock_id: Option<u32>, receiver_id: Option<AccountId> },
+    Append { append_duration_sec: u32, lock_id: Option<u32>, receiver_id: Option<AccountId> },
+    /// create a proposal without being whitelisted, with the lptoken as the bond,
+    /// the amount beyond the bond is refunded
+    CreateProposal {
+        kind: ProposalKind,
+        description: String,
+        start_at: u32,
+        duration_sec: u32,
+        voting_mode: Option<VotingMode>,
+        poll_mode: Option<PollMode>,
+    },
 }
 
 #[near_bindgen]
@@ -70,20 +127,34 @@ impl MFTTokenReceiver for Contract {
         amount: U128,
         msg: String,
     ) -> PromiseOrValue<U128> {
-        let (amount, refund) = self.real_amount_and_refund(amount.into());
+        let lock_pool = self.internal_unwrap_lock_pool(&token_id);
+        require!(lock_pool.kind == LockPoolKind::MultiFungibleToken, E600_MFT_INVALID_LPTOKEN_ID);
+        let (amount, mut refund) = real_amount_and_refund(&lock_pool, amount.into());
         require!(amount > 0, E101_INSUFFICIENT_BALANCE);
 
-        require!(token_id == self.data().lptoken_id, E600_MFT_INVALID_LPTOKEN_ID);
         require!(env::predecessor_account_id() == self.data().lptoken_contract_id, E601_MFT_INVALID_LPTOKEN_CONTRACT);
         
         let message =
             serde_json::from_str::<MFTokenReceiverMessage>(&msg).expect(E500_INVALID_MSG);
         match message {
-            MFTokenReceiverMessage::Lock { duration_sec } => {
-                self.lock_lpt(&sender_id, amount, duration_sec);
+            MFTokenReceiverMessage::Lock { duration_sec, lock_id, receiver_id } => {
+                let receiver_id = receiver_id.unwrap_or_else(|| sender_id.clone());
+                self.lock_lpt(&sender_id, &receiver_id, &token_id, lock_id.unwrap_or(0), amount, duration_sec);
             },
-            MFTokenReceiverMessage::Append { append_duration_sec } => {
-                self.append_lpt(&sender_id, amount, append_duration_sec);
+            MFTokenReceiverMessage::Append { append_duration_sec, lock_id, receiver_id } => {
+                let receiver_id = receiver_id.unwrap_or_else(|| sender_id.clone());
+                self.append_lpt(&sender_id, &receiver_id, &token_id, lock_id.unwrap_or(0), amount, append_duration_sec);
+            },
+            MFTokenReceiverMessage::CreateProposal { kind, description, start_at, duration_sec, voting_mode, poll_mode } => {
+                let mut lock_pool = self.internal_unwrap_lock_pool(&token_id);
+                let (bond, bond_refund) = self.internal_post_bond(&sender_id, &kind, BondAsset::Lpt { token_id }, amount);
+                lock_pool.bonded_lpt += bond.amount;
+                self.internal_set_lock_pool(&lock_pool);
+                // a transfer call can't carry NEAR, the storage is paid from the available storage of the proposer
+                let prev_storage = env::storage_usage();
+                self.internal_create_proposal(&sender_id, kind, description, start_at, duration_sec, voting_mode, poll_mode, Some(Box::new(bond)));
+                self.internal_charge_storage(&sender_id, prev_storage);
+                refund += bond_refund;
             },
         }
         PromiseOrValue::Value(U128(refund))
@@ -92,31 +163,59 @@ impl MFTTokenReceiver for Contract {
 
 impl Contract {
 
+    /// sender_id locks for account_id, which is the sender itself or the receiver it locks on behalf of.
     pub fn lock_lpt(
         &mut self,
+        sender_id: &AccountId,
         account_id: &AccountId,
+        token_id: &String,
+        lock_id: u32,
         amount: Balance,
         duration_sec: u32,
     ) {
-        let mut account = self.internal_unwrap_or_default_account(account_id);
+        let mut account = self.internal_unwrap_or_sponsored_account(sender_id, account_id);
+        self.internal_settle_slashed_lpt(account_id, &mut account);
         let config = self.internal_config();
-        require!(self.internal_get_account(account_id).is_some() ||  amount >= MIN_FIRST_LOCK, E503_FIRST_LOCK_TOO_FEW);
+        let mut lock_pool = self.internal_unwrap_lock_pool(token_id);
+        require!(self.internal_get_account(account_id).is_some() ||  amount >= lock_pool.min_first_lock(), E503_FIRST_LOCK_TOO_FEW);
         require!(duration_sec >= config.min_locking_duration_sec, E302_INVALID_DURATION);
         require!(duration_sec <= config.max_locking_duration_sec, E302_INVALID_DURATION);
 
-        let increased_ve_lpt = account.lock_lpt(amount, duration_sec, &config, self.data().lptoken_decimals);
-        require!(increased_ve_lpt > 0, E101_INSUFFICIENT_BALANCE);
-        self.mint_love_token(account_id, increased_ve_lpt);
+        let mut lock = account.take_lock(lock_id);
+        if lock.lpt_amount == 0 {
+            lock.token_id = token_id.clone();
+            lock.slashed_lpt_per_share_paid = lock_pool.slashed_lpt_per_share;
+        } else {
+            // relocking would extend the unlock time of the receiver
+            require!(sender_id == account_id, E109_EXTEND_OTHERS_LOCK);
+        }
+        require!(&lock.token_id == token_id, E108_LOCK_POOL_MISMATCH);
+        let prev_ve_state = lock.get_ve_state(env::block_timestamp());
+        let (increased_love, decreased_love) = lock.lock_lpt(amount, duration_sec, &config, &lock_pool);
+        if increased_love > 0 {
+            self.mint_love_token(account_id, increased_love);
+        }
+        if decreased_love > 0 {
+            self.burn_love_token(account_id, decreased_love);
+        }
 
-        self.data_mut().cur_lock_lpt += amount;
-        self.data_mut().cur_total_ve_lpt += increased_ve_lpt;
+        let cur_ve_state = lock.get_ve_state(env::block_timestamp());
+        account.put_lock(lock_id, lock);
+        let increased_ve_lpt = cur_ve_state.ve_lpt_amount - prev_ve_state.ve_lpt_amount;
+        require!(increased_ve_lpt > 0, E101_INSUFFICIENT_BALANCE);
+        lock_pool.cur_lock_lpt += amount;
+        self.internal_set_lock_pool(&lock_pool);
+        self.internal_sync_total_ve_lpt(&prev_ve_state, &cur_ve_state);
 
         self.update_impacted_proposals(&mut account, increased_ve_lpt, true);
 
+        account.checkpoint_ve_lpt();
         self.internal_set_account(account_id, account);
 
         Event::LptLock {
             caller_id: account_id,
+            token_id,
+            lock_id,
             deposit_amount: &U128(amount),
             increased_ve_lpt: &U128(increased_ve_lpt),
             duration: duration_sec,
@@ -126,45 +225,68 @@ impl Contract {
 
     pub fn append_lpt(
         &mut self,
+        sender_id: &AccountId,
         account_id: &AccountId,
+        token_id: &String,
+        lock_id: u32,
         amount: Balance,
         append_duration_sec: u32,
     ) {
+        require!(sender_id == account_id || append_duration_sec == 0, E109_EXTEND_OTHERS_LOCK);
         let mut account = self.internal_unwrap_account(account_id);
-        require!(account.unlock_timestamp != 0, E105_ACC_NOT_LOCKED);
-        let timestamp = env::block_timestamp();
-        let duration_sec = nano_to_sec(account.unlock_timestamp) - nano_to_sec(timestamp) + append_duration_sec;
-
+        self.internal_settle_slashed_lpt(account_id, &mut account);
         let config = self.internal_config();
+        let duration_sec = account.unwrap_lock(lock_id).get_remaining_sec(env::block_timestamp(), &config) + append_duration_sec;
+
         require!(duration_sec >= config.min_locking_duration_sec, E302_INVALID_DURATION);
         require!(duration_sec <= config.max_locking_duration_sec, E302_INVALID_DURATION);
 
-        let increased_ve_lpt = account.lock_lpt(amount, duration_sec, &config, self.data().lptoken_decimals);
-        require!(increased_ve_lpt > 0, E101_INSUFFICIENT_BALANCE);
-        self.mint_love_token(account_id, increased_ve_lpt);
+        let mut lock_pool = self.internal_unwrap_lock_pool(token_id);
+        let mut lock = account.take_lock(lock_id);
+        if lock.lpt_amount == 0 {
+            lock.token_id = token_id.clone();
+            lock.slashed_lpt_per_share_paid = lock_pool.slashed_lpt_per_share;
+        }
+        require!(&lock.token_id == token_id, E108_LOCK_POOL_MISMATCH);
+        let prev_ve_state = lock.get_ve_state(env::block_timestamp());
+        let (increased_love, decreased_love) = lock.lock_lpt(amount, duration_sec, &config, &lock_pool);
+        if increased_love > 0 {
+            self.mint_love_token(account_id, increased_love);
+        }
+        if decreased_love > 0 {
+            self.burn_love_token(account_id, decreased_love);
+        }
 
-        self.data_mut().cur_lock_lpt += amount;
-        self.data_mut().cur_total_ve_lpt += increased_ve_lpt;
+        let cur_ve_state = lock.get_ve_state(env::block_timestamp());
+        account.put_lock(lock_id, lock);
+        let increased_ve_lpt = cur_ve_state.ve_lpt_amount - prev_ve_state.ve_lpt_amount;
+        require!(increased_ve_lpt > 0, E101_INSUFFICIENT_BALANCE);
+        lock_pool.cur_lock_lpt += amount;
+        self.internal_set_lock_pool(&lock_pool);
+        self.internal_sync_total_ve_lpt(&prev_ve_state, &cur_ve_state);
 
         self.update_impacted_proposals(&mut account, increased_ve_lpt, true);
 
+        account.checkpoint_ve_lpt();
         self.internal_set_account(account_id, account);
 
         Event::LptAppend {
             caller_id: account_id,
+            token_id,
+            lock_id,
             deposit_amount: &U128(amount),
             increased_ve_lpt: &U128(increased_ve_lpt),
             duration: duration_sec,
         }
         .emit();
     }
+}
 
-    pub fn real_amount_and_refund(&self, amount: Balance) -> (Balance, Balance) {
-        if self.data().lptoken_decimals > LOVE_DECIMAL {
-            let decimals_diff = 10u128.pow((self.data().lptoken_decimals - LOVE_DECIMAL) as u32);
-            (amount / decimals_diff * decimals_diff, amount % decimals_diff)
-        } else {
-            (amount, 0)
-        }
+pub fn real_amount_and_refund(lock_pool: &LockPool, amount: Balance) -> (Balance, Balance) {
+    if lock_pool.decimals > LOVE_DECIMAL {
+        let decimals_diff = 10u128.pow((lock_pool.decimals - LOVE_DECIMAL) as u32);
+        (amount / decimals_diff * decimals_diff, amount % decimals_diff)
+    } else {
+        (amount, 0)
     }
 }
\ No newline at end of file
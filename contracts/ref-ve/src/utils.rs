@@ -1,3 +1,6 @@
+// the generated ext_self::callback_refund_lpt_and_call takes the gas and deposit as well
+#![allow(clippy::too_many_arguments)]
+
 use std::collections::HashSet;
 
 // use near_sdk::serde::{Deserialize, Serialize};
@@ -20,19 +23,48 @@ pub const DEFAULT_MAX_VOTING_DURATION_SEC: DurationSec = DAY_SEC * 30;
 pub const DEFAULT_MIN_PROPOSAL_START_VOTE_OFFSET_SEC: u32 = DAY_SEC;
 pub const DEFAULT_MIN_LOCKING_DURATION_SEC: DurationSec = DAY_SEC * 30;
 pub const DEFAULT_MAX_LOCKING_DURATION_SEC: DurationSec = DAY_SEC * 30 * 12; 
+/// A decaying lock unlocks at the start of a period, like weeks in Curve.
+pub const DECAY_PERIOD_SEC: DurationSec = DAY_SEC * 7;
 pub const DEFAULT_MAX_LOCKING_REWARD_RATIO: u32 = 20000;
 pub const MIN_LOCKING_REWARD_RATIO: u32 = 10000;
+pub const DEFAULT_MAX_EARLY_UNLOCK_PENALTY_RATE: u32 = 5000;
+pub const DEFAULT_EXECUTION_TIMELOCK_SEC: DurationSec = DAY_SEC * 2;
+pub const DEFAULT_INCENTIVE_CLAIM_GRACE_SEC: DurationSec = DAY_SEC * 30;
+pub const MAX_EARLY_UNLOCK_PENALTY_RATE: u32 = 10000;
+pub const SLASHED_LPT_PER_SHARE_PRECISION: u128 = 10u128.pow(18);
+pub const MAX_LOCK_POOL_DECIMALS: u8 = 24;
 
 pub const TGAS: u64 = 1_000_000_000_000;
 pub const GAS_FOR_LPT_TRANSFER: Gas = Gas(20 * TGAS);
 pub const GAS_FOR_RESOLVE_LPT_TRANSFER: Gas = Gas(10 * TGAS);
+pub const GAS_FOR_LPT_TRANSFER_CALL: Gas = Gas(150 * TGAS);
+/// enough to check and transfer the refunded lpt back to the user
+pub const GAS_FOR_RESOLVE_LPT_TRANSFER_CALL: Gas = Gas(60 * TGAS);
+pub const GAS_FOR_LPT_BALANCE_OF: Gas = Gas(5 * TGAS);
+pub const GAS_FOR_RESOLVE_LPT_REFUND: Gas = Gas(40 * TGAS);
 pub const GAS_FOR_REWARD_TRANSFER: Gas = Gas(20 * TGAS);
 pub const GAS_FOR_RESOLVE_REWARD_TRANSFER: Gas = Gas(10 * TGAS);
 pub const GAS_FOR_REMOVED_PROPOSAL_ASSETS: Gas = Gas(20 * TGAS);
 pub const GAS_FOR_RESOLVE_REMOVED_PROPOSAL_ASSETS: Gas = Gas(10 * TGAS);
+pub const GAS_FOR_RESOLVE_EXECUTE_PROPOSAL: Gas = Gas(10 * TGAS);
+pub const GAS_FOR_RESOLVE_BOND_TRANSFER: Gas = Gas(10 * TGAS);
+// what execute_proposal takes itself besides the actions and its callback
+pub const GAS_FOR_EXECUTE_PROPOSAL: Gas = Gas(20 * TGAS);
+pub const MAX_TX_GAS: Gas = Gas(300 * TGAS);
 
 pub const DESCRIPTION_LIMIT: usize = 2048;
 pub const MIN_FIRST_LOCK: u128 = 10u128.pow(22);
+pub const MAX_LOCK_POSITIONS: usize = 16;
+/// bounds the distinct rankings kept inline in a ranked choice poll, at most 6 + 6*5 + 6*5*4 = 156
+pub const MAX_RANKED_CHOICE_OPTIONS: usize = 6;
+pub const MAX_RANKING_LEN: usize = 3;
+/// bounds the checkpoints stored for each account, as the storage is not paid by the account
+pub const MAX_ACCOUNT_VE_CHECKPOINTS: u64 = 32;
+pub const BASE_VE_WEIGHT: u32 = 10000;
+pub const MAX_PROPOSAL_THRESHOLD: u32 = 10000;
+pub const FULL_VOTE_WEIGHT: u32 = 10000;
+pub const DEFAULT_APPROVAL_THRESHOLD: u32 = 5000;
+pub const DEFAULT_EXECUTABLE_QUORUM: u32 = 1000;
 pub const STORAGE_BALANCE_MIN_BOUND: u128 = 1_250_000_000_000_000_000_000;
 
 pub mod u64_dec_format {
@@ -166,6 +198,17 @@ pub trait MultiFungibleToken {
         amount: U128,
         memo: Option<String>,
     );
+
+    fn mft_balance_of(&self, token_id: String, account_id: AccountId) -> U128;
+
+    /// Returns how much was refunded back to the sender, memo is left out as never used.
+    fn mft_transfer_call(
+        &mut self,
+        token_id: String,
+        receiver_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> U128;
 }
 
 #[ext_contract(ext_self)]
@@ -174,15 +217,29 @@ pub trait TokenPostActions {
         &mut self, token_id: AccountId, sender_id: AccountId, amount: U128,
     );
 
+    fn callback_withdraw_removed_incentive(
+        &mut self, token_id: AccountId, sender_id: AccountId, amount: U128,
+    );
+
     fn callback_removed_proposal_assets(
         &mut self, token_id: AccountId, receiver_id: AccountId, amount: U128,
     );
 
-    fn callback_withdraw_lpt(&mut self, sender_id: AccountId, amount: U128);
+    fn callback_execute_proposal(&mut self, proposal_id: u32);
+
+    fn callback_transfer_bond(&mut self, proposal_id: u32, asset: crate::BondAsset, receiver_id: AccountId, amount: U128);
+
+    fn callback_withdraw_lpt(&mut self, sender_id: AccountId, token_id: String, amount: U128);
+
+    fn callback_withdraw_lpt_and_call(&mut self, sender_id: AccountId, token_id: String, receiver_id: AccountId, amount: U128);
+
+    fn callback_refund_lpt_and_call(&mut self, sender_id: AccountId, token_id: String, receiver_id: AccountId, amount: U128, unused_amount: U128);
+
+    fn callback_withdraw_lpt_lostfound(&mut self, receiver_id: AccountId, token_id: String, amount: U128);
 
-    fn callback_withdraw_lpt_lostfound(&mut self, receiver_id: AccountId, amount: U128);
+    fn callback_withdraw_lpt_slashed(&mut self, sender_id: AccountId, token_id: String, amount: U128, slashed_amount: U128);
 
-    fn callback_withdraw_lpt_slashed(&mut self, sender_id: AccountId, amount: U128);
+    fn callback_transfer_slashed_lpt(&mut self, receiver_id: AccountId, token_id: String, amount: U128);
 }
 
 #[cfg(test)]
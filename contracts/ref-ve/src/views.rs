@@ -8,16 +8,22 @@ pub struct Metadata {
     pub version: String,
     pub owner_id: AccountId,
     pub operators: Vec<AccountId>,
+    pub guardians: Vec<AccountId>,
     pub whitelisted_accounts: Vec<AccountId>,
     pub whitelisted_incentive_tokens: Vec<AccountId>,
+    #[serde(with = "u128_map_format")]
+    pub min_incentive_deposits: HashMap<AccountId, Balance>,
+    pub whitelisted_love_receivers: Vec<AccountId>,
     pub lptoken_contract_id: AccountId,
     pub lptoken_id: String,
     pub lptoken_decimals: u8,
+    pub lock_pools: Vec<LockPool>,
     pub account_count: U64,
     pub proposal_count: U64,
     pub cur_total_ve_lpt: U128,
     pub cur_lock_lpt: U128,
     pub lostfound: U128,
+    pub execution_treasury: U128,
 }
 
 #[derive(Serialize)]
@@ -25,10 +31,43 @@ pub struct Metadata {
 #[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
 pub struct AccountInfo {
     pub sponsor_id: AccountId,
+    /// The amount of LPT locked in the default position, kept for the clients before positions
+    #[serde(with = "u128_dec_format")]
+    pub lpt_amount: Balance,
+    /// The amount of LPT locked in all positions, keyed by lptoken id
+    pub lpt_amounts: HashMap<String, U128>,
+    /// The amount of veLPT the account holds currently
+    #[serde(with = "u128_dec_format")]
+    pub ve_lpt_amount: Balance,
+    /// When the default position can be unlocked without slash in nanoseconds.
+    #[serde(with = "u64_dec_format")]
+    pub unlock_timestamp: u64,
+    /// The duration of current locking of the default position in seconds.
+    pub duration_sec: u32,
+    /// Lock positions keyed by position id
+    pub locks: HashMap<u32, LockInfo>,
+    #[serde(with = "u128_map_format")]
+    pub rewards: HashMap<AccountId, Balance>,
+    /// The account it delegates veLPT to
+    pub delegatee: Option<AccountId>,
+    /// The veLPT delegated to delegatee
+    #[serde(with = "u128_dec_format")]
+    pub delegated_ve_lpt: Balance,
+    /// The veLPT delegated to it by others
+    #[serde(with = "u128_dec_format")]
+    pub received_ve_lpt: Balance,
+}
+
+#[derive(Serialize)]
+#[serde(crate = "near_sdk::serde")]
+#[cfg_attr(not(target_arch = "wasm32"), derive(Deserialize, Debug))]
+pub struct LockInfo {
+    /// Which lptoken locked in
+    pub token_id: String,
     /// The amount of LPT locked
     #[serde(with = "u128_dec_format")]
     pub lpt_amount: Balance,
-    /// The amount of veLPT the account holds
+    /// The amount of veLPT the position holds currently
     #[serde(with = "u128_dec_format")]
     pub ve_lpt_amount: Balance,
     /// When the locking token can be unlocked without slash in nanoseconds.
@@ -36,8 +75,8 @@ pub struct AccountInfo {
     pub unlock_timestamp: u64,
     /// The duration of current locking in seconds.
     pub duration_sec: u32,
-    #[serde(with = "u128_map_format")]
-    pub rewards: HashMap<AccountId, Balance>,
+    /// Whether the position is auto relocked with the max locking duration.
+    pub auto_relock: bool,
 }
 
 #[derive(Serialize)]
@@ -52,20 +91,26 @@ pub struct StorageReport {
 impl Contract {
     //******** Contract Concern */
     pub fn get_metadata(&self) -> Metadata {
+        let default_pool = self.internal_unwrap_lock_pool(&self.data().lptoken_id);
         Metadata {
             version: env!("CARGO_PKG_VERSION").to_string(),
             owner_id: self.data().owner_id.clone(),
             operators: self.data().operators.to_vec(),
+            guardians: self.data().guardians.to_vec(),
             whitelisted_accounts: self.data().whitelisted_accounts.to_vec(),
             whitelisted_incentive_tokens: self.data().whitelisted_incentive_tokens.iter().cloned().collect(),
+            min_incentive_deposits: self.data().min_incentive_deposits.clone(),
+            whitelisted_love_receivers: self.data().whitelisted_love_receivers.iter().cloned().collect(),
             lptoken_contract_id: self.data().lptoken_contract_id.clone(),
-            lptoken_id: self.data().lptoken_id.clone(),
-            lptoken_decimals: self.data().lptoken_decimals,
+            lptoken_id: default_pool.token_id,
+            lptoken_decimals: default_pool.decimals,
+            lock_pools: self.data().lock_pools.values().collect(),
             account_count: self.data().account_count.into(),
             proposal_count: self.data().proposals.len().into(),
-            cur_total_ve_lpt: self.data().cur_total_ve_lpt.into(),
-            cur_lock_lpt: self.data().cur_lock_lpt.into(),
-            lostfound: self.data().lostfound.into(),
+            cur_total_ve_lpt: self.internal_cur_total_ve_lpt().into(),
+            cur_lock_lpt: default_pool.cur_lock_lpt.into(),
+            lostfound: default_pool.lostfound.into(),
+            execution_treasury: self.data().execution_treasury.into(),
         }
     }
 
@@ -93,6 +138,7 @@ impl Contract {
             .map(|index| {
                 let mut proposal: Proposal = values.get(index).unwrap().into();
                 proposal.update_status();
+                self.internal_fill_proposal_result(&mut proposal);
                 proposal
             })
             .collect()
@@ -101,12 +147,20 @@ impl Contract {
     pub fn get_proposal(&self, proposal_id: u32) -> Option<Proposal> {
         if let Some(mut proposal) = self.internal_get_proposal(proposal_id) {
             proposal.update_status();
+            self.internal_fill_proposal_result(&mut proposal);
             Some(proposal)
         } else {
             None
         }
     }
 
+    /// The outcome of the proposal, None until it expires or if the proposal doesn't exist.
+    pub fn get_proposal_outcome(&self, proposal_id: u32) -> Option<ProposalOutcome> {
+        let mut proposal = self.internal_get_proposal(proposal_id)?;
+        proposal.update_status();
+        self.internal_proposal_outcome(&proposal)
+    }
+
     pub fn get_unclaimed_rewards(
         &self,
         account_id: AccountId,
@@ -122,20 +176,54 @@ impl Contract {
         &self,
         account_id: AccountId
     ) -> Option<AccountInfo> {
-        if let Some(account) = self.internal_get_account(&account_id) {
+        if let Some(mut account) = self.internal_get_account(&account_id) {
+            self.internal_preview_slashed_lpt(&mut account);
+            let timestamp = env::block_timestamp();
+            let mut lpt_amounts = HashMap::new();
+            for lock in account.locks.values() {
+                let amount = lpt_amounts.entry(lock.token_id.clone()).or_insert(U128(0));
+                amount.0 += lock.lpt_amount;
+            }
+            let default_lock = account.locks.get(&0);
             Some(AccountInfo {
+                lpt_amount: default_lock.map(|lock| lock.lpt_amount).unwrap_or(0),
+                lpt_amounts,
+                ve_lpt_amount: account.get_ve_lpt_amount(timestamp),
+                unlock_timestamp: default_lock.map(|lock| lock.unlock_timestamp).unwrap_or(0),
+                duration_sec: default_lock.map(|lock| lock.duration_sec).unwrap_or(0),
+                locks: account.locks.iter().map(|(lock_id, lock)| (*lock_id, LockInfo {
+                    token_id: lock.token_id.clone(),
+                    lpt_amount: lock.lpt_amount,
+                    ve_lpt_amount: lock.get_ve_lpt_amount(timestamp),
+                    unlock_timestamp: lock.unlock_timestamp,
+                    duration_sec: lock.duration_sec,
+                    auto_relock: lock.auto_relock,
+                })).collect(),
                 sponsor_id: account.sponsor_id,
-                lpt_amount: account.lpt_amount,
-                ve_lpt_amount: account.ve_lpt_amount,
-                unlock_timestamp: account.unlock_timestamp,
-                duration_sec: account.duration_sec,
                 rewards: account.rewards,
+                delegatee: account.delegatee,
+                delegated_ve_lpt: account.delegated_ve_lpt,
+                received_ve_lpt: account.received_ve_lpt,
             })
         } else {
             None
         }
     }
 
+    /// The veLPT of account_id at timestamp_sec, the future one is projected by its current locks,
+    /// 0 if timestamp_sec is older than the kept checkpoints.
+    pub fn get_ve_lpt_at(&self, account_id: AccountId, timestamp_sec: u32) -> U128 {
+        self.internal_get_account(&account_id)
+            .and_then(|account| account.get_ve_lpt_amount_at(timestamp_sec))
+            .unwrap_or(0)
+            .into()
+    }
+
+    /// The total veLPT at timestamp_sec, the future one is projected by the slope schedule.
+    pub fn get_total_ve_lpt_at(&self, timestamp_sec: u32) -> U128 {
+        self.internal_get_total_ve_lpt_at(timestamp_sec).into()
+    }
+
     pub fn get_vote_detail(
         &self,
         account_id: AccountId
@@ -144,7 +232,7 @@ impl Contract {
             let mut result = HashMap::new();
             for (proposal_id, vote_detail) in account.proposals {
                 let proposal = self.internal_unwrap_proposal(proposal_id);
-                if proposal.status != Some(ProposalStatus::Expired) {
+                if !proposal.is_finished() {
                     result.insert(proposal_id, vote_detail.clone());
                 }
             }
@@ -162,7 +250,7 @@ impl Contract {
             let mut result = HashMap::new();
             for (proposal_id, vote_detail) in account.proposals {
                 let proposal = self.internal_unwrap_proposal(proposal_id);
-                if proposal.status == Some(ProposalStatus::Expired) {
+                if proposal.is_finished() {
                     result.insert(proposal_id, vote_detail.clone());
                 }
             }
@@ -195,6 +283,14 @@ impl Contract {
         }
     }
 
+    /// The incentives of removed or vetoed proposals the account can withdraw.
+    pub fn get_removed_incentives(&self, account_id: AccountId) -> HashMap<AccountId, U128> {
+        self.internal_get_removed_incentives(&account_id)
+            .into_iter()
+            .map(|(key, val)| (key, val.into()))
+            .collect()
+    }
+
     pub fn list_removed_proposal_assets(&self, from_index: Option<u64>, limit: Option<u64>) -> HashMap<AccountId, U128> {
         let keys = self.data().removed_proposal_assets.keys_as_vector();
 
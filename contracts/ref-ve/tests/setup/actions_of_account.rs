@@ -1,4 +1,5 @@
 use crate::*;
+use near_sdk::AccountId;
 use near_sdk::json_types::U128;
 
 impl Env {
@@ -11,9 +12,97 @@ impl Env {
             .function_call(
                 self.ve_contract.contract.withdraw_lpt(
                     if let Some(amount) = amount { Some(U128(amount)) } else { None },
+                    None,
                 ),
                 MAX_GAS.0,
                 1,
             )
     }
-}
\ No newline at end of file
+
+    pub fn withdraw_lpt_early(
+        &self,
+        operator: &UserAccount,
+        amount: Option<u128>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.withdraw_lpt_early(
+                    if let Some(amount) = amount { Some(U128(amount)) } else { None },
+                    None,
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn extend_lock(
+        &self,
+        operator: &UserAccount,
+        lock_id: u32,
+        append_duration_sec: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.extend_lock(
+                    append_duration_sec,
+                    Some(lock_id),
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn set_auto_relock(
+        &self,
+        operator: &UserAccount,
+        lock_id: u32,
+        auto_relock: bool
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.set_auto_relock(
+                    auto_relock,
+                    Some(lock_id),
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn withdraw_lpt_position(
+        &self,
+        operator: &UserAccount,
+        lock_id: u32,
+        amount: Option<u128>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.withdraw_lpt(
+                    if let Some(amount) = amount { Some(U128(amount)) } else { None },
+                    Some(lock_id),
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn withdraw_lpt_and_call(
+        &self,
+        operator: &UserAccount,
+        amount: Option<u128>,
+        receiver_id: &AccountId,
+        msg: String
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.withdraw_lpt_and_call(
+                    if let Some(amount) = amount { Some(U128(amount)) } else { None },
+                    None,
+                    receiver_id.clone(),
+                    msg,
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+}
@@ -10,17 +10,62 @@ impl Env {
         start_at: u32,
         duration_sec: u32,
         deposit: Balance
+    ) -> ExecutionResult {
+        self.create_proposal_with_voting_mode(operator, kind, description, start_at, duration_sec, None, deposit)
+    }
+
+    pub fn create_proposal_with_voting_mode(
+        &self,
+        operator: &UserAccount,
+        kind: ProposalKind,
+        description: String,
+        start_at: u32,
+        duration_sec: u32,
+        voting_mode: Option<VotingMode>,
+        deposit: Balance
+    ) -> ExecutionResult {
+        self.create_proposal_with_modes(operator, kind, description, start_at, duration_sec, voting_mode, None, deposit)
+    }
+
+    pub fn create_proposal_with_modes(
+        &self,
+        operator: &UserAccount,
+        kind: ProposalKind,
+        description: String,
+        start_at: u32,
+        duration_sec: u32,
+        voting_mode: Option<VotingMode>,
+        poll_mode: Option<PollMode>,
+        deposit: Balance
     ) -> ExecutionResult {
         operator
             .function_call(
                 self.ve_contract.contract.create_proposal(
-                    kind, description, start_at, duration_sec
+                    kind, description, start_at, duration_sec, voting_mode, poll_mode
                 ),
                 MAX_GAS.0,
                 deposit,
             )
     }
 
+    pub fn update_proposal(
+        &self,
+        operator: &UserAccount,
+        proposal_id: u32,
+        description: Option<String>,
+        kind: Option<ProposalKind>,
+        duration_sec: Option<u32>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.update_proposal(
+                    proposal_id, description, kind, duration_sec
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
     pub fn remove_proposal(
         &self,
         operator: &UserAccount,
@@ -36,6 +81,79 @@ impl Env {
             )
     }
 
+    pub fn veto_proposal(
+        &self,
+        operator: &UserAccount,
+        proposal_id: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.veto_proposal(
+                    proposal_id
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn settle_proposal(
+        &self,
+        operator: &UserAccount,
+        proposal_id: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.settle_proposal(
+                    proposal_id
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
+    pub fn claim_proposal_bond(
+        &self,
+        operator: &UserAccount,
+        proposal_id: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.claim_proposal_bond(
+                    proposal_id
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
+    pub fn deposit_execution_treasury(
+        &self,
+        user: &UserAccount,
+        amount: u128
+    ) -> ExecutionResult {
+        user
+            .function_call(
+                self.ve_contract.contract.deposit_execution_treasury(),
+                MAX_GAS.0,
+                amount,
+            )
+    }
+
+    pub fn execute_proposal(
+        &self,
+        operator: &UserAccount,
+        proposal_id: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.execute_proposal(
+                    proposal_id
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
     pub fn action_proposal(
         &self,
         operator: &UserAccount,
@@ -65,4 +183,31 @@ impl Env {
                 1,
             )
     }
-}
\ No newline at end of file
+
+    pub fn delegate(
+        &self,
+        operator: &UserAccount,
+        delegatee: &UserAccount
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.delegate(
+                    delegatee.account_id()
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn undelegate(
+        &self,
+        operator: &UserAccount
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.undelegate(),
+                MAX_GAS.0,
+                1,
+            )
+    }
+}
@@ -44,4 +44,35 @@ impl Env {
                 0,
             )
     }
+
+    pub fn sweep_incentives(
+        &self,
+        operator: &UserAccount, 
+        proposal_id: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.sweep_incentives(
+                    proposal_id
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
+
+    pub fn withdraw_removed_incentive(
+        &self,
+        operator: &UserAccount, 
+        token_id: &UserAccount, amount: Option<u128>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.withdraw_removed_incentive(
+                    token_id.account_id(), 
+                    amount.map(U128)
+                ),
+                MAX_GAS.0,
+                0,
+            )
+    }
 }
\ No newline at end of file
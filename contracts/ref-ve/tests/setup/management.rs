@@ -1,5 +1,6 @@
 use crate::*;
 use near_sdk::AccountId;
+use near_sdk::json_types::U128;
 
 impl Env {
     pub fn extend_whitelisted_accounts(
@@ -62,6 +63,21 @@ impl Env {
             )
     }
 
+    pub fn modify_min_incentive_deposits(
+        &self,
+        operator: &UserAccount,
+        min_deposits: Vec<(AccountId, U128)>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_min_incentive_deposits(
+                    min_deposits
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
     pub fn modify_min_start_vote_offset_sec(
         &self,
         operator: &UserAccount,
@@ -92,6 +108,21 @@ impl Env {
             )
     }
 
+    pub fn modify_locking_multiplier_curve(
+        &self,
+        operator: &UserAccount,
+        multiplier_curve: Vec<LockingMultiplierPoint>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_locking_multiplier_curve(
+                    multiplier_curve
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
     pub fn return_lpt_lostfound(
         &self,
         operator: &UserAccount,
@@ -100,7 +131,7 @@ impl Env {
         operator
             .function_call(
                 self.ve_contract.contract.return_lpt_lostfound(
-                    account.account_id(), amount.into()
+                    account.account_id(), amount.into(), None
                 ),
                 MAX_GAS.0,
                 1,
@@ -122,6 +153,21 @@ impl Env {
             )
     }
 
+    pub fn withdraw_execution_treasury(
+        &self,
+        operator: &UserAccount,
+        receiver: &UserAccount, amount: u128
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.withdraw_execution_treasury(
+                    receiver.account_id(), amount.into()
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
     pub fn modify_voting_duration_limit(
         &self,
         operator: &UserAccount,
@@ -136,4 +182,199 @@ impl Env {
                 1,
             )
     }
-}
\ No newline at end of file
+
+    pub fn modify_early_unlock_penalty(
+        &self,
+        operator: &UserAccount,
+        max_penalty_rate: u32, penalty_sink: PenaltySink
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_early_unlock_penalty(
+                    max_penalty_rate, penalty_sink
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn modify_decay_mode(
+        &self,
+        operator: &UserAccount,
+        decay_mode: DecayMode
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_decay_mode(
+                    decay_mode
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn extend_whitelisted_love_receivers(
+        &self,
+        operator: &UserAccount,
+        accounts: Vec<AccountId>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.extend_whitelisted_love_receivers(
+                    accounts
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn remove_whitelisted_love_receivers(
+        &self,
+        operator: &UserAccount,
+        accounts: Vec<AccountId>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.remove_whitelisted_love_receivers(
+                    accounts
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn modify_love_transfer_policy(
+        &self,
+        operator: &UserAccount,
+        policy: LoveTransferPolicy
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_love_transfer_policy(
+                    policy
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn modify_proposal_thresholds(
+        &self,
+        operator: &UserAccount,
+        proposal_thresholds: ProposalThresholds
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_proposal_thresholds(
+                    proposal_thresholds
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn modify_permissionless_proposal_policy(
+        &self,
+        operator: &UserAccount,
+        policy: Option<PermissionlessProposalPolicy>
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_permissionless_proposal_policy(
+                    policy
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn modify_incentive_sweep_policy(
+        &self,
+        operator: &UserAccount,
+        policy: IncentiveSweepPolicy
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_incentive_sweep_policy(
+                    policy
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn modify_execution_timelock_sec(
+        &self,
+        operator: &UserAccount,
+        execution_timelock_sec: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_execution_timelock_sec(
+                    execution_timelock_sec
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn modify_lock_receiver_policy(
+        &self,
+        operator: &UserAccount,
+        policy: LockReceiverPolicy
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_lock_receiver_policy(
+                    policy
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn register_lock_pool(
+        &self,
+        operator: &UserAccount,
+        token_id: &String, decimals: u8, ve_weight: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.register_lock_pool(
+                    token_id.clone(), decimals, ve_weight
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn register_ft_lock_pool(
+        &self,
+        operator: &UserAccount,
+        token: &UserAccount, decimals: u8, ve_weight: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.register_ft_lock_pool(
+                    token.account_id(), decimals, ve_weight
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+
+    pub fn modify_lock_pool_ve_weight(
+        &self,
+        operator: &UserAccount,
+        token_id: &String, ve_weight: u32
+    ) -> ExecutionResult {
+        operator
+            .function_call(
+                self.ve_contract.contract.modify_lock_pool_ve_weight(
+                    token_id.clone(), ve_weight
+                ),
+                MAX_GAS.0,
+                1,
+            )
+    }
+}
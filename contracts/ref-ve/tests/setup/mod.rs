@@ -10,22 +10,27 @@ pub use near_sdk_sim::{
 use mock_mft::ContractContract as MockMultiFungibleToken;
 
 pub use ref_ve::{ContractContract as VeContract,
-    Metadata, Proposal, ProposalKind, Action, Account, Config, VoteDetail, AccountInfo, VoteInfo
+    Metadata, Proposal, ProposalKind, Action, Account, Config, VoteDetail, AccountInfo, LockInfo, VoteInfo, PenaltySink, DecayMode, LockPool, LockPoolKind, LockReceiverPolicy, LockingMultiplierPoint, LoveTransferPolicy, VotingMode,
+    ProposalThreshold, ProposalThresholds, ProposalOutcome, FunctionCallAction, ExecutionStatus, PartialConfig, WhitelistDeltas,
+    FarmVoteWeight, PollMode, RankedChoiceResult, PermissionlessProposalPolicy, BondAsset, BondForfeitSink, BondStatus, ProposalStatus,
+    IncentiveSweepPolicy, IncentiveSweepSink, IncentiveCondition
 };
 
 pub use ref_ve::{
-    DAY_SEC,
+    DAY_SEC, DECAY_PERIOD_SEC, MAX_ACCOUNT_VE_CHECKPOINTS, MAX_RANKED_CHOICE_OPTIONS, MAX_RANKING_LEN,
     DEFAULT_MIN_PROPOSAL_START_VOTE_OFFSET_SEC, DEFAULT_MAX_LOCKING_DURATION_SEC, DEFAULT_MAX_LOCKING_REWARD_RATIO,
-    DEFAULT_MIN_LOCKING_DURATION_SEC, DEFAULT_MIN_VOTING_DURATION_SEC, DEFAULT_MAX_VOTING_DURATION_SEC
+    DEFAULT_MIN_LOCKING_DURATION_SEC, DEFAULT_MIN_VOTING_DURATION_SEC, DEFAULT_MAX_VOTING_DURATION_SEC,
+    DEFAULT_MAX_EARLY_UNLOCK_PENALTY_RATE, DEFAULT_EXECUTION_TIMELOCK_SEC, DEFAULT_INCENTIVE_CLAIM_GRACE_SEC, DEFAULT_EXECUTABLE_QUORUM, BASE_VE_WEIGHT
 };
 
 pub use ref_ve::{
     E002_NOT_ALLOWED, 
-    E100_ACC_NOT_REGISTERED, E101_INSUFFICIENT_BALANCE, E102_INSUFFICIENT_STORAGE, E103_STILL_HAS_REWARD, E104_STILL_HAS_LPT,E106_STILL_HAS_LOVE_TOKEN,
-    E200_ALREADY_VOTED, E201_INVALID_VOTE, E203_INVALID_INCENTIVE_TOKEN, E204_VOTE_CAN_NOT_CANCEL, E205_NOT_VOTABLE, E206_NO_VOTED, E207_INVALID_INCENTIVE_KEY, E208_DESCRIPTION_TOO_LONG,
-    E301_INVALID_RATIO, E302_INVALID_DURATION, E305_STILL_IN_LOCK, E306_INVALID_LOCK_DURATION_LIMIT, E307_INVALID_VOTING_DURATION_LIMIT,
-    E402_INVALID_START_TIME, E404_PROPOSAL_NOT_EXIST, E406_EXPIRED_PROPOSAL,
-    E503_FIRST_LOCK_TOO_FEW
+    E100_ACC_NOT_REGISTERED, E101_INSUFFICIENT_BALANCE, E102_INSUFFICIENT_STORAGE, E103_STILL_HAS_REWARD, E104_STILL_HAS_LPT, E105_ACC_NOT_LOCKED, E106_STILL_HAS_LOVE_TOKEN, E108_LOCK_POOL_MISMATCH, E109_EXTEND_OTHERS_LOCK, E110_AUTO_RELOCKED, E111_AUTO_RELOCK_UNCHANGED, E112_LOVE_NOT_TRANSFERABLE, E113_LOVE_RECEIVER_NOT_WHITELISTED, E114_NOT_DELEGATED, E115_INVALID_DELEGATEE, E116_LOVE_NOT_MOVABLE, E117_VE_LPT_HISTORY_DROPPED,
+    E200_ALREADY_VOTED, E201_INVALID_VOTE, E202_INVALID_INCENTIVE, E203_INVALID_INCENTIVE_TOKEN, E204_VOTE_CAN_NOT_CANCEL, E205_NOT_VOTABLE, E206_NO_VOTED, E207_INVALID_INCENTIVE_KEY, E208_DESCRIPTION_TOO_LONG, E209_INCENTIVE_DEPOSIT_TOO_SMALL,
+    E301_INVALID_RATIO, E302_INVALID_DURATION, E303_INSUFFICIENT_VE_LPT, E305_STILL_IN_LOCK, E306_INVALID_LOCK_DURATION_LIMIT, E307_INVALID_VOTING_DURATION_LIMIT, E308_INVALID_PENALTY_RATE, E309_INVALID_VE_WEIGHT, E310_INVALID_MULTIPLIER_CURVE, E311_INVALID_PROPOSAL_THRESHOLD, E312_INVALID_PROPOSAL_BOND, E313_INVALID_CLAIM_GRACE_PERIOD,
+    E402_INVALID_START_TIME, E404_PROPOSAL_NOT_EXIST, E406_EXPIRED_PROPOSAL, E407_NOT_EXECUTABLE, E408_PROPOSAL_NOT_PASSED, E409_TIMELOCK_NOT_ELAPSED, E410_INVALID_EXECUTION_ACTION, E412_INVALID_POLL_MODE, E413_NOT_PERMISSIONLESS_KIND, E414_BOND_NOT_CLAIMABLE, E415_PROPOSAL_NOT_UPDATABLE, E416_PROPOSAL_KIND_CHANGED, E417_PROPOSAL_NOT_VETOABLE, E418_INCENTIVE_NOT_SWEEPABLE, E419_EXECUTION_GAS_EXCEEDED, E420_INCENTIVE_FARM_CHANGED, E421_INSUFFICIENT_EXECUTION_TREASURY,
+    E503_FIRST_LOCK_TOO_FEW,
+    E600_MFT_INVALID_LPTOKEN_ID, E602_LOCK_POOL_ALREADY_EXIST, E603_FT_INVALID_LOCK_TOKEN, E604_INVALID_LOCK_POOL_DECIMALS
 };
 
 mod users;
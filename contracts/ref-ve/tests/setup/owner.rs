@@ -50,4 +50,38 @@ impl Env {
             deposit,
         )
     }
+
+    pub fn extend_guardians(
+        &self, 
+        operator: &UserAccount,
+        guardians: Vec<&UserAccount>,
+        deposit: u128
+    ) -> ExecutionResult {
+        let guardians = guardians.iter().map(|v| v.account_id()).collect::<Vec<_>>();
+        operator
+        .function_call(
+            self.ve_contract.contract.extend_guardians(
+                guardians,
+            ),
+            MAX_GAS.0,
+            deposit,
+        )
+    }
+
+    pub fn remove_guardians(
+        &self, 
+        operator: &UserAccount,
+        guardians: Vec<&UserAccount>,
+        deposit: u128
+    ) -> ExecutionResult {
+        let guardians = guardians.iter().map(|v| v.account_id()).collect::<Vec<_>>();
+        operator
+        .function_call(
+            self.ve_contract.contract.remove_guardians(
+                guardians,
+            ),
+            MAX_GAS.0,
+            deposit,
+        )
+    }
 }
\ No newline at end of file
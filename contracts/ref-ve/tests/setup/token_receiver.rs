@@ -27,6 +27,76 @@ impl Env {
         )
     }
 
+    pub fn deposit_reward_with_condition(
+        &self,
+        token: &UserAccount,
+        user: &UserAccount,
+        amount: Balance,
+        proposal_id: u32,
+        incentive_key: u32,
+        condition: IncentiveCondition,
+    ) -> ExecutionResult {
+        user.call(
+            token.account_id.clone(),
+            "ft_transfer_call",
+            &json!({
+                "receiver_id": self.ve_contract.user_account.account_id(),
+                "amount": U128::from(amount),
+                "msg": json!({"Reward": {"proposal_id": proposal_id, "incentive_key": incentive_key, "condition": condition}}).to_string(),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS.0,
+            1,
+        )
+    }
+
+    pub fn lock_ft(
+        &self,
+        token: &UserAccount,
+        user: &UserAccount,
+        lock_id: u32,
+        amount: Balance,
+        duration_sec: u32,
+    ) -> ExecutionResult {
+        user.call(
+            token.account_id.clone(),
+            "ft_transfer_call",
+            &json!({
+                "receiver_id": self.ve_contract.user_account.account_id(),
+                "amount": U128::from(amount),
+                "msg": format!("{{\"Lock\": {{\"duration_sec\": {}, \"lock_id\": {}}}}}", duration_sec, lock_id),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS.0,
+            1,
+        )
+    }
+
+    pub fn append_ft(
+        &self,
+        token: &UserAccount,
+        user: &UserAccount,
+        lock_id: u32,
+        amount: Balance,
+        append_duration_sec: u32,
+    ) -> ExecutionResult {
+        user.call(
+            token.account_id.clone(),
+            "ft_transfer_call",
+            &json!({
+                "receiver_id": self.ve_contract.user_account.account_id(),
+                "amount": U128::from(amount),
+                "msg": format!("{{\"Append\": {{\"append_duration_sec\": {}, \"lock_id\": {}}}}}", append_duration_sec, lock_id),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS.0,
+            1,
+        )
+    }
+
     pub fn lock_lpt(
         &self,
         user: &UserAccount,
@@ -49,6 +119,38 @@ impl Env {
         )
     }
 
+    pub fn create_proposal_with_lpt_bond(
+        &self,
+        user: &UserAccount,
+        amount: Balance,
+        kind: ProposalKind,
+        description: String,
+        start_at: u32,
+        duration_sec: u32,
+    ) -> ExecutionResult {
+        user.call(
+            self.lptoken_contract.account_id(),
+            "mft_transfer_call",
+            &json!({
+                "token_id": &lpt_id(),
+                "receiver_id": self.ve_contract.user_account.account_id(),
+                "amount": U128::from(amount),
+                "msg": json!({
+                    "CreateProposal": {
+                        "kind": kind,
+                        "description": description,
+                        "start_at": start_at,
+                        "duration_sec": duration_sec,
+                    }
+                }).to_string(),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS.0,
+            1,
+        )
+    }
+
     pub fn append_lpt(
         &self,
         user: &UserAccount,
@@ -70,4 +172,109 @@ impl Env {
             1,
         )
     }
-}
\ No newline at end of file
+
+    pub fn lock_lpt_position(
+        &self,
+        user: &UserAccount,
+        lock_id: u32,
+        amount: Balance,
+        duration_sec: u32,
+    ) -> ExecutionResult {
+        self.lock_pool_lpt(user, &lpt_id(), lock_id, amount, duration_sec)
+    }
+
+    pub fn lock_pool_lpt(
+        &self,
+        user: &UserAccount,
+        token_id: &String,
+        lock_id: u32,
+        amount: Balance,
+        duration_sec: u32,
+    ) -> ExecutionResult {
+        user.call(
+            self.lptoken_contract.account_id(),
+            "mft_transfer_call",
+            &json!({
+                "token_id": token_id,
+                "receiver_id": self.ve_contract.user_account.account_id(),
+                "amount": U128::from(amount),
+                "msg": format!("{{\"Lock\": {{\"duration_sec\": {}, \"lock_id\": {}}}}}", duration_sec, lock_id),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS.0,
+            1,
+        )
+    }
+
+    pub fn append_lpt_position(
+        &self,
+        user: &UserAccount,
+        lock_id: u32,
+        amount: Balance,
+        append_duration_sec: u32,
+    ) -> ExecutionResult {
+        user.call(
+            self.lptoken_contract.account_id(),
+            "mft_transfer_call",
+            &json!({
+                "token_id": &lpt_id(),
+                "receiver_id": self.ve_contract.user_account.account_id(),
+                "amount": U128::from(amount),
+                "msg": format!("{{\"Append\": {{\"append_duration_sec\": {}, \"lock_id\": {}}}}}", append_duration_sec, lock_id),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS.0,
+            1,
+        )
+    }
+
+    pub fn lock_lpt_for(
+        &self,
+        user: &UserAccount,
+        receiver: &UserAccount,
+        lock_id: u32,
+        amount: Balance,
+        duration_sec: u32,
+    ) -> ExecutionResult {
+        user.call(
+            self.lptoken_contract.account_id(),
+            "mft_transfer_call",
+            &json!({
+                "token_id": &lpt_id(),
+                "receiver_id": self.ve_contract.user_account.account_id(),
+                "amount": U128::from(amount),
+                "msg": format!("{{\"Lock\": {{\"duration_sec\": {}, \"lock_id\": {}, \"receiver_id\": \"{}\"}}}}", duration_sec, lock_id, receiver.account_id),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS.0,
+            1,
+        )
+    }
+
+    pub fn append_lpt_for(
+        &self,
+        user: &UserAccount,
+        receiver: &UserAccount,
+        lock_id: u32,
+        amount: Balance,
+        append_duration_sec: u32,
+    ) -> ExecutionResult {
+        user.call(
+            self.lptoken_contract.account_id(),
+            "mft_transfer_call",
+            &json!({
+                "token_id": &lpt_id(),
+                "receiver_id": self.ve_contract.user_account.account_id(),
+                "amount": U128::from(amount),
+                "msg": format!("{{\"Append\": {{\"append_duration_sec\": {}, \"lock_id\": {}, \"receiver_id\": \"{}\"}}}}", append_duration_sec, lock_id, receiver.account_id),
+            })
+            .to_string()
+            .into_bytes(),
+            MAX_GAS.0,
+            1,
+        )
+    }
+}
@@ -47,6 +47,20 @@ impl Env {
             ).assert_success();
     }
 
+    pub fn transfer_love(
+        &self,
+        user: &UserAccount,
+        to: &UserAccount,
+        amount: u128
+    ) -> ExecutionResult {
+        user
+            .function_call(
+                self.ve_contract.contract.ft_transfer(to.account_id(), U128::from(amount), None),
+                DEFAULT_GAS.0,
+                1,
+            )
+    }
+
     pub fn balance_of(
         &self,
         user: &UserAccount,
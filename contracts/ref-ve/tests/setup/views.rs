@@ -2,6 +2,7 @@ use crate::*;
 use near_sdk::AccountId;
 use std::collections::HashMap;
 use near_sdk::json_types::U128;
+use near_contract_standards::storage_management::StorageBalance;
 
 
 impl Env {
@@ -33,6 +34,13 @@ impl Env {
         ).unwrap_json::<Option<Proposal>>()
     }
 
+    pub fn get_proposal_outcome(&self, proposal_id: u32) -> Option<ProposalOutcome>{
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_proposal_outcome(proposal_id)
+        ).unwrap_json::<Option<ProposalOutcome>>()
+    }
+
     pub fn get_account_info(&self, user: &UserAccount) -> Option<AccountInfo>{
         self.owner
         .view_method_call(
@@ -40,6 +48,27 @@ impl Env {
         ).unwrap_json::<Option<AccountInfo>>()
     }
 
+    pub fn storage_balance_of(&self, user: &UserAccount) -> Option<StorageBalance>{
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.storage_balance_of(user.account_id())
+        ).unwrap_json::<Option<StorageBalance>>()
+    }
+
+    pub fn get_ve_lpt_at(&self, user: &UserAccount, timestamp_sec: u32) -> u128 {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_ve_lpt_at(user.account_id(), timestamp_sec)
+        ).unwrap_json::<U128>().0
+    }
+
+    pub fn get_total_ve_lpt_at(&self, timestamp_sec: u32) -> u128 {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_total_ve_lpt_at(timestamp_sec)
+        ).unwrap_json::<U128>().0
+    }
+
     pub fn get_vote_detail(&self, user: &UserAccount) -> HashMap<u32, VoteDetail> {
         self.owner
         .view_method_call(
@@ -74,4 +103,11 @@ impl Env {
             self.ve_contract.contract.list_removed_proposal_assets(None, None)
         ).unwrap_json::<HashMap<AccountId, U128>>()
     }
+
+    pub fn get_removed_incentives(&self, user: &UserAccount) -> HashMap<AccountId, U128> {
+        self.owner
+        .view_method_call(
+            self.ve_contract.contract.get_removed_incentives(user.account_id())
+        ).unwrap_json::<HashMap<AccountId, U128>>()
+    }
 }
\ No newline at end of file
@@ -44,21 +44,21 @@ fn test_withdraw_lpt() {
     e.withdraw_lpt(&users.alice, None).assert_success();
     assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), to_yocto("200"));
     before.cur_total_ve_lpt = to_ve_token("200").into();
-    before.cur_lock_lpt = to_yocto("100").into();
+    before.lock_pools[0].cur_lock_lpt = to_yocto("100");
     assert_eq!(format!("{:?}", before), format!("{:?}", e.get_metadata()));
 
     // success bob half
     let mut before = e.get_metadata();
     e.withdraw_lpt(&users.bob, Some(to_yocto("50"))).assert_success();
     before.cur_total_ve_lpt = to_ve_token("100").into();
-    before.cur_lock_lpt = to_yocto("50").into();
+    before.lock_pools[0].cur_lock_lpt = to_yocto("50");
     assert_eq!(format!("{:?}", before), format!("{:?}", e.get_metadata()));
 
     // success bob all
     let mut before = e.get_metadata();
     e.withdraw_lpt(&users.bob, Some(to_yocto("50"))).assert_success();
     before.cur_total_ve_lpt = to_ve_token("0").into();
-    before.cur_lock_lpt = to_yocto("0").into();
+    before.lock_pools[0].cur_lock_lpt = to_yocto("0");
     assert_eq!(format!("{:?}", before), format!("{:?}", e.get_metadata()));
 
     // after vote withdraw
@@ -111,4 +111,189 @@ fn test_withdraw_lpt() {
     assert_eq!(0, e.get_proposal(0).unwrap().ve_amount_at_last_action);
     assert_eq!(HashMap::new(), e.get_vote_detail(&users.alice));
     assert_eq!(HashMap::new(), e.get_vote_detail_history(&users.alice));
-}
\ No newline at end of file
+}
+#[test]
+fn test_withdraw_lpt_and_call() {
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MIN_LOCKING_DURATION_SEC).assert_success();
+    let ve_contract_id = e.ve_contract.user_account.account_id();
+
+    // error scene 
+    // 1 : E305_STILL_IN_LOCK
+    assert_err!(e.withdraw_lpt_and_call(&users.alice, None, &ve_contract_id, "".to_string()), E305_STILL_IN_LOCK);
+
+    e.skip_time(DEFAULT_MIN_LOCKING_DURATION_SEC);
+
+    // the receiver rejects, all refunded back to the user
+    e.withdraw_lpt_and_call(&users.alice, Some(to_yocto("40")), &ve_contract_id, "invalid".to_string()).assert_success();
+    assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), to_yocto("40"));
+    assert_eq!(e.get_metadata().lock_pools[0].lostfound, 0);
+
+    // the receiver takes all, here the ve contract relocks it into another position of alice
+    let msg = format!("{{\"Lock\": {{\"duration_sec\": {}, \"lock_id\": 1, \"receiver_id\": \"{}\"}}}}", DEFAULT_MIN_LOCKING_DURATION_SEC, users.alice.account_id());
+    e.withdraw_lpt_and_call(&users.alice, None, &ve_contract_id, msg).assert_success();
+    assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), to_yocto("40"));
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert!(alice.locks.get(&0).is_none());
+    assert_eq!(alice.locks[&1].lpt_amount, to_yocto("60"));
+    assert_eq!(e.get_metadata().lock_pools[0].cur_lock_lpt, to_yocto("60"));
+}
+
+#[test]
+fn test_extend_lock() {
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC / 2).assert_success();
+    // the decaying lock is cut to unlock at the start of a period
+    let love = e.balance_of(&users.alice);
+    assert!(love <= to_ve_token("150") && love > to_ve_token("148"));
+
+    // error scene 
+    // 1 : E100_ACC_NOT_REGISTERED
+    assert_err!(e.extend_lock(&users.bob, 0, DAY_SEC), E100_ACC_NOT_REGISTERED);
+
+    // 2 : E105_ACC_NOT_LOCKED
+    assert_err!(e.extend_lock(&users.alice, 1, DAY_SEC), E105_ACC_NOT_LOCKED);
+
+    // 3 : E302_INVALID_DURATION
+    assert_err!(e.extend_lock(&users.alice, 0, 0), E302_INVALID_DURATION);
+    assert_err!(e.extend_lock(&users.alice, 0, DEFAULT_MAX_LOCKING_DURATION_SEC / 2 + DAY_SEC), E302_INVALID_DURATION);
+
+    // extend to the max locking duration
+    let unlock_timestamp = e.get_account_info(&users.alice).unwrap().locks[&0].unlock_timestamp;
+    e.extend_lock(&users.alice, 0, DEFAULT_MAX_LOCKING_DURATION_SEC / 2).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    let lock = &alice.locks[&0];
+    assert_eq!(lock.lpt_amount, to_yocto("100"));
+    assert_eq!(lock.duration_sec, DEFAULT_MAX_LOCKING_DURATION_SEC);
+    assert!(lock.unlock_timestamp >= unlock_timestamp + DEFAULT_MAX_LOCKING_DURATION_SEC as u64 / 2 * 1_000_000_000);
+    assert_eq!(alice.ve_lpt_amount, to_ve_token("200"));
+    assert_eq!(e.balance_of(&users.alice), to_ve_token("200"));
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, to_ve_token("200"));
+    assert_eq!(e.get_metadata().lock_pools[0].cur_lock_lpt, to_yocto("100"));
+
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC / 2);
+    assert_err!(e.withdraw_lpt(&users.alice, None), E305_STILL_IN_LOCK);
+}
+
+#[test]
+fn test_auto_relock() {
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.modify_decay_mode(&e.owner, DecayMode::ToZero).assert_success();
+
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC / 2).assert_success();
+    assert_eq!(e.balance_of(&users.alice), to_ve_token("150"));
+
+    // error scene 
+    // 1 : E105_ACC_NOT_LOCKED
+    assert_err!(e.set_auto_relock(&users.alice, 1, true), E105_ACC_NOT_LOCKED);
+
+    // 2 : E111_AUTO_RELOCK_UNCHANGED
+    assert_err!(e.set_auto_relock(&users.alice, 0, false), E111_AUTO_RELOCK_UNCHANGED);
+
+    // enable relocks with the max duration and stops decaying
+    e.set_auto_relock(&users.alice, 0, true).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert!(alice.locks[&0].auto_relock);
+    assert_eq!(alice.ve_lpt_amount, to_ve_token("200"));
+    assert_eq!(e.balance_of(&users.alice), to_ve_token("200"));
+
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
+    assert_eq!(e.get_account_info(&users.alice).unwrap().ve_lpt_amount, to_ve_token("200"));
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, to_ve_token("200"));
+
+    // 3 : E110_AUTO_RELOCKED
+    assert_err!(e.withdraw_lpt(&users.alice, None), E110_AUTO_RELOCKED);
+    assert_err!(e.withdraw_lpt_early(&users.alice, None), E110_AUTO_RELOCKED);
+    assert_err!(e.set_auto_relock(&users.alice, 0, true), E111_AUTO_RELOCK_UNCHANGED);
+
+    // lock into an auto relocked position always takes the max duration
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MIN_LOCKING_DURATION_SEC).assert_success();
+    assert_eq!(e.get_account_info(&users.alice).unwrap().ve_lpt_amount, to_ve_token("400"));
+    assert_eq!(e.balance_of(&users.alice), to_ve_token("400"));
+
+    // disable starts the countdown from now on
+    e.set_auto_relock(&users.alice, 0, false).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert!(!alice.locks[&0].auto_relock);
+    let duration_sec = alice.locks[&0].duration_sec;
+    assert!(duration_sec <= DEFAULT_MAX_LOCKING_DURATION_SEC && duration_sec > DEFAULT_MAX_LOCKING_DURATION_SEC - DECAY_PERIOD_SEC);
+    assert_eq!(to_sec(alice.locks[&0].unlock_timestamp) % DECAY_PERIOD_SEC, 0);
+    assert_eq!(alice.ve_lpt_amount, to_ve_token("400"));
+
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC / 2);
+    let slope = to_ve_token("400") / duration_sec as u128;
+    assert_eq!(e.get_account_info(&users.alice).unwrap().ve_lpt_amount, to_ve_token("400") - slope * (DEFAULT_MAX_LOCKING_DURATION_SEC / 2) as u128);
+    assert_err!(e.withdraw_lpt(&users.alice, None), E305_STILL_IN_LOCK);
+
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC / 2);
+    e.withdraw_lpt(&users.alice, None).assert_success();
+    assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), to_yocto("200"));
+}
+
+#[test]
+fn test_withdraw_lpt_early() {
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("200"));
+
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.mft_storage_deposit(&lpt_id(), &e.owner);
+
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    // error scene 
+    // 1 : E100_ACC_NOT_REGISTERED
+    assert_err!(e.withdraw_lpt_early(&users.charlie, None), E100_ACC_NOT_REGISTERED);
+
+    // 2 : E101_INSUFFICIENT_BALANCE
+    assert_err!(e.withdraw_lpt_early(&users.alice, Some(to_yocto("101"))), E101_INSUFFICIENT_BALANCE);
+
+    // slashed to lockers, the whole max locking duration remains, 50% slashed
+    e.withdraw_lpt_early(&users.alice, None).assert_success();
+    assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), to_yocto("150"));
+    assert_eq!(e.balance_of(&users.alice), 0);
+    assert!(e.get_account_info(&users.alice).unwrap().locks.is_empty());
+    assert_eq!(e.get_account_info(&users.bob).unwrap().lpt_amounts[&lpt_id()].0, to_yocto("150"));
+    assert_eq!(e.get_metadata().lock_pools[0].cur_lock_lpt, to_yocto("100"));
+    assert_eq!(e.get_metadata().lock_pools[0].unsettled_slashed_lpt, to_yocto("50"));
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, to_ve_token("200"));
+
+    // slashed to treasury, half of the max locking duration remains, 25% slashed
+    e.modify_early_unlock_penalty(&e.owner, DEFAULT_MAX_EARLY_UNLOCK_PENALTY_RATE, PenaltySink::Treasury { account_id: e.owner.account_id() }).assert_success();
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC / 2);
+    e.withdraw_lpt_early(&users.bob, Some(to_yocto("60"))).assert_success();
+    assert_eq!(e.mft_balance_of(&users.bob, &lpt_id()), to_yocto("145"));
+    assert_eq!(e.mft_balance_of(&e.owner, &lpt_id()), to_yocto("15"));
+    // the settled lpt comes with the veLPT and love token in proportion
+    assert_eq!(e.get_account_info(&users.bob).unwrap().lpt_amounts[&lpt_id()].0, to_yocto("90"));
+    assert_eq!(e.get_account_info(&users.bob).unwrap().ve_lpt_amount, to_ve_token("180"));
+    assert_eq!(e.balance_of(&users.bob), to_ve_token("180"));
+    assert_eq!(e.get_metadata().lock_pools[0].cur_lock_lpt, to_yocto("90"));
+    assert_eq!(e.get_metadata().lock_pools[0].unsettled_slashed_lpt, 0);
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, to_ve_token("180"));
+
+    // no slash after unlock
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC / 2);
+    e.withdraw_lpt_early(&users.bob, None).assert_success();
+    assert_eq!(e.mft_balance_of(&users.bob, &lpt_id()), to_yocto("235"));
+    assert_eq!(e.mft_balance_of(&e.owner, &lpt_id()), to_yocto("15"));
+    assert_eq!(e.get_metadata().lock_pools[0].cur_lock_lpt, 0);
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, 0);
+}
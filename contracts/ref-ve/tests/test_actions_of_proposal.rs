@@ -1,6 +1,8 @@
 mod setup;
 use crate::setup::*;
 use std::collections::HashMap;
+use near_sdk::json_types::{Base64VecU8, U64, U128};
+use near_sdk::serde_json::json;
 
 #[test]
 fn test_create_proposal(){
@@ -208,7 +210,7 @@ fn test_action_proposal(){
     assert_eq!(false, e.get_proposal(2).unwrap().is_nonsense.unwrap());
     println!("{:?}", e.get_account_info(&users.alice));
     let alice = e.get_account_info(&users.alice).unwrap();
-    assert_eq!(to_yocto("200"), alice.lpt_amount);
+    assert_eq!(to_yocto("200"), alice.lpt_amounts[&lpt_id()].0);
     assert_eq!(to_ve_token("400"), alice.ve_lpt_amount);
     assert_eq!(HashMap::from([(0, VoteDetail{
         action: Action::VoteFarm { farm_id: 0 },
@@ -456,7 +458,7 @@ fn test_action_cancel(){
     assert_eq!(to_ve_token("200"), e.get_proposal(2).unwrap().ve_amount_at_last_action);
 
     let alice = e.get_account_info(&users.alice).unwrap();
-    assert_eq!(to_yocto("100"), alice.lpt_amount);
+    assert_eq!(to_yocto("100"), alice.lpt_amounts[&lpt_id()].0);
     assert_eq!(to_ve_token("200"), alice.ve_lpt_amount);
     assert_eq!(HashMap::from([(0, VoteDetail {
         action: Action::VoteFarm { farm_id: 0 }, amount: to_ve_token("200")
@@ -511,7 +513,7 @@ fn test_action_cancel(){
     e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
     assert_err!(e.action_cancel(&users.alice, 2), E204_VOTE_CAN_NOT_CANCEL);
     let alice = e.get_account_info(&users.alice).unwrap();
-    assert_eq!(to_yocto("100"), alice.lpt_amount);
+    assert_eq!(to_yocto("100"), alice.lpt_amounts[&lpt_id()].0);
     assert_eq!(to_ve_token("200"), alice.ve_lpt_amount);
     assert_eq!(HashMap::new(), e.get_vote_detail(&users.alice));
     assert_eq!(HashMap::from([(2, VoteDetail {
@@ -520,10 +522,838 @@ fn test_action_cancel(){
 
     e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
     let alice = e.get_account_info(&users.alice).unwrap();
-    assert_eq!(to_yocto("200"), alice.lpt_amount);
+    assert_eq!(to_yocto("200"), alice.lpt_amounts[&lpt_id()].0);
     assert_eq!(to_ve_token("400"), alice.ve_lpt_amount);
     assert_eq!(HashMap::new(), e.get_vote_detail(&users.alice));
     assert_eq!(HashMap::from([(2, VoteDetail {
         action: Action::VoteReject, amount: to_ve_token("200")
     })]), e.get_vote_detail_history(&users.alice));
-}
\ No newline at end of file
+}
+#[test]
+fn test_delegate(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.charlie, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.storage_deposit(&users.bob, &users.bob, to_yocto("1"));
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.charlie, to_yocto("50"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    // error scene 
+    // 1 : E115_INVALID_DELEGATEE
+    assert_err!(e.delegate(&users.alice, &users.alice), E115_INVALID_DELEGATEE);
+
+    // 2 : E100_ACC_NOT_REGISTERED
+    assert_err!(e.delegate(&users.alice, &users.dude), E100_ACC_NOT_REGISTERED);
+    assert_err!(e.delegate(&users.dude, &users.bob), E100_ACC_NOT_REGISTERED);
+
+    // 3 : E114_NOT_DELEGATED
+    assert_err!(e.undelegate(&users.alice), E114_NOT_DELEGATED);
+
+    // success
+    e.delegate(&users.alice, &users.bob).assert_success();
+    e.delegate(&users.charlie, &users.bob).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert_eq!(Some(users.bob.account_id()), alice.delegatee);
+    assert_eq!(to_ve_token("200"), alice.delegated_ve_lpt);
+    assert_eq!(to_ve_token("300"), e.get_account_info(&users.bob).unwrap().received_ve_lpt);
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.bob.account_id()]).assert_success();
+    e.create_proposal(&users.bob, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.skip_time(DAY_SEC);
+
+    // the delegatee votes with all delegated veLPT
+    e.action_proposal(&users.bob, 0, Action::VoteApprove, None).assert_success();
+    assert_eq!(HashMap::from([(0, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("300")
+    })]), e.get_vote_detail(&users.bob));
+
+    // the delegated veLPT follows the lock changes
+    e.lock_lpt(&users.alice, to_yocto("50"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    assert_eq!(to_ve_token("300"), e.get_account_info(&users.alice).unwrap().delegated_ve_lpt);
+    assert_eq!(HashMap::from([(0, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("400")
+    })]), e.get_vote_detail(&users.bob));
+    assert_eq!(to_ve_token("400"), e.get_proposal(0).unwrap().votes[0].total_ballots);
+
+    // voting directly overrides the delegatee
+    e.action_proposal(&users.charlie, 0, Action::VoteReject, None).assert_success();
+    assert_eq!(vec![VoteInfo{
+        total_ballots: to_ve_token("300"),
+        participants: 1
+    }, VoteInfo{
+        total_ballots: to_ve_token("100"),
+        participants: 1
+    }, VoteInfo{
+        total_ballots: 0,
+        participants: 0
+    }], e.get_proposal(0).unwrap().votes);
+    assert_eq!(HashMap::from([(0, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("300")
+    })]), e.get_vote_detail(&users.bob));
+
+    // a lock change of the overriding delegator only impacts its own vote
+    e.lock_lpt(&users.charlie, to_yocto("50"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    assert_eq!(vec![VoteInfo{
+        total_ballots: to_ve_token("300"),
+        participants: 1
+    }, VoteInfo{
+        total_ballots: to_ve_token("200"),
+        participants: 1
+    }, VoteInfo{
+        total_ballots: 0,
+        participants: 0
+    }], e.get_proposal(0).unwrap().votes);
+
+    // cancel the direct vote gives it back to the delegatee
+    e.action_cancel(&users.charlie, 0).assert_success();
+    assert_eq!(HashMap::from([(0, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("500")
+    })]), e.get_vote_detail(&users.bob));
+
+    // undelegate
+    e.undelegate(&users.alice).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert_eq!(None, alice.delegatee);
+    assert_eq!(0, alice.delegated_ve_lpt);
+    assert_eq!(to_ve_token("200"), e.get_account_info(&users.bob).unwrap().received_ve_lpt);
+    assert_eq!(HashMap::from([(0, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("200")
+    })]), e.get_vote_detail(&users.bob));
+    e.action_proposal(&users.alice, 0, Action::VoteApprove, None).assert_success();
+    assert_eq!(vec![VoteInfo{
+        total_ballots: to_ve_token("500"),
+        participants: 2
+    }, VoteInfo{
+        total_ballots: 0,
+        participants: 0
+    }, VoteInfo{
+        total_ballots: 0,
+        participants: 0
+    }], e.get_proposal(0).unwrap().votes);
+}
+
+#[test]
+fn test_action_proposal_snapshot(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("300"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.charlie, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    e.create_proposal_with_voting_mode(&users.alice, ProposalKind::Common, "Snapshot".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, Some(VotingMode::Snapshot), 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "Live".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    assert_eq!(VotingMode::Snapshot, e.get_proposal(0).unwrap().voting_mode);
+    assert_eq!(VotingMode::Live, e.get_proposal(1).unwrap().voting_mode);
+
+    // locked before start counts
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.skip_time(DAY_SEC);
+    // locked after start doesn't
+    e.lock_lpt(&users.charlie, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt_position(&users.alice, 1, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    // error scene 
+    // 1 : E303_INSUFFICIENT_VE_LPT
+    assert_err!(e.action_proposal(&users.charlie, 0, Action::VoteApprove, None), E303_INSUFFICIENT_VE_LPT);
+
+    // success
+    e.action_proposal(&users.alice, 0, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.alice, 1, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VoteReject, None).assert_success();
+    assert_eq!(HashMap::from([(0, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("200")
+    }), (1, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("400")
+    })]), e.get_vote_detail(&users.alice));
+    // counted against the total at start
+    assert_eq!(to_ve_token("400"), e.get_proposal(0).unwrap().ve_amount_at_last_action);
+    assert_eq!(to_ve_token("800"), e.get_proposal(1).unwrap().ve_amount_at_last_action);
+
+    // later lock changes don't touch the snapshot votes
+    e.lock_lpt_position(&users.alice, 2, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    assert_eq!(HashMap::from([(0, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("200")
+    }), (1, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("600")
+    })]), e.get_vote_detail(&users.alice));
+    assert_eq!(vec![VoteInfo{
+        total_ballots: to_ve_token("200"),
+        participants: 1
+    }, VoteInfo{
+        total_ballots: to_ve_token("200"),
+        participants: 1
+    }, VoteInfo{
+        total_ballots: 0,
+        participants: 0
+    }], e.get_proposal(0).unwrap().votes);
+    assert_eq!(to_ve_token("400"), e.get_proposal(0).unwrap().ve_amount_at_last_action);
+}
+
+#[test]
+fn test_action_proposal_snapshot_delegation(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.charlie, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.charlie, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.delegate(&users.bob, &users.charlie).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    e.create_proposal_with_voting_mode(&users.alice, ProposalKind::Common, "Snapshot".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, Some(VotingMode::Snapshot), 1).assert_success();
+    e.skip_time(DAY_SEC);
+
+    // delegation changes after the start don't touch the snapshot
+    e.undelegate(&users.bob).assert_success();
+    e.delegate(&users.alice, &users.charlie).assert_success();
+
+    // error scene 
+    // 1 : E303_INSUFFICIENT_VE_LPT, bob's veLPT went with the delegation at the start
+    assert_err!(e.action_proposal(&users.bob, 0, Action::VoteApprove, None), E303_INSUFFICIENT_VE_LPT);
+
+    // success
+    e.action_proposal(&users.charlie, 0, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.alice, 0, Action::VoteReject, None).assert_success();
+    assert_eq!(HashMap::from([(0, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("400")
+    })]), e.get_vote_detail(&users.charlie));
+    assert_eq!(HashMap::from([(0, VoteDetail {
+        action: Action::VoteReject, amount: to_ve_token("200")
+    })]), e.get_vote_detail(&users.alice));
+    assert_eq!(to_ve_token("600"), e.get_proposal(0).unwrap().ve_amount_at_last_action);
+}
+
+#[test]
+fn test_proposal_outcome(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.charlie, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.charlie, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+
+    let thresholds = ProposalThresholds {
+        farming_reward: ProposalThreshold { quorum: 0, approval: 0 },
+        poll: ProposalThreshold { quorum: 5000, approval: 5000 },
+        common: ProposalThreshold { quorum: 5000, approval: 6000 },
+        executable: ProposalThreshold { quorum: 6000, approval: 6000 },
+    };
+
+    // error scene 
+    // 1 : E002_NOT_ALLOWED
+    assert_err!(e.modify_proposal_thresholds(&users.alice, thresholds.clone()), E002_NOT_ALLOWED);
+    // 2 : E311_INVALID_PROPOSAL_THRESHOLD
+    assert_err!(e.modify_proposal_thresholds(&e.owner, ProposalThresholds {
+        common: ProposalThreshold { quorum: 5000, approval: 10000 },
+        ..thresholds.clone()
+    }), E311_INVALID_PROPOSAL_THRESHOLD);
+
+    // success
+    e.modify_proposal_thresholds(&e.owner, thresholds.clone()).assert_success();
+    assert_eq!(thresholds, e.get_config().proposal_thresholds);
+
+    let start_at = to_sec(e.current_time() + DAY_TS);
+    for description in ["Rejected", "QuorumNotMet"] {
+        e.create_proposal(&users.alice, ProposalKind::Common, description.to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    }
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Passed".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Tie".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    assert_eq!(thresholds.common, e.get_proposal(0).unwrap().threshold);
+    assert_eq!(thresholds.poll, e.get_proposal(2).unwrap().threshold);
+
+    // later config changes don't touch the created proposals
+    e.modify_proposal_thresholds(&e.owner, ProposalThresholds::default()).assert_success();
+    assert_eq!(thresholds.common, e.get_proposal(0).unwrap().threshold);
+
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VoteReject, None).assert_success();
+    e.action_proposal(&users.alice, 1, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.alice, 2, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.bob, 2, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.charlie, 2, Action::VotePoll { poll_id: 1 }, None).assert_success();
+    e.action_proposal(&users.alice, 3, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    e.action_proposal(&users.bob, 3, Action::VotePoll { poll_id: 1 }, None).assert_success();
+
+    // no outcome until expired, nor for an unknown proposal
+    assert_eq!(None, e.get_proposal_outcome(0));
+    assert_eq!(None, e.get_proposal_outcome(4));
+    assert_eq!(None, e.get_proposal(0).unwrap().outcome);
+
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    assert_eq!(Some(ProposalOutcome::Rejected), e.get_proposal_outcome(0));
+    assert_eq!(Some(ProposalOutcome::QuorumNotMet), e.get_proposal_outcome(1));
+    assert_eq!(Some(ProposalOutcome::Passed), e.get_proposal_outcome(2));
+    assert_eq!(Some(ProposalOutcome::Passed), e.get_proposal(2).unwrap().outcome);
+    // a tie for the leading option is rejected
+    assert_eq!(Some(ProposalOutcome::Rejected), e.get_proposal_outcome(3));
+    assert_eq!(None, e.get_proposal_outcome(4));
+}
+
+#[test]
+fn test_execute_proposal(){
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    e.ft_mint(&tokens.nref, &e.ve_contract.user_account, to_yocto("100"));
+    e.ft_storage_deposit(&users.bob, &tokens.nref);
+
+    let transfer_to = |receiver: &UserAccount| FunctionCallAction {
+        receiver_id: tokens.nref.account_id(),
+        method_name: "ft_transfer".to_string(),
+        args: Base64VecU8(json!({
+            "receiver_id": receiver.account_id(),
+            "amount": to_yocto("10").to_string(),
+        }).to_string().into_bytes()),
+        deposit: 1.into(),
+        gas: U64(20_000_000_000_000),
+    };
+
+    assert_eq!(DEFAULT_EXECUTION_TIMELOCK_SEC, e.get_config().execution_timelock_sec);
+    assert_err!(e.modify_execution_timelock_sec(&users.alice, DAY_SEC), E002_NOT_ALLOWED);
+    e.modify_execution_timelock_sec(&e.owner, DAY_SEC).assert_success();
+    assert_eq!(DAY_SEC, e.get_config().execution_timelock_sec);
+
+    let start_at = to_sec(e.current_time() + DAY_TS);
+
+    // error scene 
+    // 1 : E410_INVALID_EXECUTION_ACTION
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![] }, "Executable".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1), E410_INVALID_EXECUTION_ACTION);
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![FunctionCallAction {
+        receiver_id: e.lptoken_contract.account_id(),
+        ..transfer_to(&users.bob)
+    }] }, "Executable".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1), E410_INVALID_EXECUTION_ACTION);
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![FunctionCallAction {
+        receiver_id: e.ve_contract.account_id(),
+        ..transfer_to(&users.bob)
+    }] }, "Executable".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1), E410_INVALID_EXECUTION_ACTION);
+
+    // success
+    e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![transfer_to(&users.bob), transfer_to(&users.bob)] }, "Executable".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![transfer_to(&users.bob), transfer_to(&users.dude)] }, "Executable".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![transfer_to(&users.bob)] }, "Executable".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    assert_eq!(ProposalThresholds::default().executable, e.get_proposal(0).unwrap().threshold);
+    assert_eq!(DEFAULT_EXECUTABLE_QUORUM, e.get_proposal(0).unwrap().threshold.quorum);
+    let execution = e.get_proposal(0).unwrap().execution.unwrap();
+    assert_eq!(ExecutionStatus::Pending, execution.status);
+    assert_eq!(to_nano(start_at + DEFAULT_MIN_VOTING_DURATION_SEC + DAY_SEC), execution.executable_at);
+    assert!(e.get_proposal(3).unwrap().execution.is_none());
+
+    // the tokens held for users can't be called, even after removed from the whitelist
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.wnear.account_id()]).assert_success();
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![FunctionCallAction {
+        receiver_id: tokens.wnear.account_id(),
+        ..transfer_to(&users.bob)
+    }] }, "Executable".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1), E410_INVALID_EXECUTION_ACTION);
+    e.ft_mint(&tokens.wnear, &users.dude, to_yocto("10"));
+    e.deposit_reward(&tokens.wnear, &users.dude, to_yocto("10"), 3, 0).assert_success();
+    e.remove_whitelisted_incentive_tokens(&e.owner, vec![tokens.wnear.account_id()]).assert_success();
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![FunctionCallAction {
+        receiver_id: tokens.wnear.account_id(),
+        ..transfer_to(&users.bob)
+    }] }, "Executable".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1), E410_INVALID_EXECUTION_ACTION);
+    // 2 : E419_EXECUTION_GAS_EXCEEDED
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![transfer_to(&users.bob); 14] }, "Executable".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1), E419_EXECUTION_GAS_EXCEEDED);
+
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.alice, 1, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.alice, 2, Action::VoteReject, None).assert_success();
+    e.action_proposal(&users.alice, 3, Action::VoteApprove, None).assert_success();
+
+    // 3 : E408_PROPOSAL_NOT_PASSED
+    assert_err!(e.execute_proposal(&users.charlie, 0), E408_PROPOSAL_NOT_PASSED);
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    assert_err!(e.execute_proposal(&users.charlie, 2), E408_PROPOSAL_NOT_PASSED);
+    // 4 : E407_NOT_EXECUTABLE
+    assert_err!(e.execute_proposal(&users.charlie, 3), E407_NOT_EXECUTABLE);
+    // 5 : E409_TIMELOCK_NOT_ELAPSED
+    assert_err!(e.execute_proposal(&users.charlie, 0), E409_TIMELOCK_NOT_ELAPSED);
+
+    // 6 : E421_INSUFFICIENT_EXECUTION_TREASURY
+    e.skip_time(DAY_SEC);
+    assert_err!(e.execute_proposal(&users.charlie, 0), E421_INSUFFICIENT_EXECUTION_TREASURY);
+
+    // success
+    e.deposit_execution_treasury(&users.charlie, 3).assert_success();
+    assert_eq!(3, e.get_metadata().execution_treasury.0);
+    e.execute_proposal(&users.charlie, 0).assert_success();
+    assert_eq!(ExecutionStatus::Executed, e.get_proposal(0).unwrap().execution.unwrap().status);
+    assert_eq!(to_yocto("20"), e.ft_balance_of(&tokens.nref, &users.bob));
+    assert_eq!(to_yocto("80"), e.ft_balance_of(&tokens.nref, &e.ve_contract.user_account));
+    assert_eq!(1, e.get_metadata().execution_treasury.0);
+    assert_err!(e.execute_proposal(&users.charlie, 0), E407_NOT_EXECUTABLE);
+
+    // the failed one can't be executed again, the deposit of the failed action goes back to the treasury
+    assert_err!(e.execute_proposal(&users.charlie, 1), E421_INSUFFICIENT_EXECUTION_TREASURY);
+    e.deposit_execution_treasury(&users.charlie, 1).assert_success();
+    e.execute_proposal(&users.charlie, 1).assert_success();
+    assert_eq!(ExecutionStatus::Failed, e.get_proposal(1).unwrap().execution.unwrap().status);
+    assert_eq!(to_yocto("30"), e.ft_balance_of(&tokens.nref, &users.bob));
+    assert_eq!(1, e.get_metadata().execution_treasury.0);
+    assert_err!(e.execute_proposal(&users.charlie, 1), E407_NOT_EXECUTABLE);
+
+    // only the owner takes back the treasury
+    assert_err!(e.withdraw_execution_treasury(&users.charlie, &users.charlie, 1), E002_NOT_ALLOWED);
+    assert_err!(e.withdraw_execution_treasury(&e.owner, &users.charlie, 2), E421_INSUFFICIENT_EXECUTION_TREASURY);
+    e.withdraw_execution_treasury(&e.owner, &users.charlie, 1).assert_success();
+    assert_eq!(0, e.get_metadata().execution_treasury.0);
+}
+
+#[test]
+fn test_config_change_proposal(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+
+    let start_at = to_sec(e.current_time() + DAY_TS);
+
+    // error scene 
+    // 1 : E311_INVALID_PROPOSAL_THRESHOLD
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::ConfigChange {
+        config: Box::new(PartialConfig {
+            proposal_thresholds: Some(ProposalThresholds {
+                common: ProposalThreshold { quorum: 0, approval: 10000 },
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        whitelists: Default::default(),
+    }, "ConfigChange".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1), E311_INVALID_PROPOSAL_THRESHOLD);
+
+    // success
+    e.create_proposal(&users.alice, ProposalKind::ConfigChange {
+        config: Box::new(PartialConfig {
+            max_early_unlock_penalty_rate: Some(3000),
+            execution_timelock_sec: Some(DAY_SEC),
+            ..Default::default()
+        }),
+        whitelists: WhitelistDeltas {
+            extend_whitelisted_accounts: vec![users.bob.account_id()],
+            remove_whitelisted_accounts: vec![users.alice.account_id()],
+            ..Default::default()
+        },
+    }, "ConfigChange".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VoteApprove, None).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    assert_err!(e.execute_proposal(&users.charlie, 0), E409_TIMELOCK_NOT_ELAPSED);
+    e.skip_time(DEFAULT_EXECUTION_TIMELOCK_SEC);
+    e.execute_proposal(&users.charlie, 0).assert_success();
+    assert_eq!(ExecutionStatus::Executed, e.get_proposal(0).unwrap().execution.unwrap().status);
+
+    let config = e.get_config();
+    assert_eq!(3000, config.max_early_unlock_penalty_rate);
+    assert_eq!(DAY_SEC, config.execution_timelock_sec);
+    assert_eq!(DEFAULT_MAX_LOCKING_DURATION_SEC, config.max_locking_duration_sec);
+    assert_eq!(vec![users.bob.account_id()], e.get_metadata().whitelisted_accounts);
+    assert_err!(e.execute_proposal(&users.charlie, 0), E407_NOT_EXECUTABLE);
+}
+
+#[test]
+fn test_poll_modes(){
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.charlie, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.charlie, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+
+    let start_at = to_sec(e.current_time() + DAY_TS);
+    let poll = || ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string(), "topic3".to_string()] };
+
+    // error scene 
+    // 1 : E412_INVALID_POLL_MODE
+    assert_err!(e.create_proposal_with_modes(&users.dude, ProposalKind::Common, "Common".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, None, Some(PollMode::RankedChoice), 1), E412_INVALID_POLL_MODE);
+    assert_err!(e.create_proposal_with_modes(&users.dude, poll(), "MultiSelect".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, None, Some(PollMode::MultiSelect { max_choices: 0 }), 1), E412_INVALID_POLL_MODE);
+    assert_err!(e.create_proposal_with_modes(&users.dude, poll(), "MultiSelect".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, None, Some(PollMode::MultiSelect { max_choices: 4 }), 1), E412_INVALID_POLL_MODE);
+    let options = |count: usize| ProposalKind::Poll { options: (0..count).map(|i| format!("topic{}", i + 1)).collect() };
+    assert_err!(e.create_proposal_with_modes(&users.dude, options(MAX_RANKED_CHOICE_OPTIONS + 1), "RankedChoice".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, None, Some(PollMode::RankedChoice), 1), E412_INVALID_POLL_MODE);
+
+    // success
+    e.create_proposal_with_modes(&users.dude, poll(), "MultiSelect".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, None, Some(PollMode::MultiSelect { max_choices: 2 }), 1).assert_success();
+    e.create_proposal_with_modes(&users.dude, poll(), "RankedChoice".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, None, Some(PollMode::RankedChoice), 1).assert_success();
+    e.create_proposal(&users.dude, poll(), "Single".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal_with_modes(&users.dude, options(MAX_RANKED_CHOICE_OPTIONS), "RankedChoice".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, None, Some(PollMode::RankedChoice), 1).assert_success();
+    assert_eq!(PollMode::MultiSelect { max_choices: 2 }, e.get_proposal(0).unwrap().poll_mode);
+    assert_eq!(PollMode::Single, e.get_proposal(2).unwrap().poll_mode);
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("300"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("300"), 0, 0).assert_success();
+    e.skip_time(DAY_SEC);
+
+    // 2 : E201_INVALID_VOTE
+    assert_err!(e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 0, Action::VotePollMulti { poll_ids: vec![] }, None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 0, Action::VotePollMulti { poll_ids: vec![0, 1, 2] }, None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 0, Action::VotePollMulti { poll_ids: vec![1, 1] }, None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 0, Action::VotePollRanked { poll_ids: vec![0] }, None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 1, Action::VotePollMulti { poll_ids: vec![0] }, None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 1, Action::VotePollRanked { poll_ids: vec![0, 3] }, None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 2, Action::VotePollMulti { poll_ids: vec![0] }, None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 3, Action::VotePollRanked { poll_ids: (0..MAX_RANKING_LEN + 1).collect() }, None), E201_INVALID_VOTE);
+
+    // each selected option gets the full veLPT, each voter counted once
+    e.action_proposal(&users.alice, 0, Action::VotePollMulti { poll_ids: vec![0, 1] }, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VotePollMulti { poll_ids: vec![1] }, None).assert_success();
+    e.action_proposal(&users.charlie, 0, Action::VotePollMulti { poll_ids: vec![2] }, None).assert_success();
+    assert_eq!(vec![VoteInfo{
+        total_ballots: to_ve_token("200"),
+        participants: 1
+    }, VoteInfo{
+        total_ballots: to_ve_token("400"),
+        participants: 2
+    }, VoteInfo{
+        total_ballots: to_ve_token("200"),
+        participants: 1
+    }], e.get_proposal(0).unwrap().votes);
+    assert_eq!(to_ve_token("600"), e.get_proposal(0).unwrap().voted_amount);
+    assert_eq!(3, e.get_proposal(0).unwrap().participants);
+
+    // ranked ballots count on their first choice until expired
+    e.action_proposal(&users.alice, 1, Action::VotePollRanked { poll_ids: vec![0, 1, 2] }, None).assert_success();
+    e.action_proposal(&users.bob, 1, Action::VotePollRanked { poll_ids: vec![1, 0] }, None).assert_success();
+    e.action_proposal(&users.charlie, 1, Action::VotePollRanked { poll_ids: vec![2, 1] }, None).assert_success();
+    e.action_proposal(&users.alice, 3, Action::VotePollRanked { poll_ids: vec![0] }, None).assert_success();
+    e.action_proposal(&users.bob, 3, Action::VotePollRanked { poll_ids: vec![1] }, None).assert_success();
+    assert_eq!(vec![to_ve_token("200"), to_ve_token("200"), to_ve_token("200")],
+        e.get_proposal(1).unwrap().votes.iter().map(|v| v.total_ballots).collect::<Vec<_>>());
+    assert_eq!(None, e.get_proposal(1).unwrap().ranked_choice_result);
+    assert!(!e.settle_proposal(&users.eve, 1).unwrap_json::<bool>());
+
+    // tied in the first round, topic3 is eliminated and its ballot moves to topic2
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    assert_eq!(Some(RankedChoiceResult {
+        winner: Some(1),
+        rounds: vec![
+            vec![U128(to_ve_token("200")), U128(to_ve_token("200")), U128(to_ve_token("200"))],
+            vec![U128(to_ve_token("200")), U128(to_ve_token("400")), U128(0)],
+        ],
+    }), e.get_proposal(1).unwrap().ranked_choice_result);
+    assert_eq!(Some(ProposalOutcome::Passed), e.get_proposal_outcome(0));
+    assert_eq!(Some(ProposalOutcome::Passed), e.get_proposal_outcome(1));
+    // a tie in the final round picks no winner, the same as the other modes
+    let result = e.get_proposal(3).unwrap().ranked_choice_result.unwrap();
+    assert_eq!(None, result.winner);
+    assert_eq!(MAX_RANKED_CHOICE_OPTIONS - 1, result.rounds.len());
+    assert_eq!(vec![U128(to_ve_token("200")), U128(to_ve_token("200"))], result.rounds.last().unwrap()[..2].to_vec());
+    assert_eq!(Some(ProposalOutcome::Rejected), e.get_proposal_outcome(3));
+    assert!(e.settle_proposal(&users.eve, 1).unwrap_json::<bool>());
+    assert!(!e.settle_proposal(&users.eve, 1).unwrap_json::<bool>());
+
+    // the incentive is shared pro rata to the veLPT of each voter, however many options selected
+    for user in [&users.alice, &users.bob, &users.charlie] {
+        assert_eq!(to_yocto("100"), e.get_unclaimed_rewards(user).get(&tokens.nref.account_id()).unwrap().0);
+    }
+}
+
+#[test]
+fn test_permissionless_proposal(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("10"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("110"));
+    e.mft_mint(&lpt_inner_id(), &users.charlie, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("10"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.charlie, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+
+    let start_at = to_sec(e.current_time() + DAY_TS);
+    let policy = PermissionlessProposalPolicy {
+        min_ve_lpt: to_ve_token("100"),
+        bond_asset: BondAsset::Near,
+        bond_amount: to_yocto("10"),
+        forfeit_sink: BondForfeitSink::Treasury { account_id: users.eve.account_id() },
+    };
+
+    // error scene 
+    // 1 : E002_NOT_ALLOWED
+    assert_err!(e.create_proposal(&users.bob, ProposalKind::Common, "Common".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, to_yocto("10")), E002_NOT_ALLOWED);
+    assert_err!(e.modify_permissionless_proposal_policy(&users.bob, Some(policy.clone())), E002_NOT_ALLOWED);
+    // 2 : E312_INVALID_PROPOSAL_BOND
+    assert_err!(e.modify_permissionless_proposal_policy(&e.owner, Some(PermissionlessProposalPolicy { bond_amount: 0, ..policy.clone() })), E312_INVALID_PROPOSAL_BOND);
+
+    e.modify_permissionless_proposal_policy(&e.owner, Some(policy.clone())).assert_success();
+    assert_eq!(Some(policy.clone()), e.get_config().permissionless_proposal);
+
+    // 3 : E303_INSUFFICIENT_VE_LPT
+    assert_err!(e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, to_yocto("10")), E303_INSUFFICIENT_VE_LPT);
+    // 4 : E413_NOT_PERMISSIONLESS_KIND
+    assert_err!(e.create_proposal(&users.bob, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, to_yocto("11")), E413_NOT_PERMISSIONLESS_KIND);
+    assert_err!(e.create_proposal(&users.bob, ProposalKind::ConfigChange {
+        config: Box::new(PartialConfig { max_early_unlock_penalty_rate: Some(0), ..Default::default() }),
+        whitelists: Default::default(),
+    }, "ConfigChange".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, to_yocto("11")), E413_NOT_PERMISSIONLESS_KIND);
+    // 5 : E312_INVALID_PROPOSAL_BOND
+    assert_err!(e.create_proposal(&users.bob, ProposalKind::Common, "Common".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, to_yocto("1")), E312_INVALID_PROPOSAL_BOND);
+    assert_err!(e.create_proposal_with_lpt_bond(&users.bob, to_yocto("10"), ProposalKind::Common, "Common".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC), E312_INVALID_PROPOSAL_BOND);
+    // 6 : E102_INSUFFICIENT_STORAGE, nothing left beyond the bond for the storage
+    assert_err!(e.create_proposal(&users.bob, ProposalKind::Common, "Common".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, to_yocto("10")), E102_INSUFFICIENT_STORAGE);
+
+    // success, the NEAR beyond the bond and the storage is refunded
+    let bob_balance = users.bob.account().unwrap().amount;
+    e.create_proposal(&users.bob, ProposalKind::Common, "Refunded".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, to_yocto("11")).assert_success();
+    assert!(users.bob.account().unwrap().amount > bob_balance - to_yocto("11"));
+    e.create_proposal(&users.bob, ProposalKind::Common, "Forfeited".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, to_yocto("11")).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Common, "Whitelisted".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    let bond = e.get_proposal(0).unwrap().bond.unwrap();
+    assert_eq!(to_yocto("10"), bond.amount);
+    assert_eq!(BondStatus::Held, bond.status);
+    assert!(e.get_proposal(2).unwrap().bond.is_none());
+    e.skip_time(DAY_SEC);
+
+    // 7 : E201_INVALID_VOTE, only the bonded proposal can be voted as nonsense
+    assert_err!(e.action_proposal(&users.charlie, 2, Action::VoteNonsense, None), E201_INVALID_VOTE);
+    e.action_proposal(&users.bob, 0, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.charlie, 0, Action::VoteNonsense, None).assert_success();
+    e.action_proposal(&users.alice, 1, Action::VoteReject, None).assert_success();
+    e.action_proposal(&users.charlie, 1, Action::VoteNonsense, None).assert_success();
+
+    // 8 : E414_BOND_NOT_CLAIMABLE
+    assert_err!(e.claim_proposal_bond(&users.dude, 0), E414_BOND_NOT_CLAIMABLE);
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    assert_err!(e.claim_proposal_bond(&users.dude, 2), E414_BOND_NOT_CLAIMABLE);
+
+    // not voted as nonsense, refunded to the proposer
+    assert_eq!(Some(ProposalOutcome::Passed), e.get_proposal_outcome(0));
+    let bob_balance = users.bob.account().unwrap().amount;
+    e.claim_proposal_bond(&users.dude, 0).assert_success();
+    assert_eq!(bob_balance + to_yocto("10"), users.bob.account().unwrap().amount);
+    assert_eq!(BondStatus::Refunded, e.get_proposal(0).unwrap().bond.unwrap().status);
+    assert_err!(e.claim_proposal_bond(&users.dude, 0), E414_BOND_NOT_CLAIMABLE);
+
+    // voted as nonsense, forfeited to the treasury
+    assert_eq!(Some(ProposalOutcome::Nonsense), e.get_proposal_outcome(1));
+    let eve_balance = users.eve.account().unwrap().amount;
+    e.claim_proposal_bond(&users.dude, 1).assert_success();
+    assert_eq!(eve_balance + to_yocto("10"), users.eve.account().unwrap().amount);
+    assert_eq!(BondStatus::Forfeited, e.get_proposal(1).unwrap().bond.unwrap().status);
+
+    // lpt bond shared among the nonsense voters, the lpt beyond the bond is refunded
+    e.modify_permissionless_proposal_policy(&e.owner, Some(PermissionlessProposalPolicy {
+        bond_asset: BondAsset::Lpt { token_id: lpt_id() },
+        bond_amount: to_yocto("4"),
+        forfeit_sink: BondForfeitSink::NonsenseVoters,
+        ..policy
+    })).assert_success();
+    // 9 : E102_INSUFFICIENT_STORAGE, the storage of a proposal created by transfer call is paid from the available storage
+    assert_err!(e.create_proposal_with_lpt_bond(&users.bob, to_yocto("10"), ProposalKind::Common, "NonsenseVoters".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC), E102_INSUFFICIENT_STORAGE);
+    e.storage_deposit(&users.bob, &users.bob, to_yocto("0.1")).assert_success();
+    assert_eq!(to_yocto("0.1"), e.storage_balance_of(&users.bob).unwrap().available.0);
+    e.create_proposal_with_lpt_bond(&users.bob, to_yocto("10"), ProposalKind::Common, "NonsenseVoters".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC).assert_success();
+    assert_eq!(to_yocto("6"), e.mft_balance_of(&users.bob, &lpt_id()));
+    assert_eq!(to_yocto("4"), e.get_metadata().lock_pools[0].bonded_lpt);
+    let storage_available = e.storage_balance_of(&users.bob).unwrap().available.0;
+    assert!(storage_available < to_yocto("0.1"));
+
+    // the storage grown by updating a bonded proposal is paid the same
+    e.update_proposal(&users.bob, 3, Some("a".repeat(1024)), None, None).assert_success();
+    assert!(e.storage_balance_of(&users.bob).unwrap().available.0 < storage_available);
+    e.storage_withdraw(&users.bob, 1).assert_success();
+    assert_eq!(0, e.storage_balance_of(&users.bob).unwrap().available.0);
+    // 10 : E102_INSUFFICIENT_STORAGE
+    assert_err!(e.update_proposal(&users.bob, 3, Some("a".repeat(2048)), None, None), E102_INSUFFICIENT_STORAGE);
+    e.update_proposal(&users.bob, 3, Some("NonsenseVoters".to_string()), None, None).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 3, Action::VoteNonsense, None).assert_success();
+    e.action_proposal(&users.charlie, 3, Action::VoteNonsense, None).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+
+    assert_err!(e.claim_proposal_bond(&users.bob, 3), E414_BOND_NOT_CLAIMABLE);
+    e.claim_proposal_bond(&users.charlie, 3).assert_success();
+    assert_err!(e.claim_proposal_bond(&users.charlie, 3), E414_BOND_NOT_CLAIMABLE);
+    e.claim_proposal_bond(&users.alice, 3).assert_success();
+    assert_eq!(to_yocto("4") * 200 / 220, e.mft_balance_of(&users.charlie, &lpt_id()));
+    assert_eq!(to_yocto("4") * 20 / 220, e.mft_balance_of(&users.alice, &lpt_id()));
+    let bond = e.get_proposal(3).unwrap().bond.unwrap();
+    assert_eq!(BondStatus::Forfeited, bond.status);
+    assert_eq!(to_yocto("4") * 200 / 220 + to_yocto("4") * 20 / 220, bond.claimed_amount);
+    assert_eq!(to_yocto("4") - bond.claimed_amount, e.get_metadata().lock_pools[0].bonded_lpt);
+}
+
+#[test]
+fn test_update_proposal(){
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    let start_at = to_sec(e.current_time() + DAY_TS);
+    let farms = |farm_list: Vec<&str>| ProposalKind::FarmingReward { farm_list: farm_list.into_iter().map(|farm| farm.to_string()).collect(), total_reward: 20000 };
+    e.create_proposal(&users.alice, farms(vec!["noct.near|nref.near&2657", "nusdt.near|nusdc.near|ndai.near&1910", "usn.near|nref.near&3020"]), "FarmingReward".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+
+    // error scene 
+    // 1 : E002_NOT_ALLOWED
+    assert_err!(e.update_proposal(&users.bob, 1, Some("Poll".to_string()), None, None), E002_NOT_ALLOWED);
+    // 2 : E208_DESCRIPTION_TOO_LONG
+    assert_err!(e.update_proposal(&users.alice, 1, Some("a".repeat(2049)), None, None), E208_DESCRIPTION_TOO_LONG);
+    // 3 : E416_PROPOSAL_KIND_CHANGED
+    assert_err!(e.update_proposal(&users.alice, 1, None, Some(ProposalKind::Common), None), E416_PROPOSAL_KIND_CHANGED);
+    // 4 : E302_INVALID_DURATION
+    assert_err!(e.update_proposal(&users.alice, 1, None, None, Some(DEFAULT_MAX_VOTING_DURATION_SEC + 1)), E302_INVALID_DURATION);
+    // 5 : E203_INVALID_INCENTIVE_TOKEN, the farm of the deposited incentive no longer takes it
+    assert_err!(e.update_proposal(&users.alice, 0, None, Some(farms(vec!["noct.near|nusdt.near&2657", "nusdt.near|nusdc.near|ndai.near&1910"])), None), E203_INVALID_INCENTIVE_TOKEN);
+    // 6 : E207_INVALID_INCENTIVE_KEY, the farm of the deposited incentive is gone
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 2).assert_success();
+    assert_err!(e.update_proposal(&users.alice, 0, None, Some(farms(vec!["noct.near|nref.near&2657", "nusdt.near|nusdc.near|ndai.near&1910"])), None), E207_INVALID_INCENTIVE_KEY);
+    // 7 : E420_INCENTIVE_FARM_CHANGED, the farm of the deposited incentive must stay the same even if it still takes the token
+    assert_err!(e.update_proposal(&users.alice, 0, None, Some(farms(vec!["nref.near|nusdt.near&2657", "nusdt.near|nusdc.near|ndai.near&1910", "usn.near|nref.near&3020"])), None), E420_INCENTIVE_FARM_CHANGED);
+
+    // success, only the farms without incentives change
+    e.update_proposal(&users.alice, 0, Some("FarmingReward v2".to_string()), Some(farms(vec!["noct.near|nref.near&2657", "nusdt.near|ndai.near&1910", "usn.near|nref.near&3020", "ndai.near|nusdt.near&3021"])), Some(DEFAULT_MIN_VOTING_DURATION_SEC * 2)).assert_success();
+    let proposal = e.get_proposal(0).unwrap();
+    assert_eq!("FarmingReward v2", proposal.description);
+    assert_eq!(4, proposal.votes.len());
+    assert_eq!(proposal.start_at + to_nano(DEFAULT_MIN_VOTING_DURATION_SEC * 2), proposal.end_at);
+    assert_eq!(vec![to_yocto("100")], proposal.incentive.get(&0).unwrap().incentive_amounts);
+    assert_eq!(vec![to_yocto("100")], proposal.incentive.get(&2).unwrap().incentive_amounts);
+    e.update_proposal(&users.alice, 1, None, Some(ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string(), "topic3".to_string()] }), None).assert_success();
+    assert_eq!(3, e.get_proposal(1).unwrap().votes.len());
+    assert_eq!("Poll", e.get_proposal(1).unwrap().description);
+
+    // 8 : E415_PROPOSAL_NOT_UPDATABLE
+    e.skip_time(DAY_SEC);
+    assert_err!(e.update_proposal(&users.alice, 1, Some("Poll".to_string()), None, None), E415_PROPOSAL_NOT_UPDATABLE);
+}
+
+#[test]
+fn test_veto_proposal(){
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("100"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+
+    // a bonded proposal and two executions, all vetoed below
+    e.mft_mint(&lpt_inner_id(), &users.charlie, to_yocto("100"));
+    e.lock_lpt(&users.charlie, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.modify_permissionless_proposal_policy(&e.owner, Some(PermissionlessProposalPolicy {
+        min_ve_lpt: to_ve_token("100"),
+        bond_asset: BondAsset::Near,
+        bond_amount: to_yocto("10"),
+        forfeit_sink: BondForfeitSink::Treasury { account_id: users.eve.account_id() },
+    })).assert_success();
+    e.create_proposal(&users.charlie, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, to_yocto("11")).assert_success();
+    let ping = FunctionCallAction {
+        receiver_id: users.dude.account_id(),
+        method_name: "ping".to_string(),
+        args: Base64VecU8(vec![]),
+        deposit: 0.into(),
+        gas: U64(20_000_000_000_000),
+    };
+    e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![ping.clone()] }, "Executable".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Executable { actions: vec![ping] }, "Executable".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+
+    // error scene 
+    // 1 : E002_NOT_ALLOWED, only the owner manages guardians
+    assert_err!(e.extend_guardians(&users.alice, vec![&users.bob], 1), E002_NOT_ALLOWED);
+    e.extend_guardians(&e.owner, vec![&users.bob], 1).assert_success();
+    assert_eq!(vec![users.bob.account_id()], e.get_metadata().guardians);
+    // 2 : E417_PROPOSAL_NOT_VETOABLE, the proposer can still remove it in WarmUp
+    assert_err!(e.veto_proposal(&users.bob, 0), E417_PROPOSAL_NOT_VETOABLE);
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 0 }, None).assert_success();
+    // 3 : E002_NOT_ALLOWED, an operator is not a guardian
+    e.extend_operators(&e.owner, vec![&users.operator], 1).assert_success();
+    assert_err!(e.veto_proposal(&users.operator, 0), E002_NOT_ALLOWED);
+
+    // success
+    e.veto_proposal(&users.bob, 0).assert_success();
+    let proposal = e.get_proposal(0).unwrap();
+    assert_eq!(Some(ProposalStatus::Vetoed), proposal.status);
+    assert_eq!(Some(users.bob.account_id()), proposal.vetoed_by);
+    assert!(proposal.incentive.is_empty());
+    assert_eq!(None, e.get_proposal_outcome(0));
+    assert_eq!(to_yocto("100"), e.get_removed_incentives(&users.dude).get(&tokens.nref.account_id()).unwrap().0);
+
+    // 4 : E205_NOT_VOTABLE
+    assert_err!(e.action_proposal(&users.alice, 0, Action::VotePoll { poll_id: 1 }, None), E205_NOT_VOTABLE);
+    // 5 : E406_EXPIRED_PROPOSAL, no more incentives
+    assert_err!(e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0), E406_EXPIRED_PROPOSAL);
+    // 6 : E417_PROPOSAL_NOT_VETOABLE
+    assert_err!(e.veto_proposal(&users.bob, 0), E417_PROPOSAL_NOT_VETOABLE);
+
+    // the vote is freed without rewards
+    assert_eq!(HashMap::new(), e.get_vote_detail(&users.alice));
+    assert_eq!(HashMap::new(), e.get_unclaimed_rewards(&users.alice));
+    e.claim_and_withdraw_all(&users.alice).assert_success();
+    assert_eq!(HashMap::from([(0, VoteDetail{
+        action: Action::VotePoll { poll_id: 0 }, amount: to_ve_token("100")
+    })]), e.get_vote_detail_history(&users.alice));
+    assert!(e.get_account_info(&users.alice).unwrap().rewards.is_empty());
+
+    // the bond is forfeited
+    e.veto_proposal(&users.bob, 1).assert_success();
+    let eve_balance = users.eve.account().unwrap().amount;
+    e.claim_proposal_bond(&users.dude, 1).assert_success();
+    assert_eq!(eve_balance + to_yocto("10"), users.eve.account().unwrap().amount);
+    assert_eq!(BondStatus::Forfeited, e.get_proposal(1).unwrap().bond.unwrap().status);
+
+    e.action_proposal(&users.alice, 2, Action::VoteApprove, None).assert_success();
+    e.action_proposal(&users.alice, 3, Action::VoteApprove, None).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    assert_eq!(Some(ProposalStatus::Vetoed), e.get_proposal(0).unwrap().status);
+
+    // a passed execution is vetoed during its timelock, keeping its outcome
+    e.veto_proposal(&users.bob, 2).assert_success();
+    let proposal = e.get_proposal(2).unwrap();
+    assert_eq!(Some(ProposalStatus::Expired), proposal.status);
+    assert_eq!(None, proposal.vetoed_by);
+    assert_eq!(ExecutionStatus::Vetoed, proposal.execution.unwrap().status);
+    assert_eq!(Some(ProposalOutcome::Passed), e.get_proposal_outcome(2));
+    // 7 : E417_PROPOSAL_NOT_VETOABLE, vetoed already or timelock elapsed
+    assert_err!(e.veto_proposal(&users.bob, 2), E417_PROPOSAL_NOT_VETOABLE);
+    e.skip_time(DEFAULT_EXECUTION_TIMELOCK_SEC);
+    assert_err!(e.veto_proposal(&users.bob, 3), E417_PROPOSAL_NOT_VETOABLE);
+    // 8 : E407_NOT_EXECUTABLE
+    assert_err!(e.execute_proposal(&users.charlie, 2), E407_NOT_EXECUTABLE);
+
+    e.remove_guardians(&e.owner, vec![&users.bob], 1).assert_success();
+    assert!(e.get_metadata().guardians.is_empty());
+}
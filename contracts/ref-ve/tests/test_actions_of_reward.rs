@@ -274,4 +274,275 @@ fn test_withdraw_reward() {
     e.withdraw_reward(&users.alice, &tokens.nref, None);
     assert_eq!(HashMap::new(), e.get_account_info(&users.alice).unwrap().rewards);
     assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_yocto("100"));
-}
\ No newline at end of file
+}
+#[test]
+fn test_farm_split_vote() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+
+    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("200"));
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 1).assert_success();
+    e.skip_time(DAY_SEC);
+
+    let split = |weights: Vec<(usize, u32)>| Action::VoteFarmSplit {
+        weights: weights.into_iter().map(|(farm_id, weight)| FarmVoteWeight { farm_id, weight }).collect()
+    };
+
+    // error scene 
+    // 1 : E201_INVALID_VOTE
+    assert_err!(e.action_proposal(&users.alice, 0, split(vec![(0, 6000), (1, 3000)]), None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 0, split(vec![(0, 6000), (0, 4000)]), None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 0, split(vec![(0, 6000), (3, 4000)]), None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 0, split(vec![(0, 10000), (1, 0)]), None), E201_INVALID_VOTE);
+    assert_err!(e.action_proposal(&users.alice, 1, split(vec![(0, 10000)]), None), E201_INVALID_VOTE);
+
+    // success
+    e.action_proposal(&users.alice, 0, split(vec![(0, 6000), (1, 3000), (2, 1000)]), None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VoteFarm { farm_id: 0 }, None).assert_success();
+    assert_eq!(vec![VoteInfo{
+        total_ballots: to_ve_token("320"),
+        participants: 2
+    }, VoteInfo{
+        total_ballots: to_ve_token("60"),
+        participants: 1
+    }, VoteInfo{
+        total_ballots: to_ve_token("20"),
+        participants: 1
+    }], e.get_proposal(0).unwrap().votes);
+    assert_eq!(2, e.get_proposal(0).unwrap().participants);
+
+    // lock changes follow the split
+    e.lock_lpt_position(&users.alice, 1, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    assert_eq!(vec![to_ve_token("440"), to_ve_token("120"), to_ve_token("40")],
+        e.get_proposal(0).unwrap().votes.iter().map(|v| v.total_ballots).collect::<Vec<_>>());
+
+    // cancel removes the whole split
+    e.action_cancel(&users.alice, 0).assert_success();
+    assert_eq!(vec![VoteInfo{
+        total_ballots: to_ve_token("200"),
+        participants: 1
+    }, VoteInfo{
+        total_ballots: 0,
+        participants: 0
+    }, VoteInfo{
+        total_ballots: 0,
+        participants: 0
+    }], e.get_proposal(0).unwrap().votes);
+    e.action_proposal(&users.alice, 0, split(vec![(0, 5000), (1, 5000)]), None).assert_success();
+    assert_eq!(vec![to_ve_token("400"), to_ve_token("200"), 0],
+        e.get_proposal(0).unwrap().votes.iter().map(|v| v.total_ballots).collect::<Vec<_>>());
+
+    // each farm's incentive is paid pro rata to the weight on it
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+    assert_eq!(to_yocto("150"), e.get_unclaimed_rewards(&users.alice).get(&tokens.nref.account_id()).unwrap().0);
+    assert_eq!(to_yocto("50"), e.get_unclaimed_rewards(&users.bob).get(&tokens.nref.account_id()).unwrap().0);
+}
+
+#[test]
+fn test_withdraw_removed_incentive() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+
+    e.ft_mint(&tokens.nref, &users.bob, to_yocto("2000"));
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("2000"));
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 0).assert_success();
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 1, 0).assert_success();
+    let deposits = e.get_proposal(0).unwrap().incentive.get(&0).unwrap().deposits.clone();
+    assert_eq!(2, deposits.len());
+    assert_eq!((users.bob.account_id(), to_yocto("200")), (deposits[0].depositor_id.clone(), deposits[0].amount));
+    assert_eq!((users.dude.account_id(), to_yocto("100")), (deposits[1].depositor_id.clone(), deposits[1].amount));
+
+    assert_eq!(e.remove_proposal(&users.alice, 0).unwrap_json::<bool>(), true);
+    assert_eq!(e.remove_proposal(&users.alice, 1).unwrap_json::<bool>(), true);
+    assert_eq!(to_yocto("300"), e.get_removed_incentives(&users.bob).get(&tokens.nref.account_id()).unwrap().0);
+    assert_eq!(to_yocto("100"), e.get_removed_incentives(&users.dude).get(&tokens.nref.account_id()).unwrap().0);
+    assert!(e.list_removed_proposal_assets().is_empty());
+
+    // error scene 
+    // 1 : E101_INSUFFICIENT_BALANCE
+    assert_err!(e.withdraw_removed_incentive(&users.dude, &tokens.nref, Some(to_yocto("200"))), E101_INSUFFICIENT_BALANCE);
+
+    // success
+    e.withdraw_removed_incentive(&users.bob, &tokens.nref, Some(to_yocto("200"))).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.bob), to_yocto("1900"));
+    assert_eq!(to_yocto("100"), e.get_removed_incentives(&users.bob).get(&tokens.nref.account_id()).unwrap().0);
+
+    // a failed transfer is reverted
+    e.ft_storage_unregister(&tokens.nref, &users.bob);
+    e.withdraw_removed_incentive(&users.bob, &tokens.nref, None).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.bob), 0);
+    assert_eq!(to_yocto("100"), e.get_removed_incentives(&users.bob).get(&tokens.nref.account_id()).unwrap().0);
+
+    e.ft_storage_deposit(&users.bob, &tokens.nref);
+    e.withdraw_removed_incentive(&users.bob, &tokens.nref, None).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.bob), to_yocto("100"));
+    assert!(e.get_removed_incentives(&users.bob).is_empty());
+    e.withdraw_removed_incentive(&users.dude, &tokens.nref, None).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.dude), to_yocto("2000"));
+}
+
+#[test]
+fn test_sweep_incentives() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.charlie, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.charlie, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    let start_at = to_sec(e.current_time() + DAY_TS);
+    e.create_proposal(&users.alice, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.ft_mint(&tokens.nref, &users.bob, to_yocto("1000"));
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("1000"));
+    e.deposit_reward(&tokens.nref, &users.bob, to_yocto("100"), 0, 0).assert_success();
+    // the odd yocto is left by the rounding between two equal voters
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("50") + 1, 0, 0).assert_success();
+    // nobody votes the second farm
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 0, 1).assert_success();
+    // nobody votes the poll
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("100"), 1, 0).assert_success();
+
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VoteFarm { farm_id: 0 }, None).assert_success();
+    e.action_proposal(&users.charlie, 0, Action::VoteFarm { farm_id: 0 }, None).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+
+    // error scene 
+    // 1 : E418_INCENTIVE_NOT_SWEEPABLE, still in the claim grace period
+    assert_err!(e.sweep_incentives(&users.bob, 0), E418_INCENTIVE_NOT_SWEEPABLE);
+    // 2 : E313_INVALID_CLAIM_GRACE_PERIOD
+    assert_err!(e.modify_incentive_sweep_policy(&e.owner, IncentiveSweepPolicy { claim_grace_sec: 0, sink: IncentiveSweepSink::Depositors }), E313_INVALID_CLAIM_GRACE_PERIOD);
+    // 3 : E002_NOT_ALLOWED
+    assert_err!(e.modify_incentive_sweep_policy(&users.alice, IncentiveSweepPolicy { claim_grace_sec: DAY_SEC, sink: IncentiveSweepSink::Depositors }), E002_NOT_ALLOWED);
+
+    e.skip_time(DEFAULT_INCENTIVE_CLAIM_GRACE_SEC);
+    // 4 : E002_NOT_ALLOWED, neither a depositor nor the treasury
+    assert_err!(e.sweep_incentives(&users.eve, 0), E002_NOT_ALLOWED);
+
+    // success, only the farm without votes, as the voters can still claim the other
+    e.sweep_incentives(&users.bob, 0).assert_success();
+    let sweeps = e.get_proposal(0).unwrap().sweeps;
+    assert_eq!(vec![(1, to_yocto("100"))], sweeps.iter().map(|s| (s.incentive_key, s.amount)).collect::<Vec<_>>());
+    assert_eq!(users.bob.account_id(), sweeps[0].caller_id);
+    assert!(e.get_removed_incentives(&users.bob).is_empty());
+    assert_eq!(to_yocto("100"), e.get_removed_incentives(&users.dude).get(&tokens.nref.account_id()).unwrap().0);
+    // 5 : E418_INCENTIVE_NOT_SWEEPABLE, nothing more to sweep
+    assert_err!(e.sweep_incentives(&users.dude, 0), E418_INCENTIVE_NOT_SWEEPABLE);
+
+    // the late claim still gets the rewards
+    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("75"))]), e.get_unclaimed_rewards(&users.alice));
+    e.claim_reward(&users.alice, 0).assert_success();
+    assert_eq!(&to_yocto("75"), e.get_account_info(&users.alice).unwrap().rewards.get(&tokens.nref.account_id()).unwrap());
+    assert_eq!(HashMap::from([(0, VoteDetail{
+        action: Action::VoteFarm { farm_id: 0 }, amount: to_ve_token("200")
+    })]), e.get_vote_detail_history(&users.alice));
+
+    // the rounding residual is swept once known, going to the last depositor
+    e.sweep_incentives(&users.dude, 0).assert_success();
+    let sweeps = e.get_proposal(0).unwrap().sweeps;
+    assert_eq!(vec![(1, to_yocto("100")), (0, 1)], sweeps.iter().map(|s| (s.incentive_key, s.amount)).collect::<Vec<_>>());
+    assert_eq!(to_yocto("100") + 1, e.get_removed_incentives(&users.dude).get(&tokens.nref.account_id()).unwrap().0);
+    e.claim_reward(&users.charlie, 0).assert_success();
+    assert_eq!(&to_yocto("75"), e.get_account_info(&users.charlie).unwrap().rewards.get(&tokens.nref.account_id()).unwrap());
+    assert_err!(e.sweep_incentives(&users.dude, 0), E418_INCENTIVE_NOT_SWEEPABLE);
+
+    e.withdraw_removed_incentive(&users.dude, &tokens.nref, None).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.dude), to_yocto("850"));
+
+    // the treasury sink
+    e.modify_incentive_sweep_policy(&e.owner, IncentiveSweepPolicy { claim_grace_sec: DAY_SEC, sink: IncentiveSweepSink::Treasury { account_id: users.eve.account_id() } }).assert_success();
+    e.sweep_incentives(&users.eve, 1).assert_success();
+    assert_eq!(to_yocto("100"), e.get_proposal(1).unwrap().sweeps[0].amount);
+    assert_eq!(to_yocto("100"), e.get_removed_incentives(&users.eve).get(&tokens.nref.account_id()).unwrap().0);
+    assert!(e.get_removed_incentives(&users.dude).is_empty());
+}
+
+#[test]
+fn test_conditional_incentives() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id()]).assert_success();
+    e.extend_whitelisted_accounts(&e.owner, vec![users.dude.account_id()]).assert_success();
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("200"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    let start_at = to_sec(e.current_time() + DAY_TS);
+    e.create_proposal(&users.dude, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 20000 }, "FarmingReward".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.create_proposal(&users.dude, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.ft_mint(&tokens.nref, &users.dude, to_yocto("1000"));
+    e.ft_mint(&tokens.nref, &users.eve, to_yocto("1000"));
+
+    // error scene 
+    // 1 : E202_INVALID_INCENTIVE
+    assert_err!(e.deposit_reward_with_condition(&tokens.nref, &users.dude, to_yocto("100"), 0, 0, IncentiveCondition::TopRank { rank: 0 }), E202_INVALID_INCENTIVE);
+    assert_err!(e.deposit_reward_with_condition(&tokens.nref, &users.dude, to_yocto("100"), 0, 0, IncentiveCondition::TopRank { rank: 4 }), E202_INVALID_INCENTIVE);
+    assert_err!(e.deposit_reward_with_condition(&tokens.nref, &users.dude, to_yocto("100"), 0, 0, IncentiveCondition::MinShare { share: 0 }), E202_INVALID_INCENTIVE);
+    assert_err!(e.deposit_reward_with_condition(&tokens.nref, &users.dude, to_yocto("100"), 0, 0, IncentiveCondition::MinShare { share: 10001 }), E202_INVALID_INCENTIVE);
+    assert_err!(e.deposit_reward_with_condition(&tokens.nref, &users.dude, to_yocto("100"), 1, 0, IncentiveCondition::TopRank { rank: 1 }), E202_INVALID_INCENTIVE);
+
+    // success
+    e.deposit_reward_with_condition(&tokens.nref, &users.dude, to_yocto("100"), 0, 0, IncentiveCondition::TopRank { rank: 1 }).assert_success();
+    e.deposit_reward_with_condition(&tokens.nref, &users.dude, to_yocto("100"), 0, 1, IncentiveCondition::TopRank { rank: 1 }).assert_success();
+    e.deposit_reward(&tokens.nref, &users.dude, to_yocto("50"), 0, 1).assert_success();
+    e.deposit_reward_with_condition(&tokens.nref, &users.eve, to_yocto("60"), 0, 1, IncentiveCondition::MinShare { share: 5000 }).assert_success();
+    e.deposit_reward_with_condition(&tokens.nref, &users.eve, to_yocto("40"), 0, 1, IncentiveCondition::MinShare { share: 3000 }).assert_success();
+    // nobody votes the third farm
+    e.deposit_reward_with_condition(&tokens.nref, &users.eve, to_yocto("30"), 0, 2, IncentiveCondition::TopRank { rank: 3 }).assert_success();
+    assert_eq!(4, e.get_proposal(0).unwrap().incentive.get(&1).unwrap().deposits.len());
+
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VoteFarm { farm_id: 0 }, None).assert_success();
+    e.action_proposal(&users.bob, 0, Action::VoteFarm { farm_id: 1 }, None).assert_success();
+    e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
+
+    // the first farm leads, the second one only has a third of the ballots
+    assert_eq!(to_yocto("100"), e.get_unclaimed_rewards(&users.alice).get(&tokens.nref.account_id()).unwrap().0);
+    assert_eq!(to_yocto("90"), e.get_unclaimed_rewards(&users.bob).get(&tokens.nref.account_id()).unwrap().0);
+
+    // the conditions not met are refunded before the first claim
+    e.claim_reward(&users.bob, 0).assert_success();
+    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("90"))]), e.get_account_info(&users.bob).unwrap().rewards);
+    assert_eq!(to_yocto("100"), e.get_removed_incentives(&users.dude).get(&tokens.nref.account_id()).unwrap().0);
+    assert_eq!(to_yocto("90"), e.get_removed_incentives(&users.eve).get(&tokens.nref.account_id()).unwrap().0);
+    let proposal = e.get_proposal(0).unwrap();
+    assert_eq!(vec![to_yocto("90")], proposal.incentive.get(&1).unwrap().incentive_amounts);
+    assert!(proposal.incentive.get(&2).unwrap().deposits.is_empty());
+
+    e.claim_reward(&users.alice, 0).assert_success();
+    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("100"))]), e.get_account_info(&users.alice).unwrap().rewards);
+    assert_eq!(to_yocto("100"), e.get_removed_incentives(&users.dude).get(&tokens.nref.account_id()).unwrap().0);
+
+    e.withdraw_removed_incentive(&users.eve, &tokens.nref, None).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.eve), to_yocto("960"));
+}
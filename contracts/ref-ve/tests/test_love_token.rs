@@ -0,0 +1,126 @@
+mod setup;
+use crate::setup::*;
+use std::collections::HashMap;
+
+#[test]
+fn test_love_transfer_policy() {
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.storage_deposit(&users.bob, &users.bob, to_yocto("0.00125")).assert_success();
+    assert_eq!(e.get_config().love_transfer_policy, LoveTransferPolicy::Transferable);
+
+    // transferable by default, the lock stays with alice
+    e.transfer_love(&users.alice, &users.bob, to_ve_token("1")).assert_success();
+    assert_eq!(e.balance_of(&users.bob), to_ve_token("1"));
+    assert!(e.get_account_info(&users.bob).unwrap().locks.is_empty());
+    e.transfer_love(&users.bob, &users.alice, to_ve_token("1")).assert_success();
+
+    // error scene 
+    // 1 : E112_LOVE_NOT_TRANSFERABLE
+    e.modify_love_transfer_policy(&e.owner, LoveTransferPolicy::NonTransferable).assert_success();
+    assert_err!(e.transfer_love(&users.alice, &users.bob, to_ve_token("1")), E112_LOVE_NOT_TRANSFERABLE);
+
+    // 2 : E002_NOT_ALLOWED
+    assert_err!(e.modify_love_transfer_policy(&users.alice, LoveTransferPolicy::MoveLock), E002_NOT_ALLOWED);
+    assert_err!(e.extend_whitelisted_love_receivers(&users.alice, vec![users.bob.account_id()]), E002_NOT_ALLOWED);
+
+    // 3 : E113_LOVE_RECEIVER_NOT_WHITELISTED
+    e.modify_love_transfer_policy(&e.owner, LoveTransferPolicy::WhitelistedReceivers).assert_success();
+    assert_err!(e.transfer_love(&users.alice, &users.bob, to_ve_token("1")), E113_LOVE_RECEIVER_NOT_WHITELISTED);
+
+    e.extend_whitelisted_love_receivers(&e.owner, vec![users.alice.account_id(), users.bob.account_id()]).assert_success();
+    assert_eq!(e.get_metadata().whitelisted_love_receivers.len(), 2);
+    e.transfer_love(&users.alice, &users.bob, to_ve_token("1")).assert_success();
+    assert_eq!(e.balance_of(&users.bob), to_ve_token("1"));
+    // the lock stays with alice
+    assert!(e.get_account_info(&users.bob).unwrap().locks.is_empty());
+    e.transfer_love(&users.bob, &users.alice, to_ve_token("1")).assert_success();
+
+    e.remove_whitelisted_love_receivers(&e.owner, vec![users.bob.account_id()]).assert_success();
+    assert_err!(e.transfer_love(&users.alice, &users.bob, to_ve_token("1")), E113_LOVE_RECEIVER_NOT_WHITELISTED);
+}
+
+#[test]
+fn test_love_transfer_move_lock() {
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.mft_storage_deposit(&lpt_id(), &users.bob);
+    e.lock_lpt_position(&users.alice, 0, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt_position(&users.alice, 1, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC / 2).assert_success();
+    e.storage_deposit(&users.bob, &users.bob, to_yocto("0.00125")).assert_success();
+    e.modify_love_transfer_policy(&e.owner, LoveTransferPolicy::MoveLock).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    e.create_proposal(&users.alice, ProposalKind::Common, "Common".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
+    e.skip_time(DAY_SEC);
+    e.action_proposal(&users.alice, 0, Action::VoteApprove, None).assert_success();
+    assert_eq!(e.get_proposal(0).unwrap().votes[0].total_ballots, to_ve_token("350"));
+
+    // 20% of alice's locks move to bob
+    e.transfer_love(&users.alice, &users.bob, to_ve_token("70")).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    let bob = e.get_account_info(&users.bob).unwrap();
+    assert_eq!(alice.lpt_amounts[&lpt_id()].0, to_yocto("160"));
+    assert_eq!(alice.ve_lpt_amount, to_ve_token("280"));
+    assert_eq!(bob.lpt_amounts[&lpt_id()].0, to_yocto("40"));
+    assert_eq!(bob.ve_lpt_amount, to_ve_token("70"));
+    assert_eq!(bob.locks[&0].lpt_amount, to_yocto("20"));
+    assert_eq!(bob.locks[&0].unlock_timestamp, alice.locks[&0].unlock_timestamp);
+    assert_eq!(bob.locks[&1].lpt_amount, to_yocto("20"));
+    assert_eq!(bob.locks[&1].unlock_timestamp, alice.locks[&1].unlock_timestamp);
+    assert_eq!(e.balance_of(&users.alice), to_ve_token("280"));
+    assert_eq!(e.balance_of(&users.bob), to_ve_token("70"));
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, to_ve_token("350"));
+    assert_eq!(e.get_metadata().lock_pools[0].cur_lock_lpt, to_yocto("200"));
+
+    // the vote weight follows
+    assert_eq!(HashMap::from([(0, VoteDetail {
+        action: Action::VoteApprove, amount: to_ve_token("280")
+    })]), e.get_vote_detail(&users.alice));
+    assert_eq!(e.get_proposal(0).unwrap().votes[0].total_ballots, to_ve_token("280"));
+
+    // 1 : insufficient love balance
+    assert_err!(e.transfer_love(&users.bob, &users.alice, to_ve_token("71")), "The account doesn't have enough balance");
+
+    // bob exits with the moved lock
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
+    e.withdraw_lpt_position(&users.bob, 0, None).assert_success();
+    e.withdraw_lpt_position(&users.bob, 1, None).assert_success();
+    assert_eq!(e.mft_balance_of(&users.bob, &lpt_id()), to_yocto("40"));
+    assert_eq!(e.balance_of(&users.bob), 0);
+}
+
+#[test]
+fn test_love_transfer_move_lock_too_small() {
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt_position(&users.alice, 0, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    // a 6 decimals pool, where a tiny love amount is worth less than 1 unit of the token
+    e.register_ft_lock_pool(&e.owner, &tokens.nref, 6, BASE_VE_WEIGHT).assert_success();
+    e.ft_mint(&tokens.nref, &users.alice, 100 * 10u128.pow(6));
+    e.lock_ft(&tokens.nref, &users.alice, 1, 100 * 10u128.pow(6), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    assert_eq!(e.balance_of(&users.alice), to_ve_token("400"));
+    e.storage_deposit(&users.bob, &users.bob, to_yocto("0.00125")).assert_success();
+    e.modify_love_transfer_policy(&e.owner, LoveTransferPolicy::MoveLock).assert_success();
+
+    // 1 : E116_LOVE_NOT_MOVABLE, the share of the ft position can not be sliced
+    assert_err!(e.transfer_love(&users.alice, &users.bob, 10u128.pow(9)), E116_LOVE_NOT_MOVABLE);
+    assert_eq!(e.balance_of(&users.alice), to_ve_token("400"));
+    assert_eq!(e.balance_of(&users.bob), 0);
+
+    // large enough to slice both positions
+    e.transfer_love(&users.alice, &users.bob, to_ve_token("40")).assert_success();
+    assert_eq!(e.balance_of(&users.bob), to_ve_token("40"));
+    assert_eq!(e.get_account_info(&users.bob).unwrap().locks.len(), 2);
+}
@@ -25,12 +25,45 @@ fn test_modify_config(){
     assert_eq!(e.get_config().min_locking_duration_sec, 500);
     assert_eq!(e.get_config().max_locking_duration_sec, 1000);
     assert_eq!(e.get_config().max_locking_multiplier, 30000);
+    assert!(e.get_config().locking_multiplier_curve.is_empty());
+
+    assert_err!(e.modify_locking_multiplier_curve(&users.alice, vec![
+        LockingMultiplierPoint { duration_sec: 600, multiplier: 20000 },
+        LockingMultiplierPoint { duration_sec: 500, multiplier: 25000 },
+    ]), E310_INVALID_MULTIPLIER_CURVE);
+    assert_err!(e.modify_locking_multiplier_curve(&users.alice, vec![
+        LockingMultiplierPoint { duration_sec: 500, multiplier: 20000 },
+        LockingMultiplierPoint { duration_sec: 600, multiplier: 15000 },
+    ]), E310_INVALID_MULTIPLIER_CURVE);
+    assert_err!(e.modify_locking_multiplier_curve(&users.alice, vec![
+        LockingMultiplierPoint { duration_sec: 1000, multiplier: 20000 },
+    ]), E310_INVALID_MULTIPLIER_CURVE);
+    assert_err!(e.modify_locking_multiplier_curve(&users.alice, vec![
+        LockingMultiplierPoint { duration_sec: 500, multiplier: 40000 },
+    ]), E310_INVALID_MULTIPLIER_CURVE);
+
+    let multiplier_curve = vec![
+        LockingMultiplierPoint { duration_sec: 500, multiplier: 20000 },
+        LockingMultiplierPoint { duration_sec: 800, multiplier: 28000 },
+    ];
+    e.modify_locking_multiplier_curve(&users.alice, multiplier_curve.clone()).assert_success();
+    assert_eq!(e.get_config().locking_multiplier_curve, multiplier_curve);
+
+    // the curve must stay within the new limits
+    assert_err!(e.modify_locking_policy(&users.alice, 500, 800, 30000), E310_INVALID_MULTIPLIER_CURVE);
+    e.modify_locking_policy(&users.alice, 500, 1200, 30000).assert_success();
+    assert_eq!(e.get_config().locking_multiplier_curve, multiplier_curve);
 
     assert_err!(e.modify_voting_duration_limit(&users.alice, 11000, 2000), E307_INVALID_VOTING_DURATION_LIMIT);
     e.modify_voting_duration_limit(&users.alice, 1000, 2000).assert_success();
     assert_eq!(e.get_config().min_voting_duration_sec, 1000);
     assert_eq!(e.get_config().max_voting_duration_sec, 2000);
 
+    assert_eq!(e.get_config().max_early_unlock_penalty_rate, DEFAULT_MAX_EARLY_UNLOCK_PENALTY_RATE);
+    assert_err!(e.modify_early_unlock_penalty(&users.alice, 10001, PenaltySink::Lockers), E308_INVALID_PENALTY_RATE);
+    e.modify_early_unlock_penalty(&users.alice, 3000, PenaltySink::Treasury { account_id: e.owner.account_id() }).assert_success();
+    assert_eq!(e.get_config().max_early_unlock_penalty_rate, 3000);
+    assert_eq!(e.get_config().early_unlock_penalty_sink, PenaltySink::Treasury { account_id: e.owner.account_id() });
 }
 
 #[test]
@@ -45,7 +78,7 @@ fn test_return_lpt_lostfound(){
 
     e.mft_unregister(&lpt_id(), &users.alice);
     e.withdraw_lpt(&users.alice, None).assert_success();
-    assert_eq!(to_yocto("100"), e.get_metadata().lostfound.0);
+    assert_eq!(to_yocto("100"), e.get_metadata().lock_pools[0].lostfound);
     
     e.mft_storage_deposit(&lpt_id(), &users.alice);
 
@@ -60,28 +93,22 @@ fn test_return_lpt_lostfound(){
     assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), 0);
     e.return_lpt_lostfound(&e.owner, &users.alice, to_yocto("100")).assert_success();
     assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), to_yocto("100"));
-    assert_eq!(0, e.get_metadata().lostfound.0);
+    assert_eq!(0, e.get_metadata().lock_pools[0].lostfound);
 
 }
 
 #[test]
 fn test_return_removed_proposal_assets(){
-    let e = init_env();
+    // the incentives deposited before 0.3.0 have no depositor, removing their proposal leaves them to the owner
+    let e = Env::init_with_contract(previous_ref_ve_wasm_bytes());
     let users = Users::init(&e);
     let tokens = Tokens::init(&e);
 
     e.extend_whitelisted_incentive_tokens(&e.owner, vec![tokens.nref.account_id(), tokens.wnear.account_id()]).assert_success();
-    assert_eq!(e.get_metadata().whitelisted_incentive_tokens.len(), 2);
     e.remove_whitelisted_incentive_tokens(&e.owner, vec![tokens.wnear.account_id()]).assert_success();
-    assert_eq!(e.get_metadata().whitelisted_incentive_tokens.len(), 1);
 
-    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
-    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
-    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
-    
     e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id(), users.dude.account_id()]).assert_success();
 
-    e.storage_deposit(&users.dude, &users.dude, to_yocto("1"));
     e.create_proposal(&users.alice, ProposalKind::FarmingReward { farm_list: vec!["noct.near|nref.near&2657".to_string(), "nusdt.near|nusdc.near|ndai.near&1910".to_string(), "usn.near|nusdt.near&3020".to_string()], total_reward: 2 }, "FarmingReward".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
     e.create_proposal(&users.alice, ProposalKind::Poll { options: vec!["topic1".to_string(), "topic2".to_string()] }, "Poll".to_string(), to_sec(e.current_time() + DAY_TS), DEFAULT_MIN_VOTING_DURATION_SEC, 1).assert_success();
     
@@ -90,11 +117,16 @@ fn test_return_removed_proposal_assets(){
     e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 0, 1).assert_success();
     e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 1, 0).assert_success();
 
+    e.upgrade_contract(&e.owner, ref_ve_wasm_bytes()).assert_success();
+    assert_eq!(vec![tokens.nref.account_id()], e.get_metadata().whitelisted_incentive_tokens);
+    // a deposit made after the upgrade is withdrawn by the depositor itself
+    e.deposit_reward(&tokens.nref, &users.alice, to_yocto("50"), 1, 0).assert_success();
+
     assert_eq!(e.remove_proposal(&users.alice, 0).unwrap_json::<bool>(), true);
     assert_eq!(to_yocto("200"), e.list_removed_proposal_assets().get(&tokens.nref.account_id()).unwrap().0);
     assert_eq!(e.remove_proposal(&users.alice, 1).unwrap_json::<bool>(), true);
     assert_eq!(to_yocto("300"), e.list_removed_proposal_assets().get(&tokens.nref.account_id()).unwrap().0);
-
+    assert_eq!(to_yocto("50"), e.get_removed_incentives(&users.alice).get(&tokens.nref.account_id()).unwrap().0);
 
     // error scene 
     // 1 : E002_NOT_ALLOWED
@@ -105,9 +137,10 @@ fn test_return_removed_proposal_assets(){
 
     //success
     e.return_removed_proposal_assets(&e.owner, &users.alice, &tokens.nref, to_yocto("200")).assert_success();
-    assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_yocto("1900"));
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_yocto("1850"));
     assert_eq!(to_yocto("100"), e.list_removed_proposal_assets().get(&tokens.nref.account_id()).unwrap().0);
 
+    // the transfer to an unregistered account fails, the assets stay
     e.ft_storage_unregister(&tokens.nref, &users.alice);
 
     e.return_removed_proposal_assets(&e.owner, &users.alice, &tokens.nref, to_yocto("100")).assert_success();
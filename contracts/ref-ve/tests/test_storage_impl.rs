@@ -14,9 +14,12 @@ fn test_storage_impl() {
     assert_eq!(e.get_metadata().account_count.0, 0);
     e.storage_deposit(&users.alice, &users.alice, to_yocto("0.00125")).assert_success();
     assert_eq!(e.get_metadata().account_count.0, 1);
-    // alice register again
+    assert_eq!(e.storage_balance_of(&users.alice).unwrap().available.0, 0);
+    // alice register again, the deposit is kept as available storage
     e.storage_deposit(&users.alice, &users.alice, to_yocto("0.00125")).assert_success();
     assert_eq!(e.get_metadata().account_count.0, 1);
+    assert_eq!(e.storage_balance_of(&users.alice).unwrap().available.0, to_yocto("0.00125"));
+    assert_eq!(e.storage_balance_of(&users.alice).unwrap().total.0, to_yocto("0.0025"));
     assert_eq!(e.get_account_info(&users.alice).unwrap().sponsor_id, users.alice.account_id());
 
     // alice help bob register
@@ -27,12 +30,22 @@ fn test_storage_impl() {
     assert_eq!(e.storage_unregister(&users.bob, 1).unwrap_json::<bool>(), true);
     e.storage_deposit(&users.alice, &users.bob, to_yocto("0.00125")).assert_success();
     
-    // alice unregister
+    // alice withdraw the available storage
     let user_balance_before = users.alice.account().unwrap().amount;
-    assert_eq!(e.storage_unregister(&users.alice, 1).unwrap_json::<bool>(), true);
+    e.storage_withdraw(&users.alice, 1).assert_success();
     let user_balance_after = users.alice.account().unwrap().amount;
     assert!(user_balance_after > user_balance_before);
     assert!(user_balance_after - user_balance_before < to_yocto("0.00125"));
+    assert_eq!(e.storage_balance_of(&users.alice).unwrap().available.0, 0);
+    assert_err!(e.storage_withdraw(&users.alice, 1), E101_INSUFFICIENT_BALANCE);
+
+    // alice unregister, the available storage goes back with the registration
+    e.storage_deposit(&users.alice, &users.alice, to_yocto("0.00125")).assert_success();
+    let user_balance_before = users.alice.account().unwrap().amount;
+    assert_eq!(e.storage_unregister(&users.alice, 1).unwrap_json::<bool>(), true);
+    let user_balance_after = users.alice.account().unwrap().amount;
+    assert!(user_balance_after - user_balance_before > to_yocto("0.00125"));
+    assert!(user_balance_after - user_balance_before < to_yocto("0.0025"));
     assert_eq!(e.get_metadata().account_count.0, 1);
 
     // bob unregister
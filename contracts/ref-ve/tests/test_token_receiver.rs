@@ -1,5 +1,6 @@
 mod setup;
 use crate::setup::*;
+use std::collections::HashMap;
 
 #[test]
 fn test_lock_lpt(){
@@ -27,14 +28,14 @@ fn test_lock_lpt(){
     e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
     before.account_count = 1.into();
     before.cur_total_ve_lpt = to_ve_token("200").into();
-    before.cur_lock_lpt = to_yocto("100").into();
+    before.lock_pools[0].cur_lock_lpt = to_yocto("100");
     assert_eq!(format!("{:?}", before), format!("{:?}", e.get_metadata()));
 
     // lock again
     let mut before = e.get_metadata();
     e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
     before.cur_total_ve_lpt = to_ve_token("400").into();
-    before.cur_lock_lpt = to_yocto("200").into();
+    before.lock_pools[0].cur_lock_lpt = to_yocto("200");
     assert_eq!(format!("{:?}", before), format!("{:?}", e.get_metadata()));
 
     // append
@@ -42,11 +43,422 @@ fn test_lock_lpt(){
     let mut before = e.get_metadata();
     assert_eq!("10", e.append_lpt(&users.alice, to_yocto("100") + 10, 0).unwrap_json_value());
     before.cur_total_ve_lpt = to_ve_token("600").into();
-    before.cur_lock_lpt = to_yocto("300").into();
+    before.lock_pools[0].cur_lock_lpt = to_yocto("300");
     assert_eq!(format!("{:?}", before), format!("{:?}", e.get_metadata()));
     assert_eq!(to_yocto("100"), e.mft_balance_of(&users.alice, &lpt_id()));
 }
 
+#[test]
+fn test_lock_lpt_with_decay(){
+    let e = init_env();
+    let users = Users::init(&e);
+    
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+
+    assert_err!(e.modify_decay_mode(&users.alice, DecayMode::ToBase), E002_NOT_ALLOWED);
+    e.modify_decay_mode(&e.owner, DecayMode::ToBase).assert_success();
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.modify_decay_mode(&e.owner, DecayMode::ToZero).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    // a decaying lock unlocks at the start of a period, with the veLPT of the duration cut to it
+    let alice = e.get_account_info(&users.alice).unwrap();
+    let bob = e.get_account_info(&users.bob).unwrap();
+    let duration_sec = alice.locks[&0].duration_sec;
+    assert_eq!(to_sec(alice.locks[&0].unlock_timestamp) % DECAY_PERIOD_SEC, 0);
+    assert!(duration_sec <= DEFAULT_MAX_LOCKING_DURATION_SEC && duration_sec > DEFAULT_MAX_LOCKING_DURATION_SEC - DECAY_PERIOD_SEC);
+    assert_eq!(bob.locks[&0].duration_sec, duration_sec);
+    let ve_lpt_amount = alice.ve_lpt_amount;
+    assert!(ve_lpt_amount <= to_ve_token("200") && ve_lpt_amount > to_ve_token("198"));
+    assert_eq!(bob.ve_lpt_amount, ve_lpt_amount);
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, ve_lpt_amount * 2);
+
+    let alice_slope = (ve_lpt_amount - to_ve_token("100")) / duration_sec as u128;
+    let bob_slope = ve_lpt_amount / duration_sec as u128;
+
+    // half way
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC / 2);
+    let alice_ve = ve_lpt_amount - alice_slope * (DEFAULT_MAX_LOCKING_DURATION_SEC / 2) as u128;
+    let bob_ve = ve_lpt_amount - bob_slope * (DEFAULT_MAX_LOCKING_DURATION_SEC / 2) as u128;
+    assert_eq!(e.get_account_info(&users.alice).unwrap().ve_lpt_amount, alice_ve);
+    assert_eq!(e.get_account_info(&users.bob).unwrap().ve_lpt_amount, bob_ve);
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, alice_ve + bob_ve);
+    // love token doesn't decay
+    assert_eq!(e.balance_of(&users.alice), ve_lpt_amount);
+
+    // after unlock
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
+    let alice_ve = ve_lpt_amount - alice_slope * duration_sec as u128;
+    let bob_ve = ve_lpt_amount - bob_slope * duration_sec as u128;
+    assert!(alice_ve >= to_ve_token("100") && alice_ve < to_ve_token("100.000001"));
+    assert!(bob_ve < to_ve_token("0.000001"));
+    assert_eq!(e.get_account_info(&users.alice).unwrap().ve_lpt_amount, alice_ve);
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, alice_ve + bob_ve);
+
+    e.withdraw_lpt(&users.alice, None).assert_success();
+    assert_eq!(e.balance_of(&users.alice), 0);
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, bob_ve);
+    e.withdraw_lpt(&users.bob, None).assert_success();
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, 0);
+}
+
+#[test]
+fn test_relock_with_decay(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("101"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.modify_decay_mode(&e.owner, DecayMode::ToBase).assert_success();
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    let ve_lpt_amount = e.get_account_info(&users.alice).unwrap().ve_lpt_amount;
+    assert!(ve_lpt_amount > to_ve_token("198"));
+
+    // half way, a dust append relocks from the decayed veLPT, not the initial one
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC / 2);
+    let decayed_ve = e.get_account_info(&users.alice).unwrap().ve_lpt_amount;
+    assert!(decayed_ve < to_ve_token("151"));
+    e.append_lpt(&users.alice, to_yocto("0.001"), 0).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert!(alice.ve_lpt_amount >= decayed_ve && alice.ve_lpt_amount < to_ve_token("160"));
+    assert_eq!(alice.locks[&0].ve_lpt_amount, alice.ve_lpt_amount);
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, alice.ve_lpt_amount);
+    // the love of the decayed veLPT is burnt
+    assert_eq!(e.balance_of(&users.alice), alice.ve_lpt_amount);
+
+    // nor does a 1s extension
+    e.skip_time(DAY_SEC);
+    let decayed_ve = e.get_account_info(&users.alice).unwrap().ve_lpt_amount;
+    e.extend_lock(&users.alice, 0, 1).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert!(alice.ve_lpt_amount >= decayed_ve && alice.ve_lpt_amount < to_ve_token("160"));
+    assert_eq!(e.balance_of(&users.alice), alice.ve_lpt_amount);
+}
+
+#[test]
+fn test_ve_lpt_checkpoints(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+
+    e.modify_decay_mode(&e.owner, DecayMode::ToZero).assert_success();
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    let t0 = to_sec(e.current_time());
+    let alice_ve0 = e.get_account_info(&users.alice).unwrap().ve_lpt_amount;
+    let alice_slope = alice_ve0 / e.get_account_info(&users.alice).unwrap().locks[&0].duration_sec as u128;
+
+    e.skip_time(DAY_SEC);
+    e.modify_decay_mode(&e.owner, DecayMode::Disabled).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    let t1 = t0 + DAY_SEC;
+
+    e.skip_time(DAY_SEC);
+    e.lock_lpt_position(&users.alice, 1, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    let t2 = t1 + DAY_SEC;
+
+    // before any lock
+    assert_eq!(e.get_ve_lpt_at(&users.alice, t0 - 1), 0);
+    assert_eq!(e.get_ve_lpt_at(&users.bob, t0), 0);
+    assert_eq!(e.get_total_ve_lpt_at(t0 - 1), 0);
+    assert_eq!(e.get_ve_lpt_at(&users.dude, t0), 0);
+
+    // at and between checkpoints
+    assert_eq!(e.get_ve_lpt_at(&users.alice, t0), alice_ve0);
+    assert_eq!(e.get_total_ve_lpt_at(t0), alice_ve0);
+    let alice_ve = alice_ve0 - alice_slope * (DAY_SEC / 2) as u128;
+    assert_eq!(e.get_ve_lpt_at(&users.alice, t0 + DAY_SEC / 2), alice_ve);
+    assert_eq!(e.get_total_ve_lpt_at(t0 + DAY_SEC / 2), alice_ve);
+    let alice_ve = alice_ve0 - alice_slope * DAY_SEC as u128;
+    assert_eq!(e.get_ve_lpt_at(&users.alice, t1), alice_ve);
+    assert_eq!(e.get_ve_lpt_at(&users.bob, t1), to_ve_token("200"));
+    assert_eq!(e.get_total_ve_lpt_at(t1), alice_ve + to_ve_token("200"));
+    let alice_ve = alice_ve0 + to_ve_token("200") - alice_slope * (2 * DAY_SEC) as u128;
+    assert_eq!(e.get_ve_lpt_at(&users.alice, t2), alice_ve);
+    assert_eq!(e.get_total_ve_lpt_at(t2), alice_ve + to_ve_token("200"));
+    assert_eq!(e.get_total_ve_lpt_at(t2), e.get_metadata().cur_total_ve_lpt.0);
+
+    // the future is projected by current locks
+    let future = t0 + DEFAULT_MAX_LOCKING_DURATION_SEC + DAY_SEC;
+    let alice_ve = to_ve_token("400") - alice_slope * DEFAULT_MAX_LOCKING_DURATION_SEC as u128;
+    assert_eq!(e.get_ve_lpt_at(&users.alice, future), alice_ve);
+    assert_eq!(e.get_total_ve_lpt_at(future), alice_ve + to_ve_token("200"));
+
+    // later changes don't rewrite the history
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
+    e.withdraw_lpt(&users.bob, None).assert_success();
+    assert_eq!(e.get_ve_lpt_at(&users.bob, t2), to_ve_token("200"));
+    assert_eq!(e.get_ve_lpt_at(&users.bob, to_sec(e.current_time())), 0);
+    assert_eq!(e.get_total_ve_lpt_at(t2), to_ve_token("600") - alice_slope * (2 * DAY_SEC) as u128);
+}
+
+#[test]
+fn test_ve_lpt_checkpoints_bounded(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("200"));
+    e.mft_mint(&lpt_inner_id(), &users.bob, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt(&users.bob, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    e.extend_whitelisted_accounts(&e.owner, vec![users.alice.account_id()]).assert_success();
+    let start_at = to_sec(e.current_time() + DAY_TS);
+    e.create_proposal_with_voting_mode(&users.alice, ProposalKind::Common, "Snapshot".to_string(), start_at, DEFAULT_MIN_VOTING_DURATION_SEC, Some(VotingMode::Snapshot), 1).assert_success();
+    e.skip_time(DAY_SEC);
+
+    // every change after the start pushes out the oldest checkpoint once the ring is full
+    for _ in 0..MAX_ACCOUNT_VE_CHECKPOINTS {
+        e.skip_time(1);
+        e.append_lpt(&users.alice, to_yocto("1"), 0).assert_success();
+    }
+    assert_eq!(e.get_ve_lpt_at(&users.alice, start_at), 0);
+    assert_eq!(e.get_ve_lpt_at(&users.alice, to_sec(e.current_time())), e.get_account_info(&users.alice).unwrap().ve_lpt_amount);
+    assert_eq!(e.get_ve_lpt_at(&users.bob, start_at), to_ve_token("200"));
+
+    // 1 : E117_VE_LPT_HISTORY_DROPPED
+    assert_err!(e.action_proposal(&users.alice, 0, Action::VoteApprove, None), E117_VE_LPT_HISTORY_DROPPED);
+
+    e.action_proposal(&users.bob, 0, Action::VoteApprove, None).assert_success();
+}
+
+#[test]
+fn test_lock_lpt_with_multiplier_curve(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("300"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+
+    // 1.75x at half of the max duration
+    let half_duration = DEFAULT_MAX_LOCKING_DURATION_SEC / 2;
+    e.modify_locking_multiplier_curve(&e.owner, vec![
+        LockingMultiplierPoint { duration_sec: half_duration, multiplier: 17500 },
+    ]).assert_success();
+
+    e.lock_lpt_position(&users.alice, 0, to_yocto("100"), half_duration / 2).assert_success();
+    e.lock_lpt_position(&users.alice, 1, to_yocto("100"), half_duration).assert_success();
+    e.lock_lpt_position(&users.alice, 2, to_yocto("100"), half_duration + half_duration / 2).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert_eq!(alice.locks[&0].ve_lpt_amount, to_ve_token("137.5"));
+    assert_eq!(alice.locks[&1].ve_lpt_amount, to_ve_token("175"));
+    assert_eq!(alice.locks[&2].ve_lpt_amount, to_ve_token("187.5"));
+    assert_eq!(e.balance_of(&users.alice), to_ve_token("500"));
+}
+
+#[test]
+fn test_lock_lpt_positions(){
+    let e = init_env();
+    let users = Users::init(&e);
+    
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("300"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.lock_lpt_position(&users.alice, 1, to_yocto("100"), DEFAULT_MIN_LOCKING_DURATION_SEC).assert_success();
+    assert_err!(e.append_lpt_position(&users.alice, 2, to_yocto("100"), 0), E105_ACC_NOT_LOCKED);
+
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert_eq!(alice.locks.len(), 2);
+    let lock0 = alice.locks.get(&0).unwrap();
+    let lock1 = alice.locks.get(&1).unwrap();
+    assert_eq!(lock0.ve_lpt_amount, to_ve_token("200"));
+    assert_eq!(lock1.duration_sec, DEFAULT_MIN_LOCKING_DURATION_SEC);
+    assert!(lock1.unlock_timestamp < lock0.unlock_timestamp);
+    assert_eq!(alice.lpt_amounts[&lpt_id()].0, to_yocto("200"));
+    assert_eq!(alice.ve_lpt_amount, lock0.ve_lpt_amount + lock1.ve_lpt_amount);
+    // the legacy fields follow the default position
+    assert_eq!(alice.lpt_amount, lock0.lpt_amount);
+    assert_eq!(alice.unlock_timestamp, lock0.unlock_timestamp);
+    assert_eq!(alice.duration_sec, lock0.duration_sec);
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, alice.ve_lpt_amount);
+    assert_eq!(e.balance_of(&users.alice), alice.ve_lpt_amount);
+
+    // append only moves the addressed position
+    let lock1_ve = lock1.ve_lpt_amount;
+    e.append_lpt_position(&users.alice, 1, to_yocto("100"), 0).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert_eq!(alice.locks.get(&0).unwrap().lpt_amount, to_yocto("100"));
+    assert_eq!(alice.locks.get(&1).unwrap().lpt_amount, to_yocto("200"));
+    assert!(alice.locks.get(&1).unwrap().ve_lpt_amount > lock1_ve);
+    assert_eq!(alice.locks.get(&0).unwrap().ve_lpt_amount, to_ve_token("200"));
+
+    // the short position unlocks first
+    e.skip_time(DEFAULT_MIN_LOCKING_DURATION_SEC);
+    assert_err!(e.withdraw_lpt(&users.alice, None), E305_STILL_IN_LOCK);
+    e.withdraw_lpt_position(&users.alice, 1, None).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert_eq!(alice.locks.len(), 1);
+    assert_eq!(alice.lpt_amounts[&lpt_id()].0, to_yocto("100"));
+    assert_eq!(alice.ve_lpt_amount, to_ve_token("200"));
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, to_ve_token("200"));
+    assert_err!(e.withdraw_lpt_position(&users.alice, 1, None), E105_ACC_NOT_LOCKED);
+}
+
+#[test]
+fn test_lock_lpt_for(){
+    let e = init_env();
+    let users = Users::init(&e);
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("400"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+
+    // error scene 
+    // 1 : E100_ACC_NOT_REGISTERED
+    assert_err!(e.lock_lpt_for(&users.alice, &users.bob, 0, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC), E100_ACC_NOT_REGISTERED);
+
+    // a registered receiver keeps its own sponsor
+    e.storage_deposit(&users.charlie, &users.charlie, to_yocto("0.00125")).assert_success();
+    e.lock_lpt_for(&users.alice, &users.charlie, 0, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    let charlie = e.get_account_info(&users.charlie).unwrap();
+    assert_eq!(charlie.sponsor_id, users.charlie.account_id());
+    assert_eq!(charlie.lpt_amounts[&lpt_id()].0, to_yocto("100"));
+    assert_eq!(e.balance_of(&users.charlie), to_ve_token("200"));
+
+    e.modify_lock_receiver_policy(&e.owner, LockReceiverPolicy::AllowUnregistered).assert_success();
+    assert_eq!(e.get_config().lock_receiver_policy, LockReceiverPolicy::AllowUnregistered);
+    e.lock_lpt_for(&users.alice, &users.bob, 0, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    let bob = e.get_account_info(&users.bob).unwrap();
+    assert_eq!(bob.sponsor_id, users.alice.account_id());
+    assert_eq!(bob.lpt_amounts[&lpt_id()].0, to_yocto("100"));
+    assert_eq!(bob.ve_lpt_amount, to_ve_token("200"));
+    assert_eq!(e.balance_of(&users.bob), to_ve_token("200"));
+    assert!(e.get_account_info(&users.alice).is_none());
+    assert_eq!(e.get_metadata().account_count.0, 2);
+
+    // 2 : E109_EXTEND_OTHERS_LOCK
+    assert_err!(e.lock_lpt_for(&users.alice, &users.bob, 0, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC), E109_EXTEND_OTHERS_LOCK);
+    assert_err!(e.append_lpt_for(&users.alice, &users.bob, 0, to_yocto("100"), DAY_SEC), E109_EXTEND_OTHERS_LOCK);
+
+    // append without extending the unlock time
+    let unlock_timestamp = bob.locks.get(&0).unwrap().unlock_timestamp;
+    e.append_lpt_for(&users.alice, &users.bob, 0, to_yocto("100"), 0).assert_success();
+    let bob = e.get_account_info(&users.bob).unwrap();
+    assert_eq!(bob.lpt_amounts[&lpt_id()].0, to_yocto("200"));
+    assert_eq!(bob.locks.get(&0).unwrap().unlock_timestamp, unlock_timestamp);
+    assert_eq!(e.get_metadata().lock_pools[0].cur_lock_lpt, to_yocto("300"));
+}
+
+#[test]
+fn test_lock_lpt_pools(){
+    let e = init_env();
+    let users = Users::init(&e);
+    let lpt_id_1 = ":1".to_string();
+
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_mint(&"1".to_string(), &users.alice, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    e.mft_storage_deposit(&lpt_id_1, &e.ve_contract.user_account);
+
+    // error scene 
+    // 1 : E600_MFT_INVALID_LPTOKEN_ID
+    assert_err!(e.lock_pool_lpt(&users.alice, &lpt_id_1, 1, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC), E600_MFT_INVALID_LPTOKEN_ID);
+
+    // 2 : E002_NOT_ALLOWED
+    assert_err!(e.register_lock_pool(&users.alice, &lpt_id_1, TOKEN_DECIMALS, BASE_VE_WEIGHT / 2), E002_NOT_ALLOWED);
+
+    // 3 : E309_INVALID_VE_WEIGHT
+    assert_err!(e.register_lock_pool(&e.owner, &lpt_id_1, TOKEN_DECIMALS, 0), E309_INVALID_VE_WEIGHT);
+
+    // 4 : E604_INVALID_LOCK_POOL_DECIMALS
+    assert_err!(e.register_lock_pool(&e.owner, &lpt_id_1, 25, BASE_VE_WEIGHT / 2), E604_INVALID_LOCK_POOL_DECIMALS);
+
+    // 5 : E602_LOCK_POOL_ALREADY_EXIST
+    assert_err!(e.register_lock_pool(&e.owner, &lpt_id(), TOKEN_DECIMALS, BASE_VE_WEIGHT), E602_LOCK_POOL_ALREADY_EXIST);
+
+    e.register_lock_pool(&e.owner, &lpt_id_1, TOKEN_DECIMALS, BASE_VE_WEIGHT / 2).assert_success();
+    e.lock_lpt(&users.alice, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+
+    // 6 : E108_LOCK_POOL_MISMATCH
+    assert_err!(e.lock_pool_lpt(&users.alice, &lpt_id_1, 0, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC), E108_LOCK_POOL_MISMATCH);
+
+    // success, half ve weight
+    e.lock_pool_lpt(&users.alice, &lpt_id_1, 1, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert_eq!(alice.locks.get(&1).unwrap().token_id, lpt_id_1);
+    assert_eq!(alice.locks.get(&1).unwrap().ve_lpt_amount, to_ve_token("100"));
+    assert_eq!(alice.lpt_amounts[&lpt_id()].0, to_yocto("100"));
+    assert_eq!(alice.lpt_amounts[&lpt_id_1].0, to_yocto("100"));
+    assert_eq!(alice.ve_lpt_amount, to_ve_token("300"));
+    let metadata = e.get_metadata();
+    assert_eq!(metadata.cur_total_ve_lpt.0, to_ve_token("300"));
+    let lock_pool: &LockPool = metadata.lock_pools.iter().find(|lock_pool| lock_pool.token_id == lpt_id_1).unwrap();
+    assert_eq!(lock_pool.cur_lock_lpt, to_yocto("100"));
+    assert_eq!(lock_pool.ve_weight, BASE_VE_WEIGHT / 2);
+    // the top level lptoken fields follow the default pool
+    assert_eq!(metadata.lptoken_id, lpt_id());
+    assert_eq!(metadata.lptoken_decimals, TOKEN_DECIMALS);
+    assert_eq!(metadata.cur_lock_lpt.0, to_yocto("100"));
+
+    // weight change only affects later locks
+    e.modify_lock_pool_ve_weight(&e.owner, &lpt_id_1, BASE_VE_WEIGHT * 2).assert_success();
+    assert_eq!(e.get_account_info(&users.alice).unwrap().ve_lpt_amount, to_ve_token("300"));
+
+    // withdraw returns the token of the pool
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
+    e.withdraw_lpt_position(&users.alice, 1, None).assert_success();
+    assert_eq!(e.mft_balance_of(&users.alice, &lpt_id_1), to_yocto("100"));
+    assert_eq!(e.mft_balance_of(&users.alice, &lpt_id()), 0);
+    assert_eq!(e.get_metadata().cur_total_ve_lpt.0, to_ve_token("200"));
+}
+
+#[test]
+fn test_lock_ft(){
+    let e = init_env();
+    let users = Users::init(&e);
+    let tokens = Tokens::init(&e);
+    let nref_id = tokens.nref.account_id().to_string();
+
+    e.ft_mint(&tokens.nref, &users.alice, to_ve_token("300"));
+
+    // error scene 
+    // 1 : E603_FT_INVALID_LOCK_TOKEN
+    assert_err!(e.lock_ft(&tokens.nref, &users.alice, 0, to_ve_token("100"), DEFAULT_MAX_LOCKING_DURATION_SEC), E603_FT_INVALID_LOCK_TOKEN);
+
+    // 2 : E002_NOT_ALLOWED
+    assert_err!(e.register_ft_lock_pool(&users.alice, &tokens.nref, 18, BASE_VE_WEIGHT), E002_NOT_ALLOWED);
+
+    // 3 : E604_INVALID_LOCK_POOL_DECIMALS
+    assert_err!(e.register_ft_lock_pool(&e.owner, &tokens.nref, 25, BASE_VE_WEIGHT), E604_INVALID_LOCK_POOL_DECIMALS);
+
+    e.register_ft_lock_pool(&e.owner, &tokens.nref, 18, BASE_VE_WEIGHT).assert_success();
+
+    // 4 : E600_MFT_INVALID_LPTOKEN_ID, only mft pools are lockable by mft_transfer_call
+    e.mft_mint(&lpt_inner_id(), &users.alice, to_yocto("100"));
+    e.mft_storage_deposit(&lpt_id(), &e.ve_contract.user_account);
+    assert_err!(e.lock_pool_lpt(&users.alice, &nref_id, 0, to_yocto("100"), DEFAULT_MAX_LOCKING_DURATION_SEC), E600_MFT_INVALID_LPTOKEN_ID);
+
+    // success
+    e.lock_ft(&tokens.nref, &users.alice, 0, to_ve_token("100"), DEFAULT_MAX_LOCKING_DURATION_SEC).assert_success();
+    e.append_ft(&tokens.nref, &users.alice, 0, to_ve_token("100"), 0).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_ve_token("100"));
+    let alice = e.get_account_info(&users.alice).unwrap();
+    assert_eq!(alice.lpt_amounts[&nref_id].0, to_ve_token("200"));
+    assert_eq!(alice.ve_lpt_amount, to_ve_token("400"));
+    let metadata = e.get_metadata();
+    let lock_pool: &LockPool = metadata.lock_pools.iter().find(|lock_pool| lock_pool.token_id == nref_id).unwrap();
+    assert_eq!(lock_pool.kind, LockPoolKind::FungibleToken);
+    assert_eq!(lock_pool.cur_lock_lpt, to_ve_token("200"));
+
+    // withdraw through ft_transfer
+    e.skip_time(DEFAULT_MAX_LOCKING_DURATION_SEC);
+    e.withdraw_lpt(&users.alice, Some(to_ve_token("50"))).assert_success();
+    assert_eq!(e.ft_balance_of(&tokens.nref, &users.alice), to_ve_token("150"));
+
+    // failed transfer goes to lostfound of the pool
+    e.ft_storage_unregister(&tokens.nref, &users.alice);
+    e.withdraw_lpt(&users.alice, None).assert_success();
+    let metadata = e.get_metadata();
+    let lock_pool: &LockPool = metadata.lock_pools.iter().find(|lock_pool| lock_pool.token_id == nref_id).unwrap();
+    assert_eq!(lock_pool.cur_lock_lpt, 0);
+    assert_eq!(lock_pool.lostfound, to_ve_token("150"));
+}
+
 #[test]
 fn test_deposit_reward() {
     let e = init_env();
@@ -88,7 +500,18 @@ fn test_deposit_reward() {
     assert_err!(e.deposit_reward(&tokens.nusdc, &users.alice, to_yocto("100"), 1, 0), E203_INVALID_INCENTIVE_TOKEN);
     assert_err!(e.deposit_reward(&tokens.nusdc, &users.alice, to_yocto("100"), 2, 0), E203_INVALID_INCENTIVE_TOKEN);
 
-    // 3 : E406_EXPIRED_PROPOSAL
+    // 3 : E209_INCENTIVE_DEPOSIT_TOO_SMALL
+    assert_err!(e.modify_min_incentive_deposits(&users.alice, vec![(tokens.nref.account_id(), to_yocto("1").into())]), E002_NOT_ALLOWED);
+    e.modify_min_incentive_deposits(&e.owner, vec![(tokens.nref.account_id(), to_yocto("1").into())]).assert_success();
+    assert_eq!(HashMap::from([(tokens.nref.account_id(), to_yocto("1"))]), e.get_metadata().min_incentive_deposits);
+    assert_err!(e.deposit_reward(&tokens.nref, &users.alice, 1, 2, 0), E209_INCENTIVE_DEPOSIT_TOO_SMALL);
+    e.deposit_reward(&tokens.nref, &users.alice, to_yocto("1"), 2, 0).assert_success();
+    e.deposit_reward(&tokens.wnear, &users.alice, 1, 2, 0).assert_success();
+    e.modify_min_incentive_deposits(&e.owner, vec![(tokens.nref.account_id(), 0.into())]).assert_success();
+    assert!(e.get_metadata().min_incentive_deposits.is_empty());
+    e.deposit_reward(&tokens.nref, &users.alice, 1, 2, 0).assert_success();
+
+    // 4 : E406_EXPIRED_PROPOSAL
     e.skip_time(DEFAULT_MIN_VOTING_DURATION_SEC);
     assert_err!(e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 0, 0), E406_EXPIRED_PROPOSAL);
     assert_err!(e.deposit_reward(&tokens.nref, &users.alice, to_yocto("100"), 1, 0), E406_EXPIRED_PROPOSAL);